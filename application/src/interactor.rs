@@ -1,6 +1,7 @@
 mod account;
 mod client;
 mod mfa_code;
+mod signing_key;
 mod token;
 
-pub use self::{account::*, client::*, mfa_code::*, token::*};
+pub use self::{account::*, client::*, mfa_code::*, signing_key::*, token::*};
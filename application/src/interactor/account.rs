@@ -1,9 +1,10 @@
 use crate::services::{
     CreateAccountService, CreateTemporaryAccountService, DeleteAccountService,
-    DependOnVerifyMFACodeService, UpdateAccountService, VerifyAccountService,
+    DependOnVerifyMFACodeService, UpdateAccountService, UserInfoService, VerifyAccountService,
 };
 use kernel::interfaces::repository::{
-    DependOnAccountRepository, DependOnSessionVolatileRepository,
+    DependOnAccessTokenRepository, DependOnAccountRepository, DependOnDPoPJtiVolatileRepository,
+    DependOnDPoPNonceVolatileRepository, DependOnSessionVolatileRepository,
     DependOnTemporaryAccountRepository,
 };
 use kernel::interfaces::transport::DependOnVerificationMailTransporter;
@@ -38,3 +39,12 @@ impl<T> VerifyAccountService for T where
         + DependOnVerifyMFACodeService
 {
 }
+
+// Default Impl
+impl<T> UserInfoService for T where
+    T: DependOnAccessTokenRepository
+        + DependOnAccountRepository
+        + DependOnDPoPJtiVolatileRepository
+        + DependOnDPoPNonceVolatileRepository
+{
+}
@@ -3,21 +3,24 @@ use kernel::{
     external::Uuid,
     interfaces::repository::{
         AccountRepository, ClientRegistry, DependOnAccountRepository, DependOnClientRegistry,
+        DependOnJtiVolatileRepository,
     },
+    interfaces::transport::DependOnJwksTransporter,
     prelude::entities::{
-        Address, Client, ClientDescription, ClientId, ClientName, ClientSecret, ClientTypes,
-        ClientUri, Contacts, GrantType, GrantTypes, Jwks, LogoUri, PolicyUri, RedirectUri,
-        RedirectUris, RegistrationAccessToken, RegistrationEndPoint, ResponseType, ResponseTypes,
-        ScopeDescription, ScopeMethod, Scopes, TermsUri, TokenEndPointAuthMethod, UserId,
+        AccessTokenFormat, Address, Client, ClientDescription, ClientId, ClientName, ClientSecret,
+        ClientTypes, ClientUri, Contacts, GrantType, GrantTypes, Jwks, LogoUri, PolicyUri,
+        RedirectUri, RedirectUris, RegistrationAccessToken, RegistrationEndPoint, ResponseType,
+        ResponseTypes, ScopeDescription, ScopeMethod, Scopes, TermsUri, TokenEndPointAuthMethod,
+        UserId,
     },
 };
 
-use crate::services::DeleteClientService;
+use crate::services::{AuthenticateClientService, DeleteClientService};
 use crate::{
     services::{RegisterClientService, UpdateClientService},
     transfer::client::{
-        ClientDto, GrantTypeDto, RegisterClientDto, ResponseTypeDto, TokenEndPointAuthMethodDto,
-        UpdateClientDto,
+        AccessTokenFormatDto, ClientDto, GrantTypeDto, RegisterClientDto, ResponseTypeDto,
+        TokenEndPointAuthMethodDto, UpdateClientDto,
     },
     ApplicationError,
 };
@@ -89,6 +92,12 @@ where
             contacts,
             jwks,
             jwks_uri,
+            access_token_format,
+            require_pushed_authorization_requests,
+            require_signed_request_object,
+            request_uris,
+            tls_client_auth_subject,
+            token_exchange_audiences,
         } = register;
 
         let owner = UserId::new(owner_id);
@@ -103,10 +112,11 @@ where
 
         let owner = owner.into_destruct();
 
-        let types = if auth_method != TokenEndPointAuthMethodDto::None {
-            ClientTypes::new(ClientSecret::default())
+        let (secret, types) = if auth_method != TokenEndPointAuthMethodDto::None {
+            let (plain, secret) = ClientSecret::generate(None)?;
+            (Some(plain), ClientTypes::new(secret))
         } else {
-            ClientTypes::new(None)
+            (None, ClientTypes::new(None))
         };
 
         let client_id = ClientId::new_at_now(Uuid::new_v4());
@@ -125,6 +135,10 @@ where
             }
             TokenEndPointAuthMethodDto::None => TokenEndPointAuthMethod::None,
             TokenEndPointAuthMethodDto::PrivateKeyJWT => TokenEndPointAuthMethod::PrivateKeyJWT,
+            TokenEndPointAuthMethodDto::TlsClientAuth => TokenEndPointAuthMethod::TlsClientAuth,
+            TokenEndPointAuthMethodDto::SelfSignedTlsClientAuth => {
+                TokenEndPointAuthMethod::SelfSignedTlsClientAuth
+            }
         };
         let grant_types = grant_types
             .into_iter()
@@ -136,6 +150,8 @@ where
                 GrantTypeDto::RefreshToken => GrantType::RefreshToken,
                 GrantTypeDto::JWTBearer => GrantType::JWTBearer,
                 GrantTypeDto::Saml2Bearer => GrantType::Saml2Bearer,
+                GrantTypeDto::DeviceCode => GrantType::DeviceCode,
+                GrantTypeDto::TokenExchange => GrantType::TokenExchange,
             })
             .collect::<GrantTypes>();
 
@@ -190,9 +206,25 @@ where
             conf_endpoint,
         )?;
 
+        let mut client = client.into_destruct();
+        client.access_token_format = match access_token_format {
+            AccessTokenFormatDto::Opaque => AccessTokenFormat::Opaque,
+            AccessTokenFormatDto::Jwt => AccessTokenFormat::Jwt,
+        };
+        client.require_pushed_authorization_requests = require_pushed_authorization_requests;
+        client.require_signed_request_object = require_signed_request_object;
+        client.request_uris = request_uris;
+        client.tls_client_auth_subject = tls_client_auth_subject.map(Into::into);
+        client.token_exchange_audiences = token_exchange_audiences;
+        let client = client.freeze();
+
         self.client_registry().register(&client).await?;
 
-        Ok(client.into())
+        // This is the only time the plain secret is available.
+        let mut client = ClientDto::from(client);
+        client.secret = secret;
+
+        Ok(client)
     }
 }
 
@@ -299,6 +331,12 @@ where
             scopes,
             contacts,
             jwks,
+            access_token_format,
+            require_pushed_authorization_requests,
+            require_signed_request_object,
+            request_uris,
+            tls_client_auth_subject,
+            token_exchange_audiences,
         } = update;
 
         before.name = ClientName::new(name);
@@ -318,6 +356,10 @@ where
             }
             TokenEndPointAuthMethodDto::None => TokenEndPointAuthMethod::None,
             TokenEndPointAuthMethodDto::PrivateKeyJWT => TokenEndPointAuthMethod::PrivateKeyJWT,
+            TokenEndPointAuthMethodDto::TlsClientAuth => TokenEndPointAuthMethod::TlsClientAuth,
+            TokenEndPointAuthMethodDto::SelfSignedTlsClientAuth => {
+                TokenEndPointAuthMethod::SelfSignedTlsClientAuth
+            }
         };
 
         before.grant_types = grant_types
@@ -330,6 +372,8 @@ where
                 GrantTypeDto::RefreshToken => GrantType::RefreshToken,
                 GrantTypeDto::JWTBearer => GrantType::JWTBearer,
                 GrantTypeDto::Saml2Bearer => GrantType::Saml2Bearer,
+                GrantTypeDto::DeviceCode => GrantType::DeviceCode,
+                GrantTypeDto::TokenExchange => GrantType::TokenExchange,
             })
             .collect::<GrantTypes>();
 
@@ -360,6 +404,16 @@ where
 
         before.jwks = jwks.map(Jwks::new).transpose()?;
 
+        before.access_token_format = match access_token_format {
+            AccessTokenFormatDto::Opaque => AccessTokenFormat::Opaque,
+            AccessTokenFormatDto::Jwt => AccessTokenFormat::Jwt,
+        };
+        before.require_pushed_authorization_requests = require_pushed_authorization_requests;
+        before.require_signed_request_object = require_signed_request_object;
+        before.request_uris = request_uris;
+        before.tls_client_auth_subject = tls_client_auth_subject.map(Into::into);
+        before.token_exchange_audiences = token_exchange_audiences;
+
         let after = before.freeze();
 
         self.client_registry().update(&after).await?;
@@ -370,3 +424,8 @@ where
 
 // Default Impl
 impl<T> DeleteClientService for T where T: DependOnClientRegistry + DependOnAccountRepository {}
+
+impl<T> AuthenticateClientService for T where
+    T: DependOnClientRegistry + DependOnJwksTransporter + DependOnJtiVolatileRepository
+{
+}
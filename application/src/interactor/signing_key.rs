@@ -0,0 +1,6 @@
+use crate::services::{GetJwkSetService, RotateSigningKeyService};
+use kernel::interfaces::repository::DependOnSigningKeyRepository;
+
+impl<T> RotateSigningKeyService for T where T: DependOnSigningKeyRepository {}
+
+impl<T> GetJwkSetService for T where T: DependOnSigningKeyRepository {}
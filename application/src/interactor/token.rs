@@ -1,25 +1,58 @@
 use crate::services::{
-    AcceptAuthorizeTokenService, PendingAuthorizeTokenService, RejectAuthorizeTokenService,
+    AcceptAuthorizeTokenService, AuthorizationResponseService, ClientCredentialsService,
+    CreateAccessTokenService, DPoPService, DeviceAccessTokenService, DeviceAuthorizationService,
+    FormatAccessTokenService, IntrospectTokenService, JwtBearerService,
+    PendingAuthorizeTokenService, ProtectedResourceService, PushAuthorizationRequestService,
+    RefreshAccessTokenService, RejectAuthorizeTokenService, RequestObjectService,
+    RevokeTokenService, Saml2BearerService, SignIdTokenService, TokenExchangeService,
+    VerifyUserCodeService,
 };
 use kernel::interfaces::repository::{
-    DependOnAccountRepository, DependOnAuthorizeTokenRepository, DependOnClientRegistry,
+    DependOnAccessTokenRepository, DependOnAccountRepository, DependOnAuthorizeTokenRepository,
+    DependOnClientRegistry, DependOnDPoPJtiVolatileRepository, DependOnDPoPNonceVolatileRepository,
+    DependOnDeviceAuthorizationVolatileRepository, DependOnJwtBearerJtiVolatileRepository,
     DependOnPKCEVolatileRepository, DependOnPendingAuthorizeTokenRepository,
-    DependOnStateVolatileRepository,
+    DependOnProtectedResourceRepository, DependOnPushedAuthorizationRequestVolatileRepository,
+    DependOnRefreshTokenRepository, DependOnSamlAssertionIdVolatileRepository,
+    DependOnSessionVolatileRepository, DependOnSigningKeyRepository,
+    DependOnStateVolatileRepository, DependOnTrustedIssuerRepository,
 };
+use kernel::interfaces::transport::{DependOnJwksTransporter, DependOnRequestObjectTransporter};
 
 impl<T> PendingAuthorizeTokenService for T where
     T: DependOnClientRegistry
         + DependOnPKCEVolatileRepository
         + DependOnPendingAuthorizeTokenRepository
         + DependOnStateVolatileRepository
+        + DependOnPushedAuthorizationRequestVolatileRepository
+        + DependOnJwksTransporter
+        + DependOnRequestObjectTransporter
+        + DependOnProtectedResourceRepository
 {
 }
 
+impl<T> PushAuthorizationRequestService for T where
+    T: DependOnClientRegistry
+        + DependOnPushedAuthorizationRequestVolatileRepository
+        + DependOnJwksTransporter
+        + DependOnRequestObjectTransporter
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> RequestObjectService for T where
+    T: DependOnJwksTransporter + DependOnRequestObjectTransporter
+{
+}
+
+impl<T> ProtectedResourceService for T where T: DependOnProtectedResourceRepository {}
+
 impl<T> AcceptAuthorizeTokenService for T where
     T: DependOnAccountRepository
         + DependOnStateVolatileRepository
         + DependOnPendingAuthorizeTokenRepository
         + DependOnAuthorizeTokenRepository
+        + DependOnSigningKeyRepository
 {
 }
 
@@ -27,5 +60,110 @@ impl<T> RejectAuthorizeTokenService for T where
     T: DependOnPendingAuthorizeTokenRepository
         + DependOnPKCEVolatileRepository
         + DependOnStateVolatileRepository
+        + DependOnSigningKeyRepository
+{
+}
+
+impl<T> DPoPService for T where
+    T: DependOnDPoPJtiVolatileRepository + DependOnDPoPNonceVolatileRepository
+{
+}
+
+impl<T> AuthorizationResponseService for T where T: DependOnSigningKeyRepository {}
+
+impl<T> FormatAccessTokenService for T where T: DependOnSigningKeyRepository {}
+
+impl<T> SignIdTokenService for T where T: DependOnSigningKeyRepository {}
+
+impl<T> CreateAccessTokenService for T where
+    T: DependOnClientRegistry
+        + DependOnAuthorizeTokenRepository
+        + DependOnPKCEVolatileRepository
+        + DependOnAccessTokenRepository
+        + DependOnRefreshTokenRepository
+        + FormatAccessTokenService
+        + SignIdTokenService
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> RefreshAccessTokenService for T where
+    T: DependOnClientRegistry
+        + DependOnRefreshTokenRepository
+        + DependOnAccessTokenRepository
+        + FormatAccessTokenService
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> ClientCredentialsService for T where
+    T: DependOnClientRegistry
+        + DependOnAccessTokenRepository
+        + FormatAccessTokenService
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> JwtBearerService for T where
+    T: DependOnClientRegistry
+        + DependOnTrustedIssuerRepository
+        + DependOnJwtBearerJtiVolatileRepository
+        + DependOnJwksTransporter
+        + DependOnAccessTokenRepository
+        + FormatAccessTokenService
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> Saml2BearerService for T where
+    T: DependOnClientRegistry
+        + DependOnTrustedIssuerRepository
+        + DependOnSamlAssertionIdVolatileRepository
+        + DependOnAccountRepository
+        + DependOnAccessTokenRepository
+        + FormatAccessTokenService
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> TokenExchangeService for T where
+    T: DependOnClientRegistry
+        + DependOnAccessTokenRepository
+        + FormatAccessTokenService
+        + DependOnProtectedResourceRepository
+{
+}
+
+impl<T> IntrospectTokenService for T where
+    T: DependOnClientRegistry + DependOnAccessTokenRepository + DependOnRefreshTokenRepository
+{
+}
+
+impl<T> RevokeTokenService for T where
+    T: DependOnAccessTokenRepository + DependOnRefreshTokenRepository
+{
+}
+
+impl<T> DeviceAuthorizationService for T where
+    T: DependOnClientRegistry + DependOnDeviceAuthorizationVolatileRepository
+{
+}
+
+impl<T> VerifyUserCodeService for T where
+    T: DependOnSessionVolatileRepository
+        + DependOnDeviceAuthorizationVolatileRepository
+        + DependOnPendingAuthorizeTokenRepository
+        + DependOnStateVolatileRepository
+{
+}
+
+impl<T> DeviceAccessTokenService for T where
+    T: DependOnClientRegistry
+        + DependOnDeviceAuthorizationVolatileRepository
+        + DependOnPendingAuthorizeTokenRepository
+        + DependOnAuthorizeTokenRepository
+        + DependOnAccessTokenRepository
+        + DependOnRefreshTokenRepository
+        + FormatAccessTokenService
 {
 }
@@ -1,14 +1,16 @@
 use kernel::external::{Duration, OffsetDateTime, Uuid};
 use kernel::interfaces::repository::{
-    AcceptedActionVolatileRepository, AccountRepository, DependOnAcceptedActionVolatileRepository,
+    AcceptedActionVolatileRepository, AccessTokenRepository, AccountRepository,
+    DependOnAcceptedActionVolatileRepository, DependOnAccessTokenRepository,
     DependOnAccountRepository, DependOnMFACodeVolatileRepository,
     DependOnPendingActionVolatileRepository, DependOnSessionVolatileRepository,
     DependOnTemporaryAccountRepository, MFACodeVolatileRepository, PendingActionVolatileRepository,
     SessionVolatileRepository, TemporaryAccountRepository,
 };
 use kernel::prelude::entities::{
-    Account, Address, EstablishedAt, MFACode, Password, Session, SessionId, TemporaryAccount,
-    TicketId, UpdatedAt, UserId, UserName,
+    AccessTokenId, Account, Address, Confirmation, DestructAccount, EstablishedAt, MFACode,
+    Password, ScopeMethod, Session, SessionId, TemporaryAccount, TicketId, UpdatedAt, UserId,
+    UserName,
 };
 use kernel::KernelError;
 
@@ -17,12 +19,13 @@ use kernel::interfaces::transport::{
     DependOnVerificationMailTransporter, VerificationMailTransporter,
 };
 
-use crate::services::DependOnVerifyMFACodeService;
+use crate::services::{DPoPService, DependOnVerifyMFACodeService};
 use crate::transfer::mfa_code::TicketIdDto;
+use crate::transfer::token::DPoPProofDto;
 use crate::{
     transfer::{
         account::{
-            AccountDto, CreateAccountDto, CreateTemporaryAccountDto, UpdateAccountDto,
+            AccountDto, CreateAccountDto, CreateTemporaryAccountDto, UpdateAccountDto, UserInfoDto,
             VerifyAccountDto,
         },
         session::SessionDto,
@@ -324,3 +327,114 @@ pub trait DependOnVerifyAccountService: 'static + Send + Sync {
     type VerifyAccountService: VerifyAccountService;
     fn verify_account_service(&self) -> &Self::VerifyAccountService;
 }
+
+#[async_trait::async_trait]
+pub trait UserInfoService:
+    'static + Send + Sync + DependOnAccessTokenRepository + DependOnAccountRepository + DPoPService
+{
+    /// Returns claims about the resource owner who authorized the access token,
+    /// filtered by the scopes granted to it.
+    ///
+    /// See [OpenID Connect Core 1.0 Section 5.3](https://openid.net/specs/openid-connect-core-1_0.html#UserInfo)
+    ///
+    /// A token bound to a DPoP key is only accepted with a proof of that key.
+    ///
+    /// See [RFC9449 Section 7.1](https://datatracker.ietf.org/doc/html/rfc9449#section-7.1)
+    ///
+    /// Likewise, a token bound to a client certificate is only accepted over
+    /// a mutual-TLS connection with that certificate, whose thumbprint is `x5t_s256`.
+    ///
+    /// See [RFC8705 Section 3](https://datatracker.ietf.org/doc/html/rfc8705#section-3)
+    async fn userinfo(
+        &self,
+        token: &str,
+        dpop: Option<DPoPProofDto>,
+        x5t_s256: Option<String>,
+    ) -> Result<UserInfoDto, ApplicationError> {
+        let dpop_jkt = match dpop {
+            Some(dpop) => Some(self.verify_dpop_proof(dpop).await?),
+            None => None,
+        };
+
+        let Some(token) = self
+            .access_token_repository()
+            .find_by_id(&AccessTokenId::new(token))
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_token",
+                value: "access token is invalid or revoked.".to_string(),
+            });
+        };
+
+        let ctx = token.context();
+        if ctx.expired_in().is_expired() || OffsetDateTime::now_utc() < *ctx.not_before().as_ref() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_token",
+                value: "access token is expired.".to_string(),
+            });
+        }
+
+        if ctx.confirmation().as_ref().and_then(Confirmation::jkt) != dpop_jkt.as_deref() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_token",
+                value: "access token is not presented with the DPoP key it is bound to."
+                    .to_string(),
+            });
+        }
+
+        if let Some(bound) = ctx.confirmation().as_ref().and_then(Confirmation::x5t_s256) {
+            if x5t_s256.as_deref() != Some(bound) {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_token",
+                    value: "access token is not presented with the certificate it is bound to."
+                        .to_string(),
+                });
+            }
+        }
+
+        if !ctx.scope().iter().any(ScopeMethod::is_openid) {
+            return Err(ApplicationError::InvalidValue {
+                method: "insufficient_scope",
+                value: format!("`{}` scope is required.", ScopeMethod::OPENID),
+            });
+        }
+
+        let Some(account) = ctx.account() else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_token",
+                value: "access token was not issued for a resource owner.".to_string(),
+            });
+        };
+
+        let Some(account) = self.account_repository().find_by_id(account).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_token",
+                value: "resource owner no longer exists.".to_string(),
+            });
+        };
+
+        let granted = |scope: &str| ctx.scope().iter().any(|granted| granted.as_ref() == scope);
+        let profile = granted(ScopeMethod::PROFILE);
+        let email = granted(ScopeMethod::EMAIL);
+        let verified = account.is_verified();
+
+        let DestructAccount {
+            id, address, name, ..
+        } = account.into_destruct();
+        let name = String::from(name);
+
+        Ok(UserInfoDto {
+            sub: id.to_string(),
+            name: profile.then(|| name.clone()),
+            preferred_username: profile.then_some(name),
+            email: email.then(|| address.into()),
+            email_verified: email.then_some(verified),
+        })
+    }
+}
+
+pub trait DependOnUserInfoService: 'static + Send + Sync {
+    type UserInfoService: UserInfoService;
+    fn userinfo_service(&self) -> &Self::UserInfoService;
+}
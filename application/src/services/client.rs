@@ -1,10 +1,18 @@
-use crate::transfer::client::{ClientDto, RegisterClientDto, UpdateClientDto};
+use crate::transfer::client::{
+    ClientAuthenticationDto, ClientDto, RegisterClientDto, TokenEndPointAuthMethodDto,
+    UpdateClientDto,
+};
 use crate::ApplicationError;
 use kernel::external::Uuid;
 use kernel::interfaces::repository::{
     AccountRepository, ClientRegistry, DependOnAccountRepository, DependOnClientRegistry,
+    DependOnJtiVolatileRepository, JtiVolatileRepository,
+};
+use kernel::interfaces::transport::{DependOnJwksTransporter, JwksTransporter};
+use kernel::prelude::entities::{
+    ClientCertificate, ClientId, ClientJwkSet, ClientSecret, ClientSignJwt, ClientTypes, Issuer,
+    Jwks, TokenEndPointAuthMethod,
 };
-use kernel::prelude::entities::{ClientId, ClientTypes};
 
 #[async_trait::async_trait]
 pub trait RegisterClientService:
@@ -93,3 +101,192 @@ pub trait DependOnDeleteClientService: 'static + Sync + Send {
     type DeleteClientService: DeleteClientService;
     fn delete_client_service(&self) -> &Self::DeleteClientService;
 }
+
+#[async_trait::async_trait]
+pub trait AuthenticateClientService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnJwksTransporter
+    + DependOnJtiVolatileRepository
+{
+    /// Authenticates the client by the method registered for it.
+    ///
+    /// Only the registered method is accepted, so a confidential client
+    /// cannot fall back to presenting its `client_id` alone.
+    ///
+    /// See [RFC6749 Section 2.3.1](https://datatracker.ietf.org/doc/html/rfc6749#section-2.3.1)
+    /// and [RFC7523 Section 3](https://datatracker.ietf.org/doc/html/rfc7523#section-3)
+    /// and [RFC8705 Section 2](https://datatracker.ietf.org/doc/html/rfc8705#section-2)
+    async fn authenticate(&self, auth: ClientAuthenticationDto) -> Result<(), ApplicationError> {
+        let ClientAuthenticationDto {
+            client_id,
+            method,
+            secret,
+            assertion,
+            certificate,
+        } = auth;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        // A certificate alone is presented the same way for both mTLS methods,
+        // and public clients may present one only to have their tokens bound to it.
+        // https://datatracker.ietf.org/doc/html/rfc8705#section-4
+        let method = match (method, client.auth_method()) {
+            (
+                TokenEndPointAuthMethodDto::TlsClientAuth,
+                registered @ (TokenEndPointAuthMethod::SelfSignedTlsClientAuth
+                | TokenEndPointAuthMethod::None),
+            ) => TokenEndPointAuthMethodDto::from(registered.clone()),
+            (method, _) => method,
+        };
+
+        let registered = TokenEndPointAuthMethodDto::from(client.auth_method().clone());
+        if registered.ne(&method) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: format!(
+                    "client must authenticate with `{}`.",
+                    client.auth_method().as_ref()
+                ),
+            });
+        }
+
+        match method {
+            TokenEndPointAuthMethodDto::ClientSecretBasic
+            | TokenEndPointAuthMethodDto::ClientSecretPost => {
+                let (ClientTypes::Confidential(registered), Some(secret)) =
+                    (client.types(), secret)
+                else {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "client authentication failed.".to_string(),
+                    });
+                };
+                if registered.is_expired() {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "client secret has expired.".to_string(),
+                    });
+                }
+                if registered.verify(secret.as_str()).is_err() {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "client authentication failed.".to_string(),
+                    });
+                }
+                // Secrets digested by the migration are hashed with Argon2 on their first use.
+                if registered.needs_rehash() {
+                    let rehashed = ClientSecret::new(secret, *registered.expires_at())?;
+                    let mut client = client.clone().into_destruct();
+                    client.types = ClientTypes::new(rehashed);
+                    self.client_registry().update(&client.freeze()).await?;
+                }
+            }
+            TokenEndPointAuthMethodDto::None => {}
+            TokenEndPointAuthMethodDto::PrivateKeyJWT => {
+                let Some(assertion) = assertion else {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "`client_assertion` is required.".to_string(),
+                    });
+                };
+
+                let keys = match client.jwks() {
+                    Some(Jwks::Key(key)) => ClientJwkSet::try_from(key)?,
+                    Some(Jwks::Uri(uri)) => self.jwks_transporter().fetch(uri).await?,
+                    None => {
+                        return Err(ApplicationError::InvalidValue {
+                            method: "invalid_client",
+                            value: "client has no registered `jwks`.".to_string(),
+                        })
+                    }
+                };
+
+                // The assertion is addressed to the token endpoint.
+                let audience = format!("{}/token", Issuer::default().as_ref());
+
+                let assertion = ClientSignJwt::new(assertion)
+                    .verify(&keys, client.id(), &audience)
+                    .map_err(|e| ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: e.to_string(),
+                    })?;
+
+                if !self.jti_volatile_repository().save(&assertion).await? {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "`jti` of client assertion has already been used.".to_string(),
+                    });
+                }
+            }
+            TokenEndPointAuthMethodDto::TlsClientAuth => {
+                let certificate = required_certificate(certificate)?;
+                let Some(subject) = client.tls_client_auth_subject() else {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "client has no registered certificate subject.".to_string(),
+                    });
+                };
+                if !certificate.matches(subject) {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: format!(
+                            "certificate does not match the registered `{}`.",
+                            subject.name()
+                        ),
+                    });
+                }
+            }
+            TokenEndPointAuthMethodDto::SelfSignedTlsClientAuth => {
+                let certificate = required_certificate(certificate)?;
+                let keys = match client.jwks() {
+                    Some(Jwks::Key(key)) => ClientJwkSet::try_from(key)?,
+                    Some(Jwks::Uri(uri)) => self.jwks_transporter().fetch(uri).await?,
+                    None => {
+                        return Err(ApplicationError::InvalidValue {
+                            method: "invalid_client",
+                            value: "client has no registered `jwks`.".to_string(),
+                        })
+                    }
+                };
+                if !keys.contains_certificate(&certificate) {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_client",
+                        value: "certificate is not registered in the client `jwks`.".to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn required_certificate(
+    certificate: Option<String>,
+) -> Result<ClientCertificate, ApplicationError> {
+    let Some(certificate) = certificate else {
+        return Err(ApplicationError::InvalidValue {
+            method: "invalid_client",
+            value: "client certificate is required.".to_string(),
+        });
+    };
+    ClientCertificate::from_pem(&certificate).map_err(|e| ApplicationError::InvalidValue {
+        method: "invalid_client",
+        value: e.to_string(),
+    })
+}
+
+pub trait DependOnAuthenticateClientService: 'static + Sync + Send {
+    type AuthenticateClientService: AuthenticateClientService;
+    fn authenticate_client_service(&self) -> &Self::AuthenticateClientService;
+}
@@ -0,0 +1,91 @@
+use crate::transfer::signing_key::JwkSetDto;
+use crate::ApplicationError;
+use kernel::external::OffsetDateTime;
+use kernel::interfaces::repository::{DependOnSigningKeyRepository, SigningKeyRepository};
+use kernel::prelude::entities::{KeyState, SigningAlgorithm, SigningKey};
+
+#[async_trait::async_trait]
+pub trait RotateSigningKeyService: 'static + Sync + Send + DependOnSigningKeyRepository {
+    /// Keeps one `Active` and one `Next` key, rotating them every
+    /// [SigningKey::ROTATION_PERIOD] and deleting `Retired` keys after
+    /// [SigningKey::RETENTION_PERIOD].
+    ///
+    /// This is meant to be called periodically, possibly by several instances at once,
+    /// in which case only the first one rotates the keys.
+    async fn rotate(&self) -> Result<(), ApplicationError> {
+        let now = OffsetDateTime::now_utc();
+        let keys = self.signing_key_repository().find_all().await?;
+
+        let mut active = None;
+        let mut next = None;
+        for key in keys {
+            match key.state() {
+                KeyState::Active => active = Some(key),
+                KeyState::Next => next = Some(key),
+                KeyState::Retired if key.is_expired(now) => {
+                    self.signing_key_repository().delete(key.kid()).await?;
+                }
+                KeyState::Retired => {}
+            }
+        }
+
+        let current_active = active.as_ref().map(|key| key.kid().clone());
+        let current_next = next.as_ref().map(|key| key.kid().clone());
+
+        let keys = match (active, next) {
+            (Some(active), Some(_)) if !active.is_due(now) => return Ok(()),
+            (Some(active), None) if !active.is_due(now) => {
+                vec![SigningKey::generate(SigningAlgorithm::default())?]
+            }
+            (active, next) => {
+                let next = match next {
+                    Some(next) => next,
+                    None => SigningKey::generate(SigningAlgorithm::default())?,
+                };
+                // Retired first, as only one key can be `Active` and one `Next` at a time.
+                let mut keys = active
+                    .map(|active| active.retire(now))
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                keys.push(next.activate(now));
+                keys.push(SigningKey::generate(SigningAlgorithm::default())?);
+                keys
+            }
+        };
+
+        // `false` means another caller has rotated the keys, which is just as good.
+        self.signing_key_repository()
+            .rotate(current_active, current_next, &keys)
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub trait DependOnRotateSigningKeyService: 'static + Sync + Send {
+    type RotateSigningKeyService: RotateSigningKeyService;
+    fn rotate_signing_key_service(&self) -> &Self::RotateSigningKeyService;
+}
+
+#[async_trait::async_trait]
+pub trait GetJwkSetService: 'static + Sync + Send + DependOnSigningKeyRepository {
+    /// Public keys of all signing keys: the `Next` key, so that resource servers
+    /// caching the set can verify tokens right after a rotation,
+    /// and `Retired` keys, so that tokens signed before a rotation can still be verified.
+    async fn jwks(&self) -> Result<JwkSetDto, ApplicationError> {
+        let keys = self
+            .signing_key_repository()
+            .find_all()
+            .await?
+            .iter()
+            .map(SigningKey::public_document)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(JwkSetDto { keys })
+    }
+}
+
+pub trait DependOnGetJwkSetService: 'static + Sync + Send {
+    type GetJwkSetService: GetJwkSetService;
+    fn get_jwk_set_service(&self) -> &Self::GetJwkSetService;
+}
@@ -1,20 +1,59 @@
-use crate::transfer::mfa_code::TicketIdDto;
-use crate::transfer::token::{AcceptUserFormDto, AuthorizeTokenDto, CreateAuthorizeTokenDto};
-use crate::ApplicationError;
+use crate::transfer::token::{
+    AcceptUserFormDto, AccessTokenDto, AuthorizationResponseDto, ClientCredentialsDto,
+    CreateAccessTokenDto, CreateAuthorizeTokenDto, DPoPProofDto, DecisionDto, DeviceAccessTokenDto,
+    DeviceAuthorizationDto, DeviceAuthorizationRequestDto, DeviceDecisionDto, IntrospectTokenDto,
+    JwtBearerDto, PendingAuthorizationDto, PushedAuthorizationRequestDto, PushedAuthorizeTokenDto,
+    RefreshAccessTokenDto, RevokeTokenDto, Saml2BearerDto, SignedAuthorizeTokenDto,
+    TokenExchangeDto, TokenIntrospectionDto, VerifyUserCodeDto,
+};
+use crate::{ApplicationError, ExpectUserAction};
+use kernel::KernelError;
 use kernel::{
-    external::{Duration, OffsetDateTime},
+    external::{Duration, OffsetDateTime, Uuid},
     interfaces::repository::{
-        AccountRepository, AuthorizeTokenRepository, ClientRegistry, DependOnAccessTokenRepository,
+        AccessTokenRepository, AccountRepository, AuthorizeTokenRepository, ClientRegistry,
+        DPoPJtiVolatileRepository, DPoPNonceVolatileRepository, DependOnAccessTokenRepository,
         DependOnAccountRepository, DependOnAuthorizeTokenRepository, DependOnClientRegistry,
+        DependOnDPoPJtiVolatileRepository, DependOnDPoPNonceVolatileRepository,
+        DependOnDeviceAuthorizationVolatileRepository, DependOnJwtBearerJtiVolatileRepository,
         DependOnPKCEVolatileRepository, DependOnPendingAuthorizeTokenRepository,
-        DependOnRefreshTokenRepository, DependOnStateVolatileRepository, PKCEVolatileRepository,
-        PendingAuthorizeTokenRepository, StateVolatileRepository,
+        DependOnProtectedResourceRepository, DependOnPushedAuthorizationRequestVolatileRepository,
+        DependOnRefreshTokenRepository, DependOnSamlAssertionIdVolatileRepository,
+        DependOnSessionVolatileRepository, DependOnSigningKeyRepository,
+        DependOnStateVolatileRepository, DependOnTrustedIssuerRepository,
+        DeviceAuthorizationVolatileRepository, JwtBearerJtiVolatileRepository,
+        PKCEVolatileRepository, PendingAuthorizeTokenRepository, ProtectedResourceRepository,
+        PushedAuthorizationRequestVolatileRepository, RefreshTokenRepository,
+        SamlAssertionIdVolatileRepository, SessionVolatileRepository, SigningKeyRepository,
+        StateVolatileRepository, TrustedIssuerRepository,
+    },
+    interfaces::transport::{
+        DependOnJwksTransporter, DependOnRequestObjectTransporter, JwksTransporter,
+        RequestObjectTransporter,
     },
     prelude::entities::{
-        Address, AuthorizeToken, AuthorizeTokenId, ClientId, CodeChallenge, DestructAccount,
-        DestructClient, ResponseType, ScopeMethod, State, TicketId, TokenOwnedUser,
+        AccessToken, AccessTokenFormat, AccessTokenId, Actor, Address, AuthTime,
+        AuthorizationDetails, AuthorizationResponseJwt, AuthorizeToken, AuthorizeTokenContext,
+        AuthorizeTokenId, Client, ClientId, ClientJwkSet, ClientTypes, CodeChallenge, Confirmation,
+        DPoPNonce, DPoPProof, DestructAccount, DestructAuthorizeToken,
+        DestructAuthorizeTokenContext, DestructClient, DestructPushedAuthorizationRequest,
+        DeviceAuthorization, DeviceCode, GrantType, IdToken, Issuer, Jwks, JwtBearerAssertion,
+        LoggedAt, Nonce, ProtectedResource, PushedAuthorizationRequest, RefreshToken,
+        RefreshTokenFamily, RefreshTokenId, RequestObject, RequestUri, ResponseMode, ResponseType,
+        SamlBearerAssertion, ScopeMethod, SessionId, State, TicketId, TokenOwnedUser, UserCode,
+        UserId,
     },
 };
+use std::str::FromStr;
+
+const ACCESS_TOKEN_EXPIRES_IN: i64 = 60 * 60;
+const REFRESH_TOKEN_EXPIRES_IN: i64 = 60 * 60 * 24 * 30;
+const DEVICE_CODE_EXPIRES_IN: i64 = 60 * 10;
+
+/// Token type identifier of access tokens in the token exchange.
+///
+/// See [RFC8693 Section 3](https://datatracker.ietf.org/doc/html/rfc8693#section-3)
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
 
 #[async_trait::async_trait]
 pub trait PendingAuthorizeTokenService:
@@ -25,23 +64,16 @@ pub trait PendingAuthorizeTokenService:
     + DependOnPKCEVolatileRepository
     + DependOnPendingAuthorizeTokenRepository
     + DependOnStateVolatileRepository
+    + DependOnPushedAuthorizationRequestVolatileRepository
+    + RequestObjectService
+    + ProtectedResourceService
 {
     //noinspection DuplicatedCode
     async fn pending(
         &self,
         create: CreateAuthorizeTokenDto,
-    ) -> Result<TicketIdDto, ApplicationError> {
-        let CreateAuthorizeTokenDto {
-            response_type,
-            client_id,
-            redirect_uri,
-            scope,
-            state,
-            code_challenge,
-            code_challenge_method,
-        } = create;
-
-        let client_id = ClientId::new_at_now(client_id);
+    ) -> Result<PendingAuthorizationDto, ApplicationError> {
+        let client_id = ClientId::new_at_now(create.client_id);
 
         let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
             return Err(ApplicationError::NotFound {
@@ -51,96 +83,694 @@ pub trait PendingAuthorizeTokenService:
             });
         };
 
-        let code_challenge = CodeChallenge::new(code_challenge)?;
+        // https://datatracker.ietf.org/doc/html/rfc9126#section-6
+        if client.require_pushed_authorization_requests() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: "this client requires pushed authorization requests.".to_string(),
+            });
+        }
 
-        // There is no advantage to ignoring the PKCE, so it is always required
-        if code_challenge_method.ne("S256") {
+        if client.require_signed_request_object() {
             return Err(ApplicationError::InvalidValue {
-                method: "code_challenge_method validation",
-                value: "code_challenge_method required `S256`.".to_string(),
+                method: "invalid_request",
+                value: "this client requires a signed request object.".to_string(),
             });
         }
 
-        // https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.1
-        if response_type.ne("code") {
+        let resource = self
+            .find_protected_resource(create.resource.as_deref())
+            .await?;
+        let details = self
+            .validate_authorization_details(create.authorization_details.as_deref())
+            .await?;
+        let (token, code_challenge, state) =
+            validate_authorization_request(client, create, resource, details)?;
+
+        start_pending(self, token, code_challenge, state).await
+    }
+
+    /// Start the authorization decision of a request sent as a signed request object.
+    ///
+    /// See [RFC9101 Section 6](https://datatracker.ietf.org/doc/html/rfc9101#section-6)
+    async fn pending_signed(
+        &self,
+        signed: SignedAuthorizeTokenDto,
+    ) -> Result<PendingAuthorizationDto, ApplicationError> {
+        let client_id = ClientId::new_at_now(signed.client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::NotFound {
+                method: "find_by_id",
+                entity: "client",
+                id: client_id.to_string(),
+            });
+        };
+
+        if client.require_pushed_authorization_requests() {
             return Err(ApplicationError::InvalidValue {
                 method: "invalid_request",
-                value: format!(
-                    "`response_type` must set `code`. invalid {}.",
-                    response_type
-                ),
+                value: "this client requires pushed authorization requests.".to_string(),
             });
         }
 
-        let response_type = ResponseType::Code;
+        let create = self.resolve_request_object(&client, signed).await?;
+        let resource = self
+            .find_protected_resource(create.resource.as_deref())
+            .await?;
+        let details = self
+            .validate_authorization_details(create.authorization_details.as_deref())
+            .await?;
+        let (token, code_challenge, state) =
+            validate_authorization_request(client, create, resource, details)?;
+
+        start_pending(self, token, code_challenge, state).await
+    }
 
-        if client
-            .response_types()
-            .iter()
-            .any(|ty| ty.ne(&response_type))
-        {
+    /// Start the authorization decision of a request the client pushed beforehand.
+    ///
+    /// A `request_uri` can be used only once.
+    ///
+    /// See [RFC9126 Section 4](https://datatracker.ietf.org/doc/html/rfc9126#section-4)
+    async fn pending_pushed(
+        &self,
+        pushed: PushedAuthorizeTokenDto,
+    ) -> Result<PendingAuthorizationDto, ApplicationError> {
+        let PushedAuthorizeTokenDto {
+            client_id,
+            request_uri,
+        } = pushed;
+
+        let request_uri = RequestUri::new(request_uri);
+
+        let Some(request) = self
+            .pushed_authorization_request_volatile_repository()
+            .find(&request_uri)
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: "`request_uri` is invalid, expired or already used.".to_string(),
+            });
+        };
+
+        self.pushed_authorization_request_volatile_repository()
+            .dele(&request_uri)
+            .await?;
+
+        if request.expired_in().is_expired() {
             return Err(ApplicationError::InvalidValue {
-                method: "unsupported_response_type",
-                value: "client not support this response_type".to_string(),
+                method: "invalid_request",
+                value: "`request_uri` is expired.".to_string(),
             });
         }
 
-        let DestructClient { redirect_uris, .. } = client.into_destruct();
-        let redirect_uri = match redirect_uri {
-            Some(uri) => redirect_uris
-                .into_iter()
-                .find(|reg| reg.eq(uri.as_str()))
-                .ok_or_else(|| ApplicationError::InvalidValue {
-                    method: "redirect_uri validate",
-                    value: "The specified uri is not registered with this client.".to_string(),
-                })?,
-            None => redirect_uris.take_one()?,
+        if request.token().context().client_id().id().ne(&client_id) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: "`request_uri` was pushed by another client.".to_string(),
+            });
+        }
+
+        let DestructPushedAuthorizationRequest {
+            token,
+            code_challenge,
+            state,
+            ..
+        } = request.into_destruct();
+
+        start_pending(self, token, code_challenge, state).await
+    }
+}
+
+pub trait DependOnPendingAuthorizeTokenService: 'static + Sync + Send {
+    type PendingAuthorizeTokenService: PendingAuthorizeTokenService;
+    fn pending_authorize_token_service(&self) -> &Self::PendingAuthorizeTokenService;
+}
+
+/// Stores a validated authorization request until the user decides on it.
+async fn start_pending<T: PendingAuthorizeTokenService + ?Sized>(
+    service: &T,
+    token: AuthorizeToken,
+    code_challenge: CodeChallenge,
+    state: State,
+) -> Result<PendingAuthorizationDto, ApplicationError> {
+    service
+        .pkce_volatile_repository()
+        .save(token.id(), &code_challenge)
+        .await?;
+
+    let ticket = TicketId::default();
+    service
+        .state_volatile_repository()
+        .save(&ticket, &state)
+        .await?;
+    service
+        .pending_authorize_token_repository()
+        .save(&ticket, &token)
+        .await?;
+
+    Ok(PendingAuthorizationDto::from_with(ticket, &token))
+}
+
+#[async_trait::async_trait]
+pub trait PushAuthorizationRequestService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnPushedAuthorizationRequestVolatileRepository
+    + RequestObjectService
+    + ProtectedResourceService
+{
+    /// Validate an authorization request sent directly by the client,
+    /// and keep it until the user agent brings the returned `request_uri` to the authorization endpoint.
+    ///
+    /// See [RFC9126 Section 2](https://datatracker.ietf.org/doc/html/rfc9126#section-2)
+    async fn push(
+        &self,
+        create: CreateAuthorizeTokenDto,
+    ) -> Result<PushedAuthorizationRequestDto, ApplicationError> {
+        let client_id = ClientId::new_at_now(create.client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client is not registered.".to_string(),
+            });
         };
 
-        let token_id = AuthorizeTokenId::default();
+        if client.require_signed_request_object() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: "this client requires a signed request object.".to_string(),
+            });
+        }
 
-        let state = State::new(state);
+        let resource = self
+            .find_protected_resource(create.resource.as_deref())
+            .await?;
+        let details = self
+            .validate_authorization_details(create.authorization_details.as_deref())
+            .await?;
+        let (token, code_challenge, state) =
+            validate_authorization_request(client, create, resource, details)?;
 
-        self.pkce_volatile_repository()
-            .save(&token_id, &code_challenge)
+        let request = PushedAuthorizationRequest::new(token, code_challenge, state);
+        self.pushed_authorization_request_volatile_repository()
+            .save(&request)
             .await?;
 
-        let created_at = OffsetDateTime::now_utc();
-        let updated_at = created_at;
-        let expired_in = Duration::new(60 * 10, 0);
+        Ok(request.into())
+    }
 
-        let scope = scope
-            .into_iter()
-            .map(ScopeMethod::new)
-            .collect::<Vec<ScopeMethod>>();
+    /// Same as [push](PushAuthorizationRequestService::push),
+    /// for a request sent as a signed request object.
+    ///
+    /// See [RFC9126 Section 3](https://datatracker.ietf.org/doc/html/rfc9126#section-3)
+    async fn push_signed(
+        &self,
+        signed: SignedAuthorizeTokenDto,
+    ) -> Result<PushedAuthorizationRequestDto, ApplicationError> {
+        let client_id = ClientId::new_at_now(signed.client_id);
 
-        let token = AuthorizeToken::new(
-            token_id,
-            created_at,
-            updated_at,
-            None,
-            client_id,
-            scope,
-            response_type,
-            redirect_uri,
-            expired_in,
-        );
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client is not registered.".to_string(),
+            });
+        };
 
-        let ticket = TicketId::default();
-        self.state_volatile_repository()
-            .save(&ticket, &state)
+        let create = self.resolve_request_object(&client, signed).await?;
+        let resource = self
+            .find_protected_resource(create.resource.as_deref())
             .await?;
-        self.pending_authorize_token_repository()
-            .save(&ticket, &token)
+        let details = self
+            .validate_authorization_details(create.authorization_details.as_deref())
             .await?;
+        let (token, code_challenge, state) =
+            validate_authorization_request(client, create, resource, details)?;
 
-        Ok(ticket.into())
+        let request = PushedAuthorizationRequest::new(token, code_challenge, state);
+        self.pushed_authorization_request_volatile_repository()
+            .save(&request)
+            .await?;
+
+        Ok(request.into())
     }
 }
 
-pub trait DependOnPendingAuthorizeTokenService: 'static + Sync + Send {
-    type PendingAuthorizeTokenService: PendingAuthorizeTokenService;
-    fn pending_authorize_token_service(&self) -> &Self::PendingAuthorizeTokenService;
+pub trait DependOnPushAuthorizationRequestService: 'static + Sync + Send {
+    type PushAuthorizationRequestService: PushAuthorizationRequestService;
+    fn push_authorization_request_service(&self) -> &Self::PushAuthorizationRequestService;
+}
+
+#[async_trait::async_trait]
+pub trait RequestObjectService:
+    'static + Sync + Send + DependOnJwksTransporter + DependOnRequestObjectTransporter
+{
+    /// Verify the request object with the client's keys, and take the request from its claims.
+    ///
+    /// Parameters given alongside it are not integrity protected, so they are ignored,
+    /// except `response_type` which must match the request object if given.
+    ///
+    /// See [RFC9101 Section 6](https://datatracker.ietf.org/doc/html/rfc9101#section-6)
+    /// and [OpenID Connect Core 1.0 Section 6.3.3](https://openid.net/specs/openid-connect-core-1_0.html#RequestParameter)
+    async fn resolve_request_object(
+        &self,
+        client: &Client,
+        signed: SignedAuthorizeTokenDto,
+    ) -> Result<CreateAuthorizeTokenDto, ApplicationError> {
+        let SignedAuthorizeTokenDto {
+            client_id,
+            request,
+            request_uri,
+            params,
+        } = signed;
+
+        let request = match (request, request_uri) {
+            (Some(request), None) => RequestObject::new(request),
+            // Fetching arbitrary uris on behalf of an unauthenticated request
+            // is open to SSRF, so only uris the client has registered are fetched.
+            // https://datatracker.ietf.org/doc/html/rfc9101#section-10.4.1
+            (None, Some(uri)) if !uri.starts_with("https://") => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_request_uri",
+                    value: "`request_uri` must use https.".to_string(),
+                })
+            }
+            (None, Some(uri)) if !client.request_uris().contains(&uri) => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_request_uri",
+                    value: "`request_uri` is not registered with this client.".to_string(),
+                })
+            }
+            (None, Some(uri)) => self
+                .request_object_transporter()
+                .fetch(&uri)
+                .await
+                .map_err(|e| ApplicationError::InvalidValue {
+                    method: "invalid_request_uri",
+                    value: e.to_string(),
+                })?,
+            _ => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_request",
+                    value: "either `request` or `request_uri` is required, but not both."
+                        .to_string(),
+                })
+            }
+        };
+
+        let keys = match client.jwks() {
+            Some(Jwks::Key(key)) => ClientJwkSet::try_from(key)?,
+            Some(Jwks::Uri(uri)) => self.jwks_transporter().fetch(uri).await?,
+            None => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_request_object",
+                    value: "client has no registered `jwks`.".to_string(),
+                })
+            }
+        };
+
+        let claims = request
+            .verify(&keys, client.id(), Issuer::default().as_ref())
+            .map_err(|e| ApplicationError::InvalidValue {
+                method: "invalid_request_object",
+                value: e.to_string(),
+            })?;
+
+        if claims
+            .client_id
+            .as_ref()
+            .is_some_and(|claimed| claimed.ne(&client_id.to_string()))
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request_object",
+                value: "`client_id` does not match the request.".to_string(),
+            });
+        }
+
+        // https://datatracker.ietf.org/doc/html/rfc9101#section-6.3
+        if params
+            .response_type
+            .as_ref()
+            .is_some_and(|param| claims.response_type.as_ref() != Some(param))
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request_object",
+                value: "`response_type` does not match the request object.".to_string(),
+            });
+        }
+
+        let required = |claim: Option<String>, name: &str| {
+            claim.ok_or_else(|| ApplicationError::InvalidValue {
+                method: "invalid_request_object",
+                value: format!("`{}` is required in the request object.", name),
+            })
+        };
+
+        Ok(CreateAuthorizeTokenDto {
+            response_type: required(claims.response_type, "response_type")?,
+            client_id,
+            redirect_uri: claims.redirect_uri,
+            scope: claims
+                .scope
+                .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect())
+                .unwrap_or_default(),
+            state: required(claims.state, "state")?,
+            code_challenge: required(claims.code_challenge, "code_challenge")?,
+            code_challenge_method: required(claims.code_challenge_method, "code_challenge_method")?,
+            nonce: claims.nonce,
+            response_mode: claims.response_mode,
+            resource: claims.resource,
+            authorization_details: claims
+                .authorization_details
+                .map(|details| details.to_string()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+pub trait ProtectedResourceService:
+    'static + Sync + Send + DependOnProtectedResourceRepository
+{
+    /// Find the protected resource named by the `resource` parameter.
+    ///
+    /// See [RFC8707 Section 2](https://datatracker.ietf.org/doc/html/rfc8707#section-2)
+    async fn find_protected_resource(
+        &self,
+        resource: Option<&str>,
+    ) -> Result<Option<ProtectedResource>, ApplicationError> {
+        let Some(resource) = resource else {
+            return Ok(None);
+        };
+
+        let Some(found) = self
+            .protected_resource_repository()
+            .find_by_resource(resource)
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_target",
+                value: format!("`{}` is not a known resource.", resource),
+            });
+        };
+
+        Ok(Some(found))
+    }
+
+    /// Determine the protected resource an access token is issued for,
+    /// and narrow `scope` to what the resource allows.
+    ///
+    /// If the grant was made for a resource, only that resource can be requested.
+    ///
+    /// See [RFC8707 Section 2.2](https://datatracker.ietf.org/doc/html/rfc8707#section-2.2)
+    async fn resolve_token_target(
+        &self,
+        granted: Option<&str>,
+        requested: Option<String>,
+        scope: Vec<ScopeMethod>,
+    ) -> Result<(Option<String>, Vec<ScopeMethod>), ApplicationError> {
+        if let (Some(granted), Some(requested)) = (granted, requested.as_deref()) {
+            if granted.ne(requested) {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_target",
+                    value: "`resource` is not the one the grant was made for.".to_string(),
+                });
+            }
+        }
+
+        match self
+            .find_protected_resource(requested.as_deref().or(granted))
+            .await?
+        {
+            Some(resource) => {
+                let scope = narrow_scope(&resource, &scope)?;
+                Ok((Some(resource.resource().to_string()), scope))
+            }
+            None => Ok((None, scope)),
+        }
+    }
+
+    /// Parse the `authorization_details` parameter,
+    /// and validate each object against the schema registered for its `type`.
+    ///
+    /// See [RFC9396 Section 5](https://datatracker.ietf.org/doc/html/rfc9396#section-5)
+    async fn validate_authorization_details(
+        &self,
+        raw: Option<&str>,
+    ) -> Result<Option<AuthorizationDetails>, ApplicationError> {
+        let Some(raw) = raw else {
+            return Ok(None);
+        };
+
+        let details = AuthorizationDetails::parse(raw).map_err(invalid_authorization_details)?;
+        for detail in details.iter() {
+            let Some(ty) = self
+                .protected_resource_repository()
+                .find_authorization_details_type(detail.detail_type())
+                .await?
+            else {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_authorization_details",
+                    value: format!("`{}` is not a known type.", detail.detail_type()),
+                });
+            };
+            ty.validate(detail).map_err(invalid_authorization_details)?;
+        }
+
+        Ok(Some(details))
+    }
+
+    /// Determine the authorization details an access token is issued for.
+    ///
+    /// If the grant was made with authorization details,
+    /// only a subset of them can be requested.
+    ///
+    /// See [RFC9396 Section 6.1](https://datatracker.ietf.org/doc/html/rfc9396#section-6.1)
+    async fn resolve_token_details(
+        &self,
+        granted: &Option<AuthorizationDetails>,
+        requested: Option<&str>,
+    ) -> Result<Option<AuthorizationDetails>, ApplicationError> {
+        let requested = self.validate_authorization_details(requested).await?;
+        match (granted, requested) {
+            (Some(granted), Some(requested)) if !granted.contains_all(&requested) => {
+                Err(ApplicationError::InvalidValue {
+                    method: "invalid_authorization_details",
+                    value: "`authorization_details` exceeds what the grant was made for."
+                        .to_string(),
+                })
+            }
+            (None, Some(_)) => Err(ApplicationError::InvalidValue {
+                method: "invalid_authorization_details",
+                value: "the grant was made without `authorization_details`.".to_string(),
+            }),
+            (granted, requested) => Ok(requested.or_else(|| granted.clone())),
+        }
+    }
+}
+
+fn invalid_authorization_details(e: KernelError) -> ApplicationError {
+    ApplicationError::InvalidValue {
+        method: "invalid_authorization_details",
+        value: e.to_string(),
+    }
+}
+
+/// Key a refresh token issued together with `token` is bound to.
+///
+/// Only refresh tokens of public clients are bound,
+/// those of confidential clients are already constrained by client authentication.
+///
+/// See [RFC9449 Section 5](https://datatracker.ietf.org/doc/html/rfc9449#section-5)
+/// and [RFC8705 Section 4](https://datatracker.ietf.org/doc/html/rfc8705#section-4)
+fn refresh_confirmation(client: &Client, token: &AccessToken) -> Option<Confirmation> {
+    match client.types() {
+        ClientTypes::Public => token.context().confirmation().clone(),
+        _ => None,
+    }
+}
+
+/// Refresh tokens are only issued to clients registered for the refresh token grant.
+///
+/// See [RFC6749 Section 1.5](https://datatracker.ietf.org/doc/html/rfc6749#section-1.5)
+/// and [RFC7591 Section 2](https://datatracker.ietf.org/doc/html/rfc7591#section-2)
+fn issues_refresh_token(client: &Client) -> bool {
+    client
+        .grant_types()
+        .as_ref()
+        .contains(&GrantType::RefreshToken)
+}
+
+/// Scope requested by the client, all of which must be registered with it,
+/// or every registered scope if none is requested.
+fn validate_client_scope(
+    client: &Client,
+    scope: Option<Vec<String>>,
+) -> Result<Vec<ScopeMethod>, ApplicationError> {
+    let Some(scope) = scope else {
+        return Ok(client
+            .scopes()
+            .iter()
+            .map(|(method, _)| method.clone())
+            .collect());
+    };
+    let scope = scope
+        .into_iter()
+        .map(ScopeMethod::new)
+        .collect::<Vec<ScopeMethod>>();
+    if let Some(exceeded) = scope
+        .iter()
+        .find(|req| !client.scopes().as_ref().contains_key(req))
+    {
+        return Err(ApplicationError::InvalidValue {
+            method: "invalid_scope",
+            value: format!(
+                "`{}` is not registered with this client.",
+                exceeded.as_ref()
+            ),
+        });
+    }
+    Ok(scope)
+}
+
+/// Scope that can be granted for the resource, at least one of which is required.
+fn narrow_scope(
+    resource: &ProtectedResource,
+    scope: &[ScopeMethod],
+) -> Result<Vec<ScopeMethod>, ApplicationError> {
+    let narrowed = resource.narrow(scope);
+    if narrowed.is_empty() {
+        return Err(ApplicationError::InvalidValue {
+            method: "invalid_scope",
+            value: format!(
+                "none of the requested scope is allowed for `{}`.",
+                resource.resource()
+            ),
+        });
+    }
+    Ok(narrowed)
+}
+
+/// Validates an authorization request against the client,
+/// whether it comes from the query of the authorization endpoint or is pushed.
+///
+/// See [RFC6749 Section 4.1.1](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.1)
+fn validate_authorization_request(
+    client: Client,
+    create: CreateAuthorizeTokenDto,
+    resource: Option<ProtectedResource>,
+    authorization_details: Option<AuthorizationDetails>,
+) -> Result<(AuthorizeToken, CodeChallenge, State), ApplicationError> {
+    let CreateAuthorizeTokenDto {
+        response_type,
+        redirect_uri,
+        scope,
+        state,
+        code_challenge,
+        code_challenge_method,
+        nonce,
+        response_mode,
+        ..
+    } = create;
+
+    let code_challenge = CodeChallenge::new(code_challenge)?;
+
+    // There is no advantage to ignoring the PKCE, so it is always required
+    if !CodeChallenge::SUPPORTED_METHODS.contains(&code_challenge_method.as_str()) {
+        return Err(ApplicationError::InvalidValue {
+            method: "code_challenge_method validation",
+            value: "code_challenge_method required `S256`.".to_string(),
+        });
+    }
+
+    // https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.1
+    let Some(response_type) = ResponseType::from_str(&response_type)
+        .ok()
+        .filter(|ty| ResponseType::SUPPORTED.contains(ty))
+    else {
+        return Err(ApplicationError::InvalidValue {
+            method: "invalid_request",
+            value: format!(
+                "`response_type` must set `code`. invalid {}.",
+                response_type
+            ),
+        });
+    };
+
+    if client
+        .response_types()
+        .iter()
+        .any(|ty| ty.ne(&response_type))
+    {
+        return Err(ApplicationError::InvalidValue {
+            method: "unsupported_response_type",
+            value: "client not support this response_type".to_string(),
+        });
+    }
+
+    let response_mode = response_mode
+        .map(|mode| {
+            ResponseMode::from_str(&mode).map_err(|_| ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: format!("`response_mode` {} is not supported.", mode),
+            })
+        })
+        .transpose()?;
+
+    let DestructClient {
+        id: client_id,
+        redirect_uris,
+        ..
+    } = client.into_destruct();
+    let redirect_uri = match redirect_uri {
+        Some(uri) => redirect_uris
+            .into_iter()
+            .find(|reg| reg.eq(uri.as_str()))
+            .ok_or_else(|| ApplicationError::InvalidValue {
+                method: "redirect_uri validate",
+                value: "The specified uri is not registered with this client.".to_string(),
+            })?,
+        None => redirect_uris.take_one()?,
+    };
+
+    let created_at = OffsetDateTime::now_utc();
+    let updated_at = created_at;
+    let expired_in = Duration::new(60 * 10, 0);
+
+    let scope = scope
+        .into_iter()
+        .map(ScopeMethod::new)
+        .collect::<Vec<ScopeMethod>>();
+
+    // The scope stays as requested, as it may include scope for the authorization server itself
+    // such as `openid`, and is narrowed only for the access token.
+    if let Some(resource) = &resource {
+        narrow_scope(resource, &scope)?;
+    }
+
+    let token = AuthorizeToken::new(
+        AuthorizeTokenId::default(),
+        created_at,
+        updated_at,
+        None,
+        client_id,
+        scope,
+        response_type,
+        redirect_uri,
+        nonce,
+        resource.map(|resource| resource.resource().to_string()),
+        authorization_details,
+        expired_in,
+    );
+    let token = match response_mode {
+        Some(mode) => token.with_response_mode(mode),
+        None => token,
+    };
+
+    Ok((token, code_challenge, State::new(state)))
 }
 
 #[async_trait::async_trait]
@@ -152,13 +782,20 @@ pub trait AcceptAuthorizeTokenService:
     + DependOnStateVolatileRepository
     + DependOnPendingAuthorizeTokenRepository
     + DependOnAuthorizeTokenRepository
+    + AuthorizationResponseService
 {
+    /// Issue an authorization code for the pending request the user accepted.
+    ///
+    /// For a device authorization request the code is kept for the device to poll,
+    /// and never delivered to the user agent.
+    ///
+    /// See [RFC6749 Section 4.1.2](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2)
     async fn accept(
         &self,
         ticket: &str,
         state: &str,
         accept: AcceptUserFormDto,
-    ) -> Result<AuthorizeTokenDto, ApplicationError> {
+    ) -> Result<DecisionDto, ApplicationError> {
         let ticket = TicketId::new(ticket);
         let Some(token) = self
             .pending_authorize_token_repository()
@@ -206,6 +843,7 @@ pub trait AcceptAuthorizeTokenService:
         let mut token = token.into_destruct();
 
         token.owned_by = TokenOwnedUser::new(id);
+        token.auth_time = Some(AuthTime::default());
 
         let token = token.freeze();
 
@@ -217,7 +855,16 @@ pub trait AcceptAuthorizeTokenService:
             .save(token.id(), &token)
             .await?;
 
-        Ok(AuthorizeTokenDto::from_with(token, "bearer", state))
+        if token.context().is_device() {
+            return Ok(DecisionDto::Device { approved: true });
+        }
+
+        let params = vec![
+            ("code".to_string(), token.id().as_ref().to_string()),
+            ("state".to_string(), state.to_string()),
+        ];
+        let response = self.respond(token.context(), params).await?;
+        Ok(DecisionDto::Redirect(response))
     }
 }
 
@@ -234,21 +881,43 @@ pub trait RejectAuthorizeTokenService:
     + DependOnPendingAuthorizeTokenRepository
     + DependOnStateVolatileRepository
     + DependOnPKCEVolatileRepository
+    + AuthorizationResponseService
 {
-    async fn reject(&self, ticket: &str) -> Result<(), ApplicationError> {
+    /// Discard the pending request the user denied, and tell the client with `access_denied`.
+    ///
+    /// See [RFC6749 Section 4.1.2.1](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2.1)
+    async fn reject(&self, ticket: &str) -> Result<DecisionDto, ApplicationError> {
         let ticket = TicketId::new(ticket);
-        if let Some(token) = self
+        let Some(token) = self
             .pending_authorize_token_repository()
             .find(&ticket)
             .await?
-        {
-            self.pkce_volatile_repository().dele(token.id()).await?;
-        }
+        else {
+            return Err(ApplicationError::NotFound {
+                method: "find",
+                entity: "ticket",
+                id: format!("Ticket not found or expired, ticket: {:?}", ticket),
+            });
+        };
+        let state = self.state_volatile_repository().find(&ticket).await?;
+
+        self.pkce_volatile_repository().dele(token.id()).await?;
         self.state_volatile_repository().dele(&ticket).await?;
         self.pending_authorize_token_repository()
             .dele(&ticket)
             .await?;
-        Ok(())
+
+        // The device finds the decision gone and reports `access_denied` itself.
+        if token.context().is_device() {
+            return Ok(DecisionDto::Device { approved: false });
+        }
+
+        let mut params = vec![("error".to_string(), "access_denied".to_string())];
+        if let Some(state) = state {
+            params.push(("state".to_string(), state.into()));
+        }
+        let response = self.respond(token.context(), params).await?;
+        Ok(DecisionDto::Redirect(response))
     }
 }
 
@@ -258,20 +927,1769 @@ pub trait DependOnRejectAuthorizeTokenService: 'static + Sync + Send {
 }
 
 #[async_trait::async_trait]
-pub trait CreateAccessTokenService:
-    'static
-    + Sync
-    + Send
-    + DependOnPKCEVolatileRepository
-    + DependOnAccessTokenRepository
-    + DependOnRefreshTokenRepository
+pub trait AuthorizationResponseService:
+    'static + Sync + Send + DependOnSigningKeyRepository
 {
-    async fn create(&self) -> Result<(), ApplicationError>;
+    /// Build the response to the redirect uri of the authorization request,
+    /// signing the parameters with the active signing key for the JWT secured response modes.
+    ///
+    /// See [JARM Section 2.3](https://openid.net/specs/oauth-v2-jarm.html#name-response-mode-jwt)
+    async fn respond(
+        &self,
+        ctx: &AuthorizeTokenContext,
+        params: Vec<(String, String)>,
+    ) -> Result<AuthorizationResponseDto, ApplicationError> {
+        let mode = ctx.response_mode();
+        let params = if mode.is_jwt() {
+            let Some(key) = self.signing_key_repository().find_active().await? else {
+                return Err(ApplicationError::NotFound {
+                    method: "find_active",
+                    entity: "signing_key",
+                    id: "active".to_string(),
+                });
+            };
+            let response = AuthorizationResponseJwt::issue(&key, ctx.client_id(), &params)?;
+            vec![("response".to_string(), response.into())]
+        } else {
+            params
+        };
+
+        Ok(AuthorizationResponseDto {
+            redirect_uri: ctx.redirect_uri().as_ref().to_string(),
+            response_mode: mode.delivery().as_ref().to_string(),
+            params,
+        })
+    }
 }
 
 #[async_trait::async_trait]
-pub trait RefreshAccessTokenService:
-    'static + Sync + Send + DependOnRefreshTokenRepository + DependOnAccessTokenRepository
+pub trait FormatAccessTokenService: 'static + Sync + Send + DependOnSigningKeyRepository {
+    /// Convert the token into the format the client has chosen.
+    ///
+    /// `Jwt` tokens are signed with the active signing key.
+    ///
+    /// See [RFC9068](https://datatracker.ietf.org/doc/html/rfc9068)
+    async fn format(
+        &self,
+        format: &AccessTokenFormat,
+        token: AccessToken,
+    ) -> Result<AccessToken, ApplicationError> {
+        match format {
+            AccessTokenFormat::Opaque => Ok(token),
+            AccessTokenFormat::Jwt => {
+                let Some(key) = self.signing_key_repository().find_active().await? else {
+                    return Err(ApplicationError::NotFound {
+                        method: "find_active",
+                        entity: "signing_key",
+                        id: "active".to_string(),
+                    });
+                };
+                Ok(token.into_jwt(&key)?)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait DPoPService:
+    'static + Sync + Send + DependOnDPoPJtiVolatileRepository + DependOnDPoPNonceVolatileRepository
 {
-    async fn refresh(&self) -> Result<(), ApplicationError>;
+    /// Verify a DPoP proof, and return the thumbprint of its key to bind the access token to.
+    ///
+    /// When nonces are required and the proof does not carry a valid one,
+    /// a new nonce is issued as the value of a `use_dpop_nonce` error, for the client to retry with.
+    ///
+    /// See [RFC9449 Section 4.3](https://datatracker.ietf.org/doc/html/rfc9449#section-4.3)
+    /// and [RFC9449 Section 8](https://datatracker.ietf.org/doc/html/rfc9449#section-8)
+    async fn verify_dpop_proof(&self, proof: DPoPProofDto) -> Result<String, ApplicationError> {
+        let DPoPProofDto {
+            proof,
+            htm,
+            htu,
+            access_token,
+        } = proof;
+
+        let proof = DPoPProof::new(proof)
+            .verify(&htm, &htu, access_token.as_deref())
+            .map_err(|e| ApplicationError::InvalidValue {
+                method: "invalid_dpop_proof",
+                value: e.to_string(),
+            })?;
+
+        if DPoPNonce::required() {
+            let issued = match proof.nonce() {
+                Some(nonce) => self
+                    .dpop_nonce_volatile_repository()
+                    .find(&DPoPNonce::new(nonce))
+                    .await?
+                    .is_some(),
+                None => false,
+            };
+            if !issued {
+                let nonce = DPoPNonce::default();
+                self.dpop_nonce_volatile_repository().save(&nonce).await?;
+                return Err(ApplicationError::InvalidValue {
+                    method: "use_dpop_nonce",
+                    value: nonce.into(),
+                });
+            }
+        }
+
+        if !self.dpop_jti_volatile_repository().save(&proof).await? {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_dpop_proof",
+                value: "`jti` has already been used.".to_string(),
+            });
+        }
+
+        Ok(proof.jkt().to_string())
+    }
+}
+
+pub trait DependOnDPoPService: 'static + Sync + Send {
+    type DPoPService: DPoPService;
+    fn dpop_service(&self) -> &Self::DPoPService;
+}
+
+pub trait DependOnFormatAccessTokenService: 'static + Sync + Send {
+    type FormatAccessTokenService: FormatAccessTokenService;
+    fn format_access_token_service(&self) -> &Self::FormatAccessTokenService;
+}
+
+#[async_trait::async_trait]
+pub trait SignIdTokenService: 'static + Sync + Send + DependOnSigningKeyRepository {
+    /// Issue an ID Token for the access token with the active signing key.
+    ///
+    /// See [OpenID Connect Core 1.0 Section 3.1.3.3](https://openid.net/specs/openid-connect-core-1_0.html#TokenResponse)
+    async fn sign_id_token(
+        &self,
+        account: &UserId,
+        auth_time: Option<&AuthTime>,
+        nonce: Option<&Nonce>,
+        token: &AccessToken,
+    ) -> Result<IdToken, ApplicationError> {
+        let Some(key) = self.signing_key_repository().find_active().await? else {
+            return Err(ApplicationError::NotFound {
+                method: "find_active",
+                entity: "signing_key",
+                id: "active".to_string(),
+            });
+        };
+        Ok(IdToken::issue(
+            &key,
+            token.context().client_id(),
+            account,
+            auth_time,
+            nonce,
+            token,
+        )?)
+    }
+}
+
+pub trait DependOnSignIdTokenService: 'static + Sync + Send {
+    type SignIdTokenService: SignIdTokenService;
+    fn sign_id_token_service(&self) -> &Self::SignIdTokenService;
+}
+
+#[async_trait::async_trait]
+pub trait CreateAccessTokenService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnAuthorizeTokenRepository
+    + DependOnPKCEVolatileRepository
+    + DependOnAccessTokenRepository
+    + DependOnRefreshTokenRepository
+    + FormatAccessTokenService
+    + SignIdTokenService
+    + ProtectedResourceService
+{
+    /// Exchange an authorization code for an access token.
+    ///
+    /// If the `openid` scope was granted, an ID Token is issued as well.
+    ///
+    /// See [RFC6749 Section 4.1.3](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.3)
+    async fn create(
+        &self,
+        create: CreateAccessTokenDto,
+    ) -> Result<AccessTokenDto, ApplicationError> {
+        let CreateAccessTokenDto {
+            code,
+            redirect_uri,
+            client_id,
+            code_verifier,
+            resource,
+            authorization_details,
+            dpop_jkt,
+            x5t_s256,
+        } = create;
+
+        let code = AuthorizeTokenId::new(code);
+
+        // The authorization code is single-use, so it is consumed before any verification,
+        // and only the request that took it can go on.
+        // https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2
+        let code_challenge = self.pkce_volatile_repository().take(&code).await?;
+        let Some(token) = self.authorize_token_repository().take(&code).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "authorization code is invalid, expired or already used.".to_string(),
+            });
+        };
+
+        if token.context().expired_in().is_expired() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "authorization code is expired.".to_string(),
+            });
+        }
+
+        if token.context().client_id().id().ne(&client_id) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "authorization code was issued to another client.".to_string(),
+            });
+        }
+
+        let Some(redirect_uri) = redirect_uri else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "`redirect_uri` is required.".to_string(),
+            });
+        };
+
+        if token.context().redirect_uri().ne(redirect_uri.as_str()) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "`redirect_uri` does not match the authorization request.".to_string(),
+            });
+        }
+
+        let Some(code_challenge) = code_challenge else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "code_challenge not found or expired.".to_string(),
+            });
+        };
+
+        code_challenge.verify(code_verifier)?;
+
+        let DestructAuthorizeToken {
+            owned_by,
+            auth_time,
+            ctx,
+            ..
+        } = token.into_destruct();
+        let DestructAuthorizeTokenContext {
+            client_id,
+            scopes,
+            nonce,
+            resource: granted,
+            authorization_details: granted_details,
+            ..
+        } = ctx.into_destruct();
+
+        let account = UserId::try_from(owned_by)?;
+
+        let (resource, scope) = self
+            .resolve_token_target(granted.as_deref(), resource, scopes.clone())
+            .await?;
+        let details = self
+            .resolve_token_details(&granted_details, authorization_details.as_deref())
+            .await?;
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "client no longer exists.".to_string(),
+            });
+        };
+
+        if !client
+            .grant_types()
+            .as_ref()
+            .contains(&GrantType::AuthorizationCode)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to use the authorization code grant.".to_string(),
+            });
+        }
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+        let expired_in = Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0);
+
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            client_id,
+            account,
+            scope,
+            Issuer::default(),
+            resource.unwrap_or_else(|| client_id.id().to_string()),
+            account.to_string(),
+            expired_in,
+        )
+        .with_authorization_details(details);
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        let id_token = if scopes.iter().any(ScopeMethod::is_openid) {
+            let id_token = self
+                .sign_id_token(&account, auth_time.as_ref(), nonce.as_ref(), &token)
+                .await?;
+            Some(id_token)
+        } else {
+            None
+        };
+
+        let refresh = issues_refresh_token(&client).then(|| {
+            RefreshToken::new(
+                RefreshTokenId::default(),
+                created_at,
+                updated_at,
+                RefreshTokenFamily::default(),
+                token.id(),
+                client_id,
+                account,
+                scopes,
+                granted,
+                granted_details,
+                Duration::new(REFRESH_TOKEN_EXPIRES_IN, 0),
+            )
+            .bind(refresh_confirmation(&client, &token))
+        });
+
+        self.access_token_repository().create(&token).await?;
+        if let Some(refresh) = &refresh {
+            self.refresh_token_repository().create(refresh).await?;
+        }
+
+        let dto = AccessTokenDto::from_with(token, refresh);
+        Ok(AccessTokenDto {
+            id_token: id_token.map(Into::into),
+            ..dto
+        })
+    }
+}
+
+pub trait DependOnCreateAccessTokenService: 'static + Sync + Send {
+    type CreateAccessTokenService: CreateAccessTokenService;
+    fn create_access_token_service(&self) -> &Self::CreateAccessTokenService;
+}
+
+#[async_trait::async_trait]
+pub trait RefreshAccessTokenService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnRefreshTokenRepository
+    + DependOnAccessTokenRepository
+    + FormatAccessTokenService
+    + ProtectedResourceService
+{
+    /// Issue a new access token with a refresh token, rotating the refresh token.
+    ///
+    /// See [RFC6749 Section 6](https://datatracker.ietf.org/doc/html/rfc6749#section-6)
+    async fn refresh(
+        &self,
+        refresh: RefreshAccessTokenDto,
+    ) -> Result<AccessTokenDto, ApplicationError> {
+        let RefreshAccessTokenDto {
+            refresh_token,
+            client_id,
+            scope,
+            resource,
+            authorization_details,
+            dpop_jkt,
+            x5t_s256,
+        } = refresh;
+
+        let refresh_token = RefreshTokenId::new(refresh_token);
+
+        let Some(current) = self
+            .refresh_token_repository()
+            .find_by_id(&refresh_token)
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "refresh token is invalid or revoked.".to_string(),
+            });
+        };
+
+        if current.client_id().id().ne(&client_id) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "refresh token was issued to another client.".to_string(),
+            });
+        }
+
+        // A rotated token presented again means that either the legitimate client
+        // or an attacker holds a stolen copy, so the whole family is revoked.
+        if current.is_rotated() {
+            self.refresh_token_repository()
+                .revoke_family(current.family())
+                .await?;
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "refresh token has already been used.".to_string(),
+            });
+        }
+
+        if current.expired_in().is_expired() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "refresh token is expired.".to_string(),
+            });
+        }
+
+        // A bound refresh token is only usable with the key it is bound to.
+        // https://datatracker.ietf.org/doc/html/rfc9449#section-5
+        match current.confirmation() {
+            Some(Confirmation::Jkt(jkt)) if dpop_jkt.as_ref().ne(&Some(jkt)) => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_dpop_proof",
+                    value: "DPoP proof is missing or made with another key than the refresh token is bound to.".to_string(),
+                });
+            }
+            Some(Confirmation::X5tS256(x5t)) if x5t_s256.as_ref().ne(&Some(x5t)) => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_grant",
+                    value: "refresh token is bound to another client certificate.".to_string(),
+                });
+            }
+            _ => {}
+        }
+
+        let scope = match scope {
+            Some(scope) => {
+                let scope = scope
+                    .into_iter()
+                    .map(ScopeMethod::new)
+                    .collect::<Vec<ScopeMethod>>();
+                if let Some(exceeded) = scope.iter().find(|req| !current.scope().contains(req)) {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_scope",
+                        value: format!(
+                            "`{}` exceeds the scope originally granted.",
+                            exceeded.as_ref()
+                        ),
+                    });
+                }
+                scope
+            }
+            None => current.scope().clone(),
+        };
+
+        let (resource, scope) = self
+            .resolve_token_target(current.resource(), resource, scope)
+            .await?;
+        let details = self
+            .resolve_token_details(
+                current.authorization_details(),
+                authorization_details.as_deref(),
+            )
+            .await?;
+
+        let Some(client) = self
+            .client_registry()
+            .find_by_id(current.client_id())
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "client no longer exists.".to_string(),
+            });
+        };
+
+        if !issues_refresh_token(&client) {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to use the refresh token grant.".to_string(),
+            });
+        }
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            *current.client_id(),
+            *current.account(),
+            scope,
+            Issuer::default(),
+            resource.unwrap_or_else(|| current.client_id().id().to_string()),
+            current.account().to_string(),
+            Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0),
+        )
+        .with_authorization_details(details);
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        let rotated = RefreshToken::new(
+            RefreshTokenId::default(),
+            created_at,
+            updated_at,
+            *current.family(),
+            token.id(),
+            *current.client_id(),
+            *current.account(),
+            current.scope().clone(),
+            current.resource().map(ToOwned::to_owned),
+            current.authorization_details().clone(),
+            Duration::new(REFRESH_TOKEN_EXPIRES_IN, 0),
+        )
+        .bind(current.confirmation().clone());
+
+        let mut current = current.into_destruct();
+        current.rotated = true;
+        current.date = LoggedAt::new(*current.date.created_at().as_ref(), updated_at);
+        let current = current.freeze();
+
+        // Two requests may have passed the check above with the same token,
+        // and the one that loses the rotation is treated as reuse.
+        if !self.refresh_token_repository().rotate(&current).await? {
+            self.refresh_token_repository()
+                .revoke_family(current.family())
+                .await?;
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "refresh token has already been used.".to_string(),
+            });
+        }
+
+        self.access_token_repository().create(&token).await?;
+        self.refresh_token_repository().create(&rotated).await?;
+
+        Ok(AccessTokenDto::from_with(token, rotated))
+    }
+}
+
+pub trait DependOnRefreshAccessTokenService: 'static + Sync + Send {
+    type RefreshAccessTokenService: RefreshAccessTokenService;
+    fn refresh_access_token_service(&self) -> &Self::RefreshAccessTokenService;
+}
+
+#[async_trait::async_trait]
+pub trait ClientCredentialsService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnAccessTokenRepository
+    + FormatAccessTokenService
+    + ProtectedResourceService
+{
+    /// Issue an access token to a confidential client acting on its own behalf.
+    ///
+    /// No refresh token is issued, as the client can simply request a new access token.
+    /// The client must already be authenticated,
+    /// see [AuthenticateClientService](crate::services::AuthenticateClientService).
+    ///
+    /// See [RFC6749 Section 4.4](https://datatracker.ietf.org/doc/html/rfc6749#section-4.4)
+    async fn issue(&self, issue: ClientCredentialsDto) -> Result<AccessTokenDto, ApplicationError> {
+        let ClientCredentialsDto {
+            client_id,
+            scope,
+            resource,
+            authorization_details,
+            dpop_jkt,
+            x5t_s256,
+        } = issue;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        // https://datatracker.ietf.org/doc/html/rfc6749#section-4.4
+        if let ClientTypes::Public = client.types() {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "public clients cannot use `client_credentials`.".to_string(),
+            });
+        }
+
+        if !client
+            .grant_types()
+            .as_ref()
+            .contains(&GrantType::ClientCredentials)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to use `client_credentials`.".to_string(),
+            });
+        }
+
+        let scope = validate_client_scope(&client, scope)?;
+
+        let (resource, scope) = self.resolve_token_target(None, resource, scope).await?;
+        let details = self
+            .validate_authorization_details(authorization_details.as_deref())
+            .await?;
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        // The client is the subject itself.
+        // https://datatracker.ietf.org/doc/html/rfc9068#section-2.2
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            *client.id(),
+            None,
+            scope,
+            Issuer::default(),
+            resource.unwrap_or_else(|| client.id().id().to_string()),
+            client.id().id().to_string(),
+            Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0),
+        )
+        .with_authorization_details(details);
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        self.access_token_repository().create(&token).await?;
+
+        Ok(token.into())
+    }
+}
+
+pub trait DependOnClientCredentialsService: 'static + Sync + Send {
+    type ClientCredentialsService: ClientCredentialsService;
+    fn client_credentials_service(&self) -> &Self::ClientCredentialsService;
+}
+
+#[async_trait::async_trait]
+pub trait JwtBearerService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnTrustedIssuerRepository
+    + DependOnJwtBearerJtiVolatileRepository
+    + DependOnJwksTransporter
+    + DependOnAccessTokenRepository
+    + FormatAccessTokenService
+    + ProtectedResourceService
+{
+    /// Issue an access token for the subject of an assertion signed by a trusted issuer.
+    ///
+    /// No refresh token is issued, as the client can simply present a new assertion.
+    /// The client must already be authenticated,
+    /// see [AuthenticateClientService](crate::services::AuthenticateClientService).
+    ///
+    /// See [RFC7523 Section 2.1](https://datatracker.ietf.org/doc/html/rfc7523#section-2.1)
+    /// and [RFC7523 Section 3](https://datatracker.ietf.org/doc/html/rfc7523#section-3)
+    async fn issue_by_assertion(
+        &self,
+        issue: JwtBearerDto,
+    ) -> Result<AccessTokenDto, ApplicationError> {
+        let JwtBearerDto {
+            client_id,
+            assertion,
+            scope,
+            resource,
+            dpop_jkt,
+            x5t_s256,
+        } = issue;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        if !client
+            .grant_types()
+            .as_ref()
+            .contains(&GrantType::JWTBearer)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to use `jwt-bearer`.".to_string(),
+            });
+        }
+
+        let assertion = JwtBearerAssertion::new(assertion);
+        let issuer = assertion
+            .unverified_issuer()
+            .map_err(|e| ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: e.to_string(),
+            })?;
+
+        let Some(trusted) = self
+            .trusted_issuer_repository()
+            .find_by_issuer(&issuer)
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("`{}` is not a trusted issuer.", issuer),
+            });
+        };
+
+        if !trusted.allows_client(client.id().id()) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("client may not present assertions of `{}`.", issuer),
+            });
+        }
+
+        let keys = match (trusted.jwks(), trusted.jwks_uri()) {
+            (Some(keys), _) => keys.clone(),
+            (None, Some(uri)) => self.jwks_transporter().fetch(uri).await?,
+            (None, None) => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_grant",
+                    value: format!("`{}` has no keys.", issuer),
+                })
+            }
+        };
+
+        // Either the issuer identifier or the token endpoint identifies this server.
+        // https://datatracker.ietf.org/doc/html/rfc7523#section-3
+        let audiences = if trusted.audiences().is_empty() {
+            let issuer = Issuer::default();
+            vec![
+                issuer.as_ref().to_string(),
+                format!("{}/token", issuer.as_ref()),
+            ]
+        } else {
+            trusted.audiences().to_vec()
+        };
+
+        let assertion = assertion
+            .verify(&keys, trusted.issuer(), &audiences)
+            .map_err(|e| ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: e.to_string(),
+            })?;
+
+        if !trusted.allows_subject(assertion.sub()) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("`{}` may not assert `{}`.", issuer, assertion.sub()),
+            });
+        }
+
+        if !self
+            .jwt_bearer_jti_volatile_repository()
+            .save(&assertion)
+            .await?
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "`jti` of the assertion has already been used.".to_string(),
+            });
+        }
+
+        let scope = validate_client_scope(&client, scope)?;
+        let (resource, scope) = self.resolve_token_target(None, resource, scope).await?;
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        // The subject belongs to the trusted issuer, not to an account of this server.
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            *client.id(),
+            None,
+            scope,
+            Issuer::default(),
+            resource.unwrap_or_else(|| client.id().id().to_string()),
+            assertion.sub(),
+            Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0),
+        );
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        self.access_token_repository().create(&token).await?;
+
+        Ok(token.into())
+    }
+}
+
+pub trait DependOnJwtBearerService: 'static + Sync + Send {
+    type JwtBearerService: JwtBearerService;
+    fn jwt_bearer_service(&self) -> &Self::JwtBearerService;
+}
+
+#[async_trait::async_trait]
+pub trait Saml2BearerService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnTrustedIssuerRepository
+    + DependOnSamlAssertionIdVolatileRepository
+    + DependOnAccountRepository
+    + DependOnAccessTokenRepository
+    + FormatAccessTokenService
+    + ProtectedResourceService
+{
+    /// Issue an access token for the account named by a SAML assertion signed by a trusted issuer.
+    ///
+    /// `NameID` is either the id or the address of the account.
+    /// No refresh token is issued, as the client can simply present a new assertion.
+    /// The client must already be authenticated,
+    /// see [AuthenticateClientService](crate::services::AuthenticateClientService).
+    ///
+    /// See [RFC7522 Section 2.1](https://datatracker.ietf.org/doc/html/rfc7522#section-2.1)
+    /// and [RFC7522 Section 3](https://datatracker.ietf.org/doc/html/rfc7522#section-3)
+    async fn issue_by_saml_assertion(
+        &self,
+        issue: Saml2BearerDto,
+    ) -> Result<AccessTokenDto, ApplicationError> {
+        let Saml2BearerDto {
+            client_id,
+            assertion,
+            scope,
+            resource,
+            dpop_jkt,
+            x5t_s256,
+        } = issue;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        if !client
+            .grant_types()
+            .as_ref()
+            .contains(&GrantType::Saml2Bearer)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to use `saml2-bearer`.".to_string(),
+            });
+        }
+
+        let assertion = SamlBearerAssertion::new(assertion);
+        let issuer = assertion
+            .unverified_issuer()
+            .map_err(|e| ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: e.to_string(),
+            })?;
+
+        let Some(trusted) = self
+            .trusted_issuer_repository()
+            .find_by_issuer(&issuer)
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("`{}` is not a trusted issuer.", issuer),
+            });
+        };
+
+        if !trusted.allows_client(client.id().id()) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("client may not present assertions of `{}`.", issuer),
+            });
+        }
+
+        let Some(certificate) = trusted.certificate() else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("`{}` has no certificate.", issuer),
+            });
+        };
+
+        // The token endpoint is both the recipient and, by default, the audience.
+        // https://datatracker.ietf.org/doc/html/rfc7522#section-3
+        let token_endpoint = format!("{}/token", Issuer::default().as_ref());
+        let audiences = if trusted.audiences().is_empty() {
+            vec![
+                Issuer::default().as_ref().to_string(),
+                token_endpoint.clone(),
+            ]
+        } else {
+            trusted.audiences().to_vec()
+        };
+
+        let assertion = assertion
+            .verify(certificate, trusted.issuer(), &audiences, &token_endpoint)
+            .map_err(|e| ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: e.to_string(),
+            })?;
+
+        if !trusted.allows_subject(assertion.name_id()) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("`{}` may not assert `{}`.", issuer, assertion.name_id()),
+            });
+        }
+
+        if !self
+            .saml_assertion_id_volatile_repository()
+            .save(&assertion)
+            .await?
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "`ID` of the assertion has already been used.".to_string(),
+            });
+        }
+
+        let account = match Uuid::from_str(assertion.name_id()) {
+            Ok(id) => {
+                self.account_repository()
+                    .find_by_id(&UserId::new(id))
+                    .await?
+            }
+            Err(_) => {
+                self.account_repository()
+                    .find_by_address(&Address::new(assertion.name_id()))
+                    .await?
+            }
+        };
+        let Some(account) = account else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: format!("no account matches `{}`.", assertion.name_id()),
+            });
+        };
+
+        let scope = validate_client_scope(&client, scope)?;
+        let (resource, scope) = self.resolve_token_target(None, resource, scope).await?;
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        let account = *account.id();
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            *client.id(),
+            account,
+            scope,
+            Issuer::default(),
+            resource.unwrap_or_else(|| client.id().id().to_string()),
+            account.to_string(),
+            Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0),
+        );
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        self.access_token_repository().create(&token).await?;
+
+        Ok(token.into())
+    }
+}
+
+pub trait DependOnSaml2BearerService: 'static + Sync + Send {
+    type Saml2BearerService: Saml2BearerService;
+    fn saml2_bearer_service(&self) -> &Self::Saml2BearerService;
+}
+
+#[async_trait::async_trait]
+pub trait TokenExchangeService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnAccessTokenRepository
+    + FormatAccessTokenService
+    + ProtectedResourceService
+{
+    /// Exchange an access token of the subject for one the client uses on their behalf,
+    /// e.g. to call another service.
+    ///
+    /// The issued token is restricted to a single audience and at most the scope of the
+    /// subject token, and names the client, or the subject of the `actor_token`, in `act`.
+    /// Only the client itself and its [token_exchange_audiences](Client::token_exchange_audiences)
+    /// may be requested as the audience.
+    /// The client must already be authenticated,
+    /// see [AuthenticateClientService](crate::services::AuthenticateClientService).
+    ///
+    /// See [RFC8693 Section 2](https://datatracker.ietf.org/doc/html/rfc8693#section-2)
+    async fn exchange(
+        &self,
+        exchange: TokenExchangeDto,
+    ) -> Result<AccessTokenDto, ApplicationError> {
+        let TokenExchangeDto {
+            client_id,
+            subject_token,
+            subject_token_type,
+            actor_token,
+            actor_token_type,
+            resource,
+            audience,
+            scope,
+            requested_token_type,
+            dpop_jkt,
+            x5t_s256,
+        } = exchange;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        if let ClientTypes::Public = client.types() {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "public clients cannot exchange tokens.".to_string(),
+            });
+        }
+
+        if !client
+            .grant_types()
+            .as_ref()
+            .contains(&GrantType::TokenExchange)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to exchange tokens.".to_string(),
+            });
+        }
+
+        if let Some(requested) = requested_token_type.filter(|ty| ty.ne(ACCESS_TOKEN_TYPE)) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: format!("`{}` cannot be issued.", requested),
+            });
+        }
+
+        let subject = self
+            .find_exchangeable_token(&subject_token, &subject_token_type, "subject_token")
+            .await?;
+
+        let actor = match (actor_token, actor_token_type) {
+            (Some(token), Some(token_type)) => Some(
+                self.find_exchangeable_token(&token, &token_type, "actor_token")
+                    .await?,
+            ),
+            (None, None) => None,
+            _ => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_request",
+                    value: "`actor_token` and `actor_token_type` must be given together."
+                        .to_string(),
+                })
+            }
+        };
+
+        let ctx = subject.context();
+
+        let scope = match scope {
+            Some(scope) => {
+                let scope = scope
+                    .into_iter()
+                    .map(ScopeMethod::new)
+                    .collect::<Vec<ScopeMethod>>();
+                if let Some(exceeded) = scope.iter().find(|req| !ctx.scope().contains(req)) {
+                    return Err(ApplicationError::InvalidValue {
+                        method: "invalid_scope",
+                        value: format!(
+                            "`{}` exceeds the scope of `subject_token`.",
+                            exceeded.as_ref()
+                        ),
+                    });
+                }
+                scope
+            }
+            None => ctx.scope().clone(),
+        };
+
+        // A `resource` has to be a known protected resource, and narrows the scope to it.
+        // https://datatracker.ietf.org/doc/html/rfc8693#section-2.1
+        let (audience, scope) = match (audience, resource) {
+            (Some(_), Some(_)) => {
+                return Err(ApplicationError::InvalidValue {
+                    method: "invalid_request",
+                    value: "only one of `audience` and `resource` may be given.".to_string(),
+                })
+            }
+            (Some(audience), None) => (audience, scope),
+            (None, Some(resource)) => {
+                let (target, scope) = self
+                    .resolve_token_target(None, Some(resource), scope)
+                    .await?;
+                (
+                    target.unwrap_or_else(|| client.id().id().to_string()),
+                    scope,
+                )
+            }
+            (None, None) => (client.id().id().to_string(), scope),
+        };
+
+        if audience.ne(&client.id().id().to_string())
+            && !client.token_exchange_audiences().contains(&audience)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_target",
+                value: format!(
+                    "client is not allowed to exchange tokens for `{}`.",
+                    audience
+                ),
+            });
+        }
+
+        // Keep the delegation chain of the subject token as the prior actors.
+        // https://datatracker.ietf.org/doc/html/rfc8693#section-4.1
+        let act = Actor::new(
+            actor.map_or_else(
+                || client.id().id().to_string(),
+                |actor| actor.context().subject().as_ref().to_string(),
+            ),
+            ctx.actor().clone(),
+        );
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        // The exchanged token must not outlive the subject token.
+        let expires_in =
+            Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0).min(*ctx.expired_in().as_ref() - created_at);
+
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            *client.id(),
+            *ctx.account(),
+            scope,
+            Issuer::default(),
+            audience,
+            ctx.subject().as_ref(),
+            expires_in,
+        )
+        .delegate(act);
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        self.access_token_repository().create(&token).await?;
+
+        let dto = AccessTokenDto::from(token);
+        Ok(AccessTokenDto {
+            issued_token_type: Some(ACCESS_TOKEN_TYPE.to_string()),
+            ..dto
+        })
+    }
+
+    /// Find an active access token issued by this server, presented as `name`.
+    ///
+    /// See [RFC8693 Section 2.2.2](https://datatracker.ietf.org/doc/html/rfc8693#section-2.2.2)
+    async fn find_exchangeable_token(
+        &self,
+        token: &str,
+        token_type: &str,
+        name: &str,
+    ) -> Result<AccessToken, ApplicationError> {
+        if token_type.ne(ACCESS_TOKEN_TYPE) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: format!("`{}_type` of `{}` is not supported.", name, token_type),
+            });
+        }
+
+        let Some(token) = self
+            .access_token_repository()
+            .find_by_id(&AccessTokenId::new(token))
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: format!("`{}` is invalid.", name),
+            });
+        };
+
+        let ctx = token.context();
+        if ctx.expired_in().is_expired() || OffsetDateTime::now_utc() < *ctx.not_before().as_ref() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: format!("`{}` is not active.", name),
+            });
+        }
+
+        Ok(token)
+    }
+}
+
+pub trait DependOnTokenExchangeService: 'static + Sync + Send {
+    type TokenExchangeService: TokenExchangeService;
+    fn token_exchange_service(&self) -> &Self::TokenExchangeService;
+}
+
+#[async_trait::async_trait]
+pub trait IntrospectTokenService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnAccessTokenRepository
+    + DependOnRefreshTokenRepository
+{
+    /// Tell a resource server whether the token is active, and its context if so.
+    ///
+    /// Tokens that are unknown, expired or already rotated are reported only as inactive.
+    /// The caller must already be authenticated and be a confidential client.
+    ///
+    /// See [RFC7662 Section 2](https://datatracker.ietf.org/doc/html/rfc7662#section-2)
+    async fn introspect(
+        &self,
+        introspect: IntrospectTokenDto,
+    ) -> Result<TokenIntrospectionDto, ApplicationError> {
+        let IntrospectTokenDto {
+            client_id,
+            token,
+            token_type_hint,
+        } = introspect;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        // https://datatracker.ietf.org/doc/html/rfc7662#section-4
+        if let ClientTypes::Public = client.types() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "public clients cannot introspect tokens.".to_string(),
+            });
+        }
+
+        // The hint only decides which kind of token is looked up first.
+        // https://datatracker.ietf.org/doc/html/rfc7662#section-2.1
+        let introspected = if token_type_hint.as_deref() == Some("refresh_token") {
+            match self.introspect_refresh_token(&token).await? {
+                Some(introspected) => Some(introspected),
+                None => self.introspect_access_token(&token).await?,
+            }
+        } else {
+            match self.introspect_access_token(&token).await? {
+                Some(introspected) => Some(introspected),
+                None => self.introspect_refresh_token(&token).await?,
+            }
+        };
+
+        Ok(introspected.unwrap_or_else(TokenIntrospectionDto::inactive))
+    }
+
+    async fn introspect_access_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<TokenIntrospectionDto>, ApplicationError> {
+        let Some(token) = self
+            .access_token_repository()
+            .find_by_id(&AccessTokenId::new(token))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let ctx = token.context();
+        if ctx.expired_in().is_expired() || OffsetDateTime::now_utc() < *ctx.not_before().as_ref() {
+            return Ok(Some(TokenIntrospectionDto::inactive()));
+        }
+
+        Ok(Some(token.into()))
+    }
+
+    async fn introspect_refresh_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<TokenIntrospectionDto>, ApplicationError> {
+        let Some(token) = self
+            .refresh_token_repository()
+            .find_by_id(&RefreshTokenId::new(token))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        if token.is_rotated() || token.expired_in().is_expired() {
+            return Ok(Some(TokenIntrospectionDto::inactive()));
+        }
+
+        Ok(Some(token.into()))
+    }
+}
+
+pub trait DependOnIntrospectTokenService: 'static + Sync + Send {
+    type IntrospectTokenService: IntrospectTokenService;
+    fn introspect_token_service(&self) -> &Self::IntrospectTokenService;
+}
+
+#[async_trait::async_trait]
+pub trait RevokeTokenService:
+    'static + Sync + Send + DependOnAccessTokenRepository + DependOnRefreshTokenRepository
+{
+    /// Invalidate a token at the request of the client it was issued to.
+    ///
+    /// Revoking a refresh token revokes its whole family, including the access tokens derived from it.
+    /// Unknown tokens are ignored, as there is nothing left to revoke.
+    /// The client must already be authenticated.
+    ///
+    /// See [RFC7009 Section 2](https://datatracker.ietf.org/doc/html/rfc7009#section-2)
+    async fn revoke(&self, revoke: RevokeTokenDto) -> Result<(), ApplicationError> {
+        let RevokeTokenDto {
+            client_id,
+            token,
+            token_type_hint,
+        } = revoke;
+
+        // The hint only decides which kind of token is looked up first.
+        // https://datatracker.ietf.org/doc/html/rfc7009#section-2.1
+        if token_type_hint.as_deref() == Some("refresh_token") {
+            if !self.revoke_refresh_token(&client_id, &token).await? {
+                self.revoke_access_token(&client_id, &token).await?;
+            }
+        } else if !self.revoke_access_token(&client_id, &token).await? {
+            self.revoke_refresh_token(&client_id, &token).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `false` if the token is not an access token.
+    async fn revoke_access_token(
+        &self,
+        client_id: &Uuid,
+        token: &str,
+    ) -> Result<bool, ApplicationError> {
+        let id = AccessTokenId::new(token);
+        let Some(token) = self.access_token_repository().find_by_id(&id).await? else {
+            return Ok(false);
+        };
+
+        if token.context().client_id().id().ne(client_id) {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "token was issued to another client.".to_string(),
+            });
+        }
+
+        self.access_token_repository().delete(&id).await?;
+
+        Ok(true)
+    }
+
+    /// Returns `false` if the token is not a refresh token.
+    async fn revoke_refresh_token(
+        &self,
+        client_id: &Uuid,
+        token: &str,
+    ) -> Result<bool, ApplicationError> {
+        let id = RefreshTokenId::new(token);
+        let Some(token) = self.refresh_token_repository().find_by_id(&id).await? else {
+            return Ok(false);
+        };
+
+        if token.client_id().id().ne(client_id) {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "token was issued to another client.".to_string(),
+            });
+        }
+
+        self.refresh_token_repository()
+            .revoke_family(token.family())
+            .await?;
+
+        Ok(true)
+    }
+}
+
+pub trait DependOnRevokeTokenService: 'static + Sync + Send {
+    type RevokeTokenService: RevokeTokenService;
+    fn revoke_token_service(&self) -> &Self::RevokeTokenService;
+}
+
+#[async_trait::async_trait]
+pub trait DeviceAuthorizationService:
+    'static + Sync + Send + DependOnClientRegistry + DependOnDeviceAuthorizationVolatileRepository
+{
+    /// Issue a device code and a user code to a client that cannot receive a redirect.
+    ///
+    /// The client must already be authenticated,
+    /// see [AuthenticateClientService](crate::services::AuthenticateClientService).
+    ///
+    /// See [RFC8628 Section 3.1](https://datatracker.ietf.org/doc/html/rfc8628#section-3.1)
+    async fn authorize_device(
+        &self,
+        request: DeviceAuthorizationRequestDto,
+    ) -> Result<DeviceAuthorizationDto, ApplicationError> {
+        let DeviceAuthorizationRequestDto {
+            client_id,
+            scope,
+            verification_uri,
+        } = request;
+
+        let client_id = ClientId::new_at_now(client_id);
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value: "client authentication failed.".to_string(),
+            });
+        };
+
+        if !client
+            .grant_types()
+            .as_ref()
+            .contains(&GrantType::DeviceCode)
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "unauthorized_client",
+                value: "client is not allowed to use the device authorization grant.".to_string(),
+            });
+        }
+
+        let scope = validate_client_scope(&client, scope)?;
+
+        let device = DeviceAuthorization::new(
+            client_id,
+            scope,
+            verification_uri,
+            Duration::new(DEVICE_CODE_EXPIRES_IN, 0),
+        );
+
+        self.device_authorization_volatile_repository()
+            .save(&device)
+            .await?;
+
+        Ok(device.into())
+    }
+}
+
+pub trait DependOnDeviceAuthorizationService: 'static + Sync + Send {
+    type DeviceAuthorizationService: DeviceAuthorizationService;
+    fn device_authorization_service(&self) -> &Self::DeviceAuthorizationService;
+}
+
+#[async_trait::async_trait]
+pub trait VerifyUserCodeService:
+    'static
+    + Sync
+    + Send
+    + DependOnSessionVolatileRepository
+    + DependOnDeviceAuthorizationVolatileRepository
+    + DependOnPendingAuthorizeTokenRepository
+    + DependOnStateVolatileRepository
+{
+    /// Open the decision for the request the user code was issued for.
+    ///
+    /// The decision is stored like a pending authorization request,
+    /// so it is accepted or rejected at the same decision endpoint,
+    /// see [AcceptAuthorizeTokenService] and [RejectAuthorizeTokenService].
+    ///
+    /// See [RFC8628 Section 3.3](https://datatracker.ietf.org/doc/html/rfc8628#section-3.3)
+    async fn verify_user_code(
+        &self,
+        verify: VerifyUserCodeDto,
+    ) -> Result<DeviceDecisionDto, ApplicationError> {
+        let VerifyUserCodeDto { user_code, session } = verify;
+
+        let Some(session) = session else {
+            return Err(ApplicationError::RequireUserAction(ExpectUserAction::Login));
+        };
+        match self
+            .session_volatile_repository()
+            .find(&SessionId::new(session))
+            .await?
+        {
+            Some(session) if !session.exp().is_expired() => {}
+            _ => return Err(ApplicationError::RequireUserAction(ExpectUserAction::Login)),
+        }
+
+        let user_code = UserCode::new(user_code);
+        let Some(device) = self
+            .device_authorization_volatile_repository()
+            .find_by_user_code(&user_code)
+            .await?
+            .filter(|device| !device.expired_in().is_expired())
+        else {
+            return Err(ApplicationError::NotFound {
+                method: "find_by_user_code",
+                entity: "device_authorization",
+                id: user_code.formatted(),
+            });
+        };
+
+        if device.is_verified() {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_request",
+                value: "user code has already been used.".to_string(),
+            });
+        }
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        let token = AuthorizeToken::new(
+            AuthorizeTokenId::default(),
+            created_at,
+            updated_at,
+            None,
+            *device.client_id(),
+            device.scopes().clone(),
+            ResponseType::Code,
+            device.verification_uri().as_ref(),
+            None,
+            None,
+            None,
+            Duration::new(DEVICE_CODE_EXPIRES_IN, 0),
+        )
+        .for_device();
+
+        let ticket = TicketId::default();
+        let state = State::default();
+        self.state_volatile_repository()
+            .save(&ticket, &state)
+            .await?;
+        self.pending_authorize_token_repository()
+            .save(&ticket, &token)
+            .await?;
+
+        let device = device.verify(ticket.clone(), token.id().clone());
+        self.device_authorization_volatile_repository()
+            .save(&device)
+            .await?;
+
+        Ok(DeviceDecisionDto {
+            ticket: ticket.into(),
+            state: state.into(),
+            client_id: *device.client_id().id(),
+            scope: device
+                .scopes()
+                .iter()
+                .map(|s| s.as_ref().to_owned())
+                .collect(),
+        })
+    }
+}
+
+pub trait DependOnVerifyUserCodeService: 'static + Sync + Send {
+    type VerifyUserCodeService: VerifyUserCodeService;
+    fn verify_user_code_service(&self) -> &Self::VerifyUserCodeService;
+}
+
+#[async_trait::async_trait]
+pub trait DeviceAccessTokenService:
+    'static
+    + Sync
+    + Send
+    + DependOnClientRegistry
+    + DependOnDeviceAuthorizationVolatileRepository
+    + DependOnPendingAuthorizeTokenRepository
+    + DependOnAuthorizeTokenRepository
+    + DependOnAccessTokenRepository
+    + DependOnRefreshTokenRepository
+    + FormatAccessTokenService
+{
+    /// Answer a poll from the device, issuing an access token once the user has accepted.
+    ///
+    /// See [RFC8628 Section 3.5](https://datatracker.ietf.org/doc/html/rfc8628#section-3.5)
+    async fn exchange_device_code(
+        &self,
+        exchange: DeviceAccessTokenDto,
+    ) -> Result<AccessTokenDto, ApplicationError> {
+        let DeviceAccessTokenDto {
+            device_code,
+            client_id,
+            dpop_jkt,
+            x5t_s256,
+        } = exchange;
+
+        let Some(device) = self
+            .device_authorization_volatile_repository()
+            .find_by_device_code(&DeviceCode::new(device_code))
+            .await?
+        else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "device code is invalid or already used.".to_string(),
+            });
+        };
+
+        if device.client_id().id().ne(&client_id) {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "device code was issued to another client.".to_string(),
+            });
+        }
+
+        if device.expired_in().is_expired() {
+            self.device_authorization_volatile_repository()
+                .dele(&device)
+                .await?;
+            return Err(ApplicationError::InvalidValue {
+                method: "expired_token",
+                value: "device code is expired.".to_string(),
+            });
+        }
+
+        let (device, too_fast) = device.poll(OffsetDateTime::now_utc());
+        self.device_authorization_volatile_repository()
+            .save(&device)
+            .await?;
+
+        if too_fast {
+            return Err(ApplicationError::InvalidValue {
+                method: "slow_down",
+                value: format!("poll at most every {} seconds.", device.interval()),
+            });
+        }
+
+        let (Some(ticket), Some(code)) = (device.ticket(), device.authorize_token()) else {
+            return Err(ApplicationError::InvalidValue {
+                method: "authorization_pending",
+                value: "user has not entered the user code yet.".to_string(),
+            });
+        };
+
+        if self
+            .pending_authorize_token_repository()
+            .find(ticket)
+            .await?
+            .is_some()
+        {
+            return Err(ApplicationError::InvalidValue {
+                method: "authorization_pending",
+                value: "user has not decided yet.".to_string(),
+            });
+        }
+
+        // The decision is gone without a token left, so the user has rejected it,
+        // or a concurrent poll has already taken the token.
+        let Some(token) = self.authorize_token_repository().take(code).await? else {
+            self.device_authorization_volatile_repository()
+                .dele(&device)
+                .await?;
+            return Err(ApplicationError::InvalidValue {
+                method: "access_denied",
+                value: "user has denied the authorization request.".to_string(),
+            });
+        };
+
+        self.device_authorization_volatile_repository()
+            .dele(&device)
+            .await?;
+
+        let DestructAuthorizeToken { owned_by, ctx, .. } = token.into_destruct();
+        let DestructAuthorizeTokenContext {
+            client_id, scopes, ..
+        } = ctx.into_destruct();
+
+        let account = UserId::try_from(owned_by)?;
+
+        let Some(client) = self.client_registry().find_by_id(&client_id).await? else {
+            return Err(ApplicationError::InvalidValue {
+                method: "invalid_grant",
+                value: "client no longer exists.".to_string(),
+            });
+        };
+
+        let created_at = OffsetDateTime::now_utc();
+        let updated_at = created_at;
+
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            updated_at,
+            client_id,
+            account,
+            scopes,
+            Issuer::default(),
+            client_id.id().to_string(),
+            account.to_string(),
+            Duration::new(ACCESS_TOKEN_EXPIRES_IN, 0),
+        );
+        let token = self
+            .format(
+                client.access_token_format(),
+                token.bind(Confirmation::from_parts(dpop_jkt, x5t_s256)),
+            )
+            .await?;
+
+        let refresh = issues_refresh_token(&client).then(|| {
+            RefreshToken::new(
+                RefreshTokenId::default(),
+                created_at,
+                updated_at,
+                RefreshTokenFamily::default(),
+                token.id(),
+                client_id,
+                account,
+                token.context().scope().clone(),
+                None,
+                None,
+                Duration::new(REFRESH_TOKEN_EXPIRES_IN, 0),
+            )
+            .bind(refresh_confirmation(&client, &token))
+        });
+
+        self.access_token_repository().create(&token).await?;
+        if let Some(refresh) = &refresh {
+            self.refresh_token_repository().create(refresh).await?;
+        }
+
+        Ok(AccessTokenDto::from_with(token, refresh))
+    }
+}
+
+pub trait DependOnDeviceAccessTokenService: 'static + Sync + Send {
+    type DeviceAccessTokenService: DeviceAccessTokenService;
+    fn device_access_token_service(&self) -> &Self::DeviceAccessTokenService;
 }
@@ -2,4 +2,5 @@ pub mod account;
 pub mod client;
 pub mod mfa_code;
 pub mod session;
+pub mod signing_key;
 pub mod token;
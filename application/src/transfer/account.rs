@@ -97,3 +97,17 @@ impl CreateTemporaryAccountDto {
         }
     }
 }
+
+/// Claims about the resource owner returned from the UserInfo Endpoint.
+///
+/// Claims that the granted scopes do not cover are `None`.
+///
+/// See [OpenID Connect Core 1.0 Section 5.1](https://openid.net/specs/openid-connect-core-1_0.html#StandardClaims)
+#[derive(Debug)]
+pub struct UserInfoDto {
+    pub sub: String,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+}
@@ -1,8 +1,10 @@
 use kernel::external::{JsonWebKey, OffsetDateTime, Uuid};
 use kernel::prelude::entities::{
-    Client, ClientSecret, DestructClient, DestructClientId, DestructClientSecret,
-    GrantType as GrantTypeDomain, Jwks, ResponseType as ResponseTypeDomain, ScopeDescription,
-    ScopeMethod, TokenEndPointAuthMethod as TokenEndPointAuthMethodDomain,
+    AccessTokenFormat as AccessTokenFormatDomain, Client, ClientSecret, DestructClient,
+    DestructClientId, DestructClientSecret, GrantType as GrantTypeDomain, Jwks,
+    ResponseType as ResponseTypeDomain, ScopeDescription, ScopeMethod,
+    TlsClientAuthSubject as TlsClientAuthSubjectDomain,
+    TokenEndPointAuthMethod as TokenEndPointAuthMethodDomain,
 };
 
 #[derive(Debug)]
@@ -28,6 +30,12 @@ pub struct ClientDto {
     pub jwks: Option<JwksDto>,
     pub conf_access_token: String,
     pub conf_endpoint: String,
+    pub access_token_format: AccessTokenFormatDto,
+    pub require_pushed_authorization_requests: bool,
+    pub require_signed_request_object: bool,
+    pub request_uris: Vec<String>,
+    pub tls_client_auth_subject: Option<TlsClientAuthSubjectDto>,
+    pub token_exchange_audiences: Vec<String>,
 }
 
 impl From<Client> for ClientDto {
@@ -51,6 +59,12 @@ impl From<Client> for ClientDto {
             jwks,
             conf_token,
             conf_endpoint,
+            access_token_format,
+            require_pushed_authorization_requests,
+            require_signed_request_object,
+            request_uris,
+            tls_client_auth_subject,
+            token_exchange_audiences,
         } = value.into_destruct();
 
         let DestructClientId { id, issued_at } = id.into_destruct();
@@ -58,8 +72,9 @@ impl From<Client> for ClientDto {
         let confidential: Option<ClientSecret> = types.into();
         let confidential = match confidential {
             Some(secret) => {
-                let DestructClientSecret { secret, expires_at } = secret.into_destruct();
-                (Some(secret), expires_at)
+                // Only the hash is held, so the plain secret is filled in on registration.
+                let DestructClientSecret { expires_at, .. } = secret.into_destruct();
+                (None, expires_at)
             }
             None => (None, None),
         };
@@ -85,6 +100,12 @@ impl From<Client> for ClientDto {
             jwks: jwks.map(Into::into),
             conf_access_token: conf_token.into(),
             conf_endpoint: conf_endpoint.into(),
+            access_token_format: access_token_format.into(),
+            require_pushed_authorization_requests,
+            require_signed_request_object,
+            request_uris,
+            tls_client_auth_subject: tls_client_auth_subject.map(Into::into),
+            token_exchange_audiences,
         }
     }
 }
@@ -96,6 +117,9 @@ pub enum TokenEndPointAuthMethodDto {
     None,
 
     PrivateKeyJWT,
+
+    TlsClientAuth,
+    SelfSignedTlsClientAuth,
 }
 
 impl From<TokenEndPointAuthMethodDomain> for TokenEndPointAuthMethodDto {
@@ -105,6 +129,60 @@ impl From<TokenEndPointAuthMethodDomain> for TokenEndPointAuthMethodDto {
             TokenEndPointAuthMethodDomain::ClientSecretBasic => Self::ClientSecretBasic,
             TokenEndPointAuthMethodDomain::None => Self::None,
             TokenEndPointAuthMethodDomain::PrivateKeyJWT => Self::PrivateKeyJWT,
+            TokenEndPointAuthMethodDomain::TlsClientAuth => Self::TlsClientAuth,
+            TokenEndPointAuthMethodDomain::SelfSignedTlsClientAuth => Self::SelfSignedTlsClientAuth,
+        }
+    }
+}
+
+/// Subject DN or SAN of the certificate a `tls_client_auth` client authenticates with.
+///
+/// See [RFC8705 Section 2.1.2](https://datatracker.ietf.org/doc/html/rfc8705#section-2.1.2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TlsClientAuthSubjectDto {
+    SubjectDn(String),
+    SanDns(String),
+    SanUri(String),
+    SanIp(String),
+    SanEmail(String),
+}
+
+impl From<TlsClientAuthSubjectDomain> for TlsClientAuthSubjectDto {
+    fn from(value: TlsClientAuthSubjectDomain) -> Self {
+        match value {
+            TlsClientAuthSubjectDomain::SubjectDn(dn) => Self::SubjectDn(dn),
+            TlsClientAuthSubjectDomain::SanDns(dns) => Self::SanDns(dns),
+            TlsClientAuthSubjectDomain::SanUri(uri) => Self::SanUri(uri),
+            TlsClientAuthSubjectDomain::SanIp(ip) => Self::SanIp(ip),
+            TlsClientAuthSubjectDomain::SanEmail(email) => Self::SanEmail(email),
+        }
+    }
+}
+
+impl From<TlsClientAuthSubjectDto> for TlsClientAuthSubjectDomain {
+    fn from(value: TlsClientAuthSubjectDto) -> Self {
+        match value {
+            TlsClientAuthSubjectDto::SubjectDn(dn) => Self::SubjectDn(dn),
+            TlsClientAuthSubjectDto::SanDns(dns) => Self::SanDns(dns),
+            TlsClientAuthSubjectDto::SanUri(uri) => Self::SanUri(uri),
+            TlsClientAuthSubjectDto::SanIp(ip) => Self::SanIp(ip),
+            TlsClientAuthSubjectDto::SanEmail(email) => Self::SanEmail(email),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum AccessTokenFormatDto {
+    #[default]
+    Opaque,
+    Jwt,
+}
+
+impl From<AccessTokenFormatDomain> for AccessTokenFormatDto {
+    fn from(value: AccessTokenFormatDomain) -> Self {
+        match value {
+            AccessTokenFormatDomain::Opaque => Self::Opaque,
+            AccessTokenFormatDomain::Jwt => Self::Jwt,
         }
     }
 }
@@ -118,6 +196,8 @@ pub enum GrantTypeDto {
     RefreshToken,
     JWTBearer,
     Saml2Bearer,
+    DeviceCode,
+    TokenExchange,
 }
 
 impl From<GrantTypeDomain> for GrantTypeDto {
@@ -130,6 +210,8 @@ impl From<GrantTypeDomain> for GrantTypeDto {
             GrantTypeDomain::RefreshToken => Self::RefreshToken,
             GrantTypeDomain::JWTBearer => Self::JWTBearer,
             GrantTypeDomain::Saml2Bearer => Self::Saml2Bearer,
+            GrantTypeDomain::DeviceCode => Self::DeviceCode,
+            GrantTypeDomain::TokenExchange => Self::TokenExchange,
         }
     }
 }
@@ -196,6 +278,12 @@ pub struct RegisterClientDto {
     pub contacts: Vec<String>,
     pub jwks: Option<String>,
     pub jwks_uri: Option<String>,
+    pub access_token_format: AccessTokenFormatDto,
+    pub require_pushed_authorization_requests: bool,
+    pub require_signed_request_object: bool,
+    pub request_uris: Vec<String>,
+    pub tls_client_auth_subject: Option<TlsClientAuthSubjectDto>,
+    pub token_exchange_audiences: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -214,4 +302,24 @@ pub struct UpdateClientDto {
     pub scopes: Vec<ScopeDto>,
     pub contacts: Vec<String>,
     pub jwks: Option<String>,
+    pub access_token_format: AccessTokenFormatDto,
+    pub require_pushed_authorization_requests: bool,
+    pub require_signed_request_object: bool,
+    pub request_uris: Vec<String>,
+    pub tls_client_auth_subject: Option<TlsClientAuthSubjectDto>,
+    pub token_exchange_audiences: Vec<String>,
+}
+
+/// Credentials presented by the client at the token endpoint.
+///
+/// See [RFC6749 Section 2.3](https://datatracker.ietf.org/doc/html/rfc6749#section-2.3)
+#[derive(Debug)]
+pub struct ClientAuthenticationDto {
+    pub client_id: Uuid,
+    pub method: TokenEndPointAuthMethodDto,
+    pub secret: Option<String>,
+    /// Signed JWT for `private_key_jwt`.
+    pub assertion: Option<String>,
+    /// PEM encoded certificate presented on the mutual-TLS connection.
+    pub certificate: Option<String>,
 }
@@ -0,0 +1,8 @@
+/// JWK Set of the keys this server signs tokens with.
+///
+/// See [RFC7517 Section 5](https://datatracker.ietf.org/doc/html/rfc7517#section-5)
+#[derive(Debug)]
+pub struct JwkSetDto {
+    /// Each key is a JWK document.
+    pub keys: Vec<String>,
+}
@@ -1,4 +1,11 @@
 mod access;
 mod authorize;
+mod device;
+mod dpop;
+mod introspection;
+mod par;
+mod revocation;
 
-pub use self::{access::*, authorize::*};
+pub use self::{
+    access::*, authorize::*, device::*, dpop::*, introspection::*, par::*, revocation::*,
+};
@@ -1,5 +1,179 @@
+use kernel::external::{OffsetDateTime, Uuid};
+use kernel::prelude::entities::{
+    AccessToken, Confirmation, DestructAccessToken, DestructAccessTokenContext, RefreshToken,
+};
+
+#[derive(Debug)]
+pub struct AccessTokenDto {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub refresh_token: Option<String>,
+    pub scope: Vec<String>,
+    pub id_token: Option<String>,
+    /// Type of the issued token, only returned by the token exchange.
+    ///
+    /// See [RFC8693 Section 2.2.1](https://datatracker.ietf.org/doc/html/rfc8693#section-2.2.1)
+    pub issued_token_type: Option<String>,
+    /// JSON encoded authorization details the access token is granted for.
+    ///
+    /// See [RFC9396 Section 7](https://datatracker.ietf.org/doc/html/rfc9396#section-7)
+    pub authorization_details: Option<String>,
+}
+
+impl AccessTokenDto {
+    pub fn from_with(origin: AccessToken, refresh: impl Into<Option<RefreshToken>>) -> Self {
+        let dto = Self::from(origin);
+        Self {
+            refresh_token: refresh
+                .into()
+                .map(|refresh| refresh.id().as_ref().to_owned()),
+            ..dto
+        }
+    }
+}
+
+impl From<AccessToken> for AccessTokenDto {
+    fn from(origin: AccessToken) -> Self {
+        let DestructAccessToken { id, ctx, .. } = origin.into_destruct();
+        let DestructAccessTokenContext {
+            scope,
+            exp,
+            cnf,
+            authorization_details,
+            ..
+        } = ctx.into_destruct();
+
+        let expires_in = (*exp.as_ref() - OffsetDateTime::now_utc()).whole_seconds();
+
+        Self {
+            access_token: id.into(),
+            token_type: cnf
+                .as_ref()
+                .map_or("Bearer", Confirmation::token_type)
+                .to_string(),
+            expires_in,
+            refresh_token: None,
+            scope: scope.into_iter().map(Into::into).collect(),
+            id_token: None,
+            issued_token_type: None,
+            authorization_details: authorization_details.map(|details| details.to_json()),
+        }
+    }
+}
+
+/// Parameters of the Access Token Request in the Authorization Code Grant.
+///
+/// Defined in [RFC6749 Section 4.1.3](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.3)
+/// and [RFC7636 Section 4.5](https://datatracker.ietf.org/doc/html/rfc7636#section-4.5)
+#[derive(Debug)]
+pub struct CreateAccessTokenDto {
+    pub code: String,
+    pub redirect_uri: Option<String>,
+    pub client_id: Uuid,
+    pub code_verifier: String,
+    /// Protected resource the access token is requested for.
+    ///
+    /// See [RFC8707 Section 2.2](https://datatracker.ietf.org/doc/html/rfc8707#section-2.2)
+    pub resource: Option<String>,
+    /// JSON encoded authorization details the access token is requested for.
+    ///
+    /// See [RFC9396 Section 6](https://datatracker.ietf.org/doc/html/rfc9396#section-6)
+    pub authorization_details: Option<String>,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
+
+/// Parameters of the Refresh Token Request.
+///
+/// Defined in [RFC6749 Section 6](https://datatracker.ietf.org/doc/html/rfc6749#section-6)
+#[derive(Debug)]
+pub struct RefreshAccessTokenDto {
+    pub refresh_token: String,
+    pub client_id: Uuid,
+    pub scope: Option<Vec<String>>,
+    /// Protected resource the access token is requested for.
+    pub resource: Option<String>,
+    /// JSON encoded authorization details the access token is requested for.
+    ///
+    /// See [RFC9396 Section 6](https://datatracker.ietf.org/doc/html/rfc9396#section-6)
+    pub authorization_details: Option<String>,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
+
+/// Parameters of the Access Token Request in the Client Credentials Grant.
+///
+/// Defined in [RFC6749 Section 4.4.2](https://datatracker.ietf.org/doc/html/rfc6749#section-4.4.2)
+#[derive(Debug)]
+pub struct ClientCredentialsDto {
+    pub client_id: Uuid,
+    pub scope: Option<Vec<String>>,
+    /// Protected resource the access token is requested for.
+    pub resource: Option<String>,
+    /// JSON encoded authorization details the access token is requested for.
+    ///
+    /// See [RFC9396 Section 6](https://datatracker.ietf.org/doc/html/rfc9396#section-6)
+    pub authorization_details: Option<String>,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
+
+/// Parameters of the Token Exchange Request.
+///
+/// Defined in [RFC8693 Section 2.1](https://datatracker.ietf.org/doc/html/rfc8693#section-2.1)
+#[derive(Debug)]
+pub struct TokenExchangeDto {
+    pub client_id: Uuid,
+    pub subject_token: String,
+    pub subject_token_type: String,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    pub resource: Option<String>,
+    pub audience: Option<String>,
+    pub scope: Option<Vec<String>>,
+    pub requested_token_type: Option<String>,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
+
+/// Parameters of the Access Token Request with a JWT as the authorization grant.
+///
+/// Defined in [RFC7523 Section 2.1](https://datatracker.ietf.org/doc/html/rfc7523#section-2.1)
 #[derive(Debug)]
-pub struct AccessTokenDto {}
+pub struct JwtBearerDto {
+    pub client_id: Uuid,
+    pub assertion: String,
+    pub scope: Option<Vec<String>>,
+    /// Protected resource the access token is requested for.
+    pub resource: Option<String>,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
 
+/// Parameters of the Access Token Request with a SAML 2.0 assertion as the authorization grant.
+///
+/// Defined in [RFC7522 Section 2.1](https://datatracker.ietf.org/doc/html/rfc7522#section-2.1)
 #[derive(Debug)]
-pub struct CreateAccessTokenDto {}
+pub struct Saml2BearerDto {
+    pub client_id: Uuid,
+    /// Base64url encoded assertion.
+    pub assertion: String,
+    pub scope: Option<Vec<String>>,
+    /// Protected resource the access token is requested for.
+    pub resource: Option<String>,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
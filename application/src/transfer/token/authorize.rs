@@ -1,41 +1,29 @@
 use kernel::external::Uuid;
-use kernel::prelude::entities::{
-    AuthorizeToken, DestructAuthorizeToken, DestructAuthorizeTokenContext,
-};
+use kernel::prelude::entities::{AuthorizeToken, TicketId};
 
+/// Authorization response to deliver to the redirect uri of the client,
+/// either a successful one or an error.
+///
+/// See [RFC6749 Section 4.1.2](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2)
 #[derive(Debug)]
-pub struct AuthorizeTokenDto {
-    pub token_id: String,
-    pub token_type: String,
-    pub response_type: String,
+pub struct AuthorizationResponseDto {
     pub redirect_uri: String,
-    pub scope: Vec<String>,
-    pub state: String,
+    /// One of `query`, `fragment` or `form_post`.
+    pub response_mode: String,
+    /// Parameters to deliver, or only `response` for the JWT secured response modes.
+    pub params: Vec<(String, String)>,
 }
 
-impl AuthorizeTokenDto {
-    pub fn from_with(
-        origin: AuthorizeToken,
-        token_type: impl Into<String>,
-        state: impl Into<String>,
-    ) -> Self {
-        let DestructAuthorizeToken { id, ctx, .. } = origin.into_destruct();
-        let DestructAuthorizeTokenContext {
-            scopes,
-            response_type,
-            redirect_uri,
-            ..
-        } = ctx.into_destruct();
-
-        Self {
-            token_id: id.into(),
-            token_type: token_type.into(),
-            response_type: response_type.into(),
-            redirect_uri: redirect_uri.as_ref().to_owned(),
-            scope: scopes.into_iter().map(Into::into).collect(),
-            state: state.into(),
-        }
-    }
+/// Outcome of the user's decision on a pending request.
+#[derive(Debug)]
+pub enum DecisionDto {
+    /// Delivered to the redirect uri of the client.
+    Redirect(AuthorizationResponseDto),
+    /// A device authorization request has no redirect uri,
+    /// as the device learns the outcome by polling the token endpoint.
+    ///
+    /// See [RFC8628 Section 3.3](https://datatracker.ietf.org/doc/html/rfc8628#section-3.3)
+    Device { approved: bool },
 }
 
 #[derive(Debug)]
@@ -47,6 +35,71 @@ pub struct CreateAuthorizeTokenDto {
     pub state: String,
     pub code_challenge: String,
     pub code_challenge_method: String,
+    pub nonce: Option<String>,
+    /// See [OAuth 2.0 Multiple Response Type Encoding Practices Section 2.1](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html#ResponseModes)
+    pub response_mode: Option<String>,
+    /// See [RFC8707 Section 2.1](https://datatracker.ietf.org/doc/html/rfc8707#section-2.1)
+    pub resource: Option<String>,
+    /// JSON encoded array of authorization details objects.
+    ///
+    /// See [RFC9396 Section 2](https://datatracker.ietf.org/doc/html/rfc9396#section-2)
+    pub authorization_details: Option<String>,
+}
+
+/// Parameters given alongside a request object.
+///
+/// All of them are optional, as the request object may carry them instead.
+#[derive(Debug, Default)]
+pub struct AuthorizationParamsDto {
+    pub response_type: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<Vec<String>>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
+    pub response_mode: Option<String>,
+    pub resource: Option<String>,
+    pub authorization_details: Option<String>,
+}
+
+/// Authorization request made with a request object, either by value or by reference.
+///
+/// See [RFC9101 Section 5](https://datatracker.ietf.org/doc/html/rfc9101#section-5)
+#[derive(Debug)]
+pub struct SignedAuthorizeTokenDto {
+    pub client_id: Uuid,
+    pub request: Option<String>,
+    pub request_uri: Option<String>,
+    pub params: AuthorizationParamsDto,
+}
+
+/// Authorization request waiting for the user's decision,
+/// with what the user is asked to consent to.
+#[derive(Debug)]
+pub struct PendingAuthorizationDto {
+    pub ticket: String,
+    pub client_id: Uuid,
+    pub scope: Vec<String>,
+    /// JSON encoded `authorization_details` of the request.
+    ///
+    /// See [RFC9396 Section 2](https://datatracker.ietf.org/doc/html/rfc9396#section-2)
+    pub authorization_details: Option<String>,
+}
+
+impl PendingAuthorizationDto {
+    pub fn from_with(ticket: TicketId, token: &AuthorizeToken) -> Self {
+        let ctx = token.context();
+        Self {
+            ticket: ticket.as_ref().to_owned(),
+            client_id: *ctx.client_id().id(),
+            scope: ctx.scopes().iter().map(|s| s.as_ref().to_owned()).collect(),
+            authorization_details: ctx
+                .authorization_details()
+                .as_ref()
+                .map(|details| details.to_json()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,68 @@
+use kernel::external::{OffsetDateTime, Uuid};
+use kernel::prelude::entities::DeviceAuthorization;
+
+/// Parameters of the Device Authorization Request.
+///
+/// Defined in [RFC8628 Section 3.1](https://datatracker.ietf.org/doc/html/rfc8628#section-3.1)
+#[derive(Debug)]
+pub struct DeviceAuthorizationRequestDto {
+    pub client_id: Uuid,
+    pub scope: Option<Vec<String>>,
+    pub verification_uri: String,
+}
+
+/// Defined in [RFC8628 Section 3.2](https://datatracker.ietf.org/doc/html/rfc8628#section-3.2)
+#[derive(Debug)]
+pub struct DeviceAuthorizationDto {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: i64,
+    pub interval: i64,
+}
+
+impl From<DeviceAuthorization> for DeviceAuthorizationDto {
+    fn from(origin: DeviceAuthorization) -> Self {
+        let user_code = origin.user_code().formatted();
+        let verification_uri = origin.verification_uri().as_ref().to_owned();
+        Self {
+            device_code: origin.device_code().as_ref().to_owned(),
+            verification_uri_complete: format!("{}?user_code={}", verification_uri, user_code),
+            verification_uri,
+            user_code,
+            expires_in: (*origin.expired_in().as_ref() - OffsetDateTime::now_utc()).whole_seconds(),
+            interval: origin.interval(),
+        }
+    }
+}
+
+/// The user code entered by a user signed in with `session`.
+#[derive(Debug)]
+pub struct VerifyUserCodeDto {
+    pub user_code: String,
+    pub session: Option<String>,
+}
+
+/// Decision the user is asked for after entering the user code,
+/// to be accepted or rejected like the one of the authorization endpoint.
+#[derive(Debug)]
+pub struct DeviceDecisionDto {
+    pub ticket: String,
+    pub state: String,
+    pub client_id: Uuid,
+    pub scope: Vec<String>,
+}
+
+/// Parameters of the Device Access Token Request.
+///
+/// Defined in [RFC8628 Section 3.4](https://datatracker.ietf.org/doc/html/rfc8628#section-3.4)
+#[derive(Debug)]
+pub struct DeviceAccessTokenDto {
+    pub device_code: String,
+    pub client_id: Uuid,
+    /// Thumbprint of the DPoP key to bind the access token to.
+    pub dpop_jkt: Option<String>,
+    /// Thumbprint of the mutual-TLS client certificate to bind the access token to.
+    pub x5t_s256: Option<String>,
+}
@@ -0,0 +1,13 @@
+/// DPoP proof presented with a request, and what it has to be bound to.
+///
+/// See [RFC9449 Section 4.3](https://datatracker.ietf.org/doc/html/rfc9449#section-4.3)
+#[derive(Debug)]
+pub struct DPoPProofDto {
+    pub proof: String,
+    /// Method of the request.
+    pub htm: String,
+    /// URI of the request.
+    pub htu: String,
+    /// Access token presented with the proof to a resource.
+    pub access_token: Option<String>,
+}
@@ -0,0 +1,122 @@
+use kernel::external::Uuid;
+use kernel::prelude::entities::{AccessToken, Confirmation, Issuer, RefreshToken};
+
+/// Parameters of the Introspection Request.
+///
+/// Defined in [RFC7662 Section 2.1](https://datatracker.ietf.org/doc/html/rfc7662#section-2.1)
+#[derive(Debug)]
+pub struct IntrospectTokenDto {
+    /// The client calling the endpoint, which is already authenticated.
+    pub client_id: Uuid,
+    pub token: String,
+    pub token_type_hint: Option<String>,
+}
+
+/// Introspection Response.
+///
+/// Only `active` is set for a token that is not active.
+///
+/// Defined in [RFC7662 Section 2.2](https://datatracker.ietf.org/doc/html/rfc7662#section-2.2)
+#[derive(Debug, Default)]
+pub struct TokenIntrospectionDto {
+    pub active: bool,
+    pub scope: Vec<String>,
+    pub client_id: Option<Uuid>,
+    pub token_type: Option<String>,
+    pub exp: Option<i64>,
+    pub iat: Option<i64>,
+    pub nbf: Option<i64>,
+    pub sub: Option<String>,
+    pub aud: Option<String>,
+    pub iss: Option<String>,
+    /// `jkt` of `cnf`, for a token bound to a DPoP key.
+    ///
+    /// See [RFC9449 Section 6.2](https://datatracker.ietf.org/doc/html/rfc9449#section-6.2)
+    pub jkt: Option<String>,
+    /// `x5t#S256` of `cnf`, for a token bound to a client certificate.
+    ///
+    /// See [RFC8705 Section 3.2](https://datatracker.ietf.org/doc/html/rfc8705#section-3.2)
+    pub x5t_s256: Option<String>,
+    /// Subjects of the `act` delegation chain, the current actor first.
+    ///
+    /// See [RFC8693 Section 4.1](https://datatracker.ietf.org/doc/html/rfc8693#section-4.1)
+    pub act: Vec<String>,
+    /// JSON encoded authorization details the token is granted for.
+    ///
+    /// See [RFC9396 Section 9.2](https://datatracker.ietf.org/doc/html/rfc9396#section-9.2)
+    pub authorization_details: Option<String>,
+}
+
+impl TokenIntrospectionDto {
+    pub fn inactive() -> Self {
+        Self::default()
+    }
+}
+
+impl From<AccessToken> for TokenIntrospectionDto {
+    fn from(origin: AccessToken) -> Self {
+        let ctx = origin.context();
+        Self {
+            active: true,
+            scope: ctx.scope().iter().map(|s| s.as_ref().to_string()).collect(),
+            client_id: Some(*ctx.client_id().id()),
+            token_type: Some(
+                ctx.confirmation()
+                    .as_ref()
+                    .map_or("Bearer", Confirmation::token_type)
+                    .to_string(),
+            ),
+            exp: Some(ctx.expired_in().as_ref_i64()),
+            iat: Some(ctx.issued_at().as_ref().unix_timestamp()),
+            nbf: Some(ctx.not_before().as_ref().unix_timestamp()),
+            sub: Some(ctx.subject().as_ref().to_string()),
+            aud: Some(ctx.audience().as_ref().to_string()),
+            iss: Some(ctx.issuer().as_ref().to_string()),
+            jkt: ctx
+                .confirmation()
+                .as_ref()
+                .and_then(Confirmation::jkt)
+                .map(ToOwned::to_owned),
+            x5t_s256: ctx
+                .confirmation()
+                .as_ref()
+                .and_then(Confirmation::x5t_s256)
+                .map(ToOwned::to_owned),
+            act: std::iter::successors(ctx.actor().as_ref(), |actor| actor.prior())
+                .map(|actor| actor.subject().to_string())
+                .collect(),
+            authorization_details: ctx
+                .authorization_details()
+                .as_ref()
+                .map(|details| details.to_json()),
+        }
+    }
+}
+
+impl From<RefreshToken> for TokenIntrospectionDto {
+    fn from(origin: RefreshToken) -> Self {
+        Self {
+            active: true,
+            scope: origin
+                .scope()
+                .iter()
+                .map(|s| s.as_ref().to_string())
+                .collect(),
+            client_id: Some(*origin.client_id().id()),
+            token_type: Some("refresh_token".to_string()),
+            exp: Some(origin.expired_in().as_ref_i64()),
+            iat: Some(origin.date().created_at().as_ref().unix_timestamp()),
+            nbf: None,
+            sub: Some(origin.account().to_string()),
+            aud: Some(origin.client_id().id().to_string()),
+            iss: Some(Issuer::default().into()),
+            jkt: None,
+            x5t_s256: None,
+            act: Vec::new(),
+            authorization_details: origin
+                .authorization_details()
+                .as_ref()
+                .map(|details| details.to_json()),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use kernel::external::Uuid;
+use kernel::prelude::entities::PushedAuthorizationRequest;
+
+/// Defined in [RFC9126 Section 2.2](https://datatracker.ietf.org/doc/html/rfc9126#section-2.2)
+#[derive(Debug)]
+pub struct PushedAuthorizationRequestDto {
+    pub request_uri: String,
+    pub expires_in: i64,
+}
+
+impl From<PushedAuthorizationRequest> for PushedAuthorizationRequestDto {
+    fn from(value: PushedAuthorizationRequest) -> Self {
+        Self {
+            request_uri: value.request_uri().as_ref().to_string(),
+            expires_in: PushedAuthorizationRequest::EXPIRES_IN,
+        }
+    }
+}
+
+/// Authorization request that refers to a pushed one by its `request_uri`.
+///
+/// See [RFC9126 Section 4](https://datatracker.ietf.org/doc/html/rfc9126#section-4)
+#[derive(Debug)]
+pub struct PushedAuthorizeTokenDto {
+    pub client_id: Uuid,
+    pub request_uri: String,
+}
@@ -0,0 +1,12 @@
+use kernel::external::Uuid;
+
+/// Parameters of the Revocation Request.
+///
+/// Defined in [RFC7009 Section 2.1](https://datatracker.ietf.org/doc/html/rfc7009#section-2.1)
+#[derive(Debug)]
+pub struct RevokeTokenDto {
+    /// The client calling the endpoint, which is already authenticated.
+    pub client_id: Uuid,
+    pub token: String,
+    pub token_type_hint: Option<String>,
+}
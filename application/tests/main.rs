@@ -1 +1,4 @@
+mod test_account_serv;
 mod test_client_serv;
+mod test_signing_key_serv;
+mod test_token_serv;
@@ -0,0 +1,139 @@
+use application::services::{GetJwkSetService, RotateSigningKeyService};
+use kernel::external::OffsetDateTime;
+use kernel::interfaces::repository::{DependOnSigningKeyRepository, MockSigningKeyRepository};
+use kernel::prelude::entities::{KeyState, SigningAlgorithm, SigningKey};
+use mockall::predicate::always;
+
+struct TestHandler {
+    keys: MockSigningKeyRepository,
+}
+
+impl DependOnSigningKeyRepository for TestHandler {
+    type SigningKeyRepository = MockSigningKeyRepository;
+    fn signing_key_repository(&self) -> &Self::SigningKeyRepository {
+        &self.keys
+    }
+}
+
+#[tokio::test]
+async fn test_rotate_initial() -> anyhow::Result<()> {
+    let mut keys = MockSigningKeyRepository::new();
+    keys.expect_find_all().returning(|| Ok(vec![]));
+    keys.expect_rotate()
+        .with(always(), always(), always())
+        .times(1)
+        .returning(|active, next, keys| {
+            assert!(active.is_none() && next.is_none());
+            let states = keys.iter().map(SigningKey::state).collect::<Vec<_>>();
+            assert_eq!(states, vec![&KeyState::Active, &KeyState::Next]);
+            Ok(true)
+        });
+
+    TestHandler { keys }.rotate().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_due() -> anyhow::Result<()> {
+    let now = OffsetDateTime::now_utc();
+    let active =
+        SigningKey::generate(SigningAlgorithm::EdDSA)?.activate(now - SigningKey::ROTATION_PERIOD);
+    let next = SigningKey::generate(SigningAlgorithm::EdDSA)?;
+    let expired =
+        SigningKey::generate(SigningAlgorithm::EdDSA)?.retire(now - SigningKey::RETENTION_PERIOD);
+
+    let active_kid = active.kid().clone();
+    let next_kid = next.kid().clone();
+    let expired_kid = expired.kid().clone();
+
+    let mut keys = MockSigningKeyRepository::new();
+    keys.expect_find_all()
+        .returning(move || Ok(vec![active.clone(), next.clone(), expired.clone()]));
+    keys.expect_delete()
+        .withf(move |kid| kid.eq(&expired_kid))
+        .times(1)
+        .returning(|_| Ok(()));
+    keys.expect_rotate()
+        .with(always(), always(), always())
+        .times(1)
+        .returning(move |active, next, keys| {
+            assert_eq!(active.as_ref(), Some(&active_kid));
+            assert_eq!(next.as_ref(), Some(&next_kid));
+            assert_eq!(keys.len(), 3);
+            assert_eq!(keys[0].state(), &KeyState::Retired);
+            assert_eq!(keys[0].kid(), &active_kid);
+            assert_eq!(keys[1].state(), &KeyState::Active);
+            assert_eq!(keys[1].kid(), &next_kid);
+            assert_eq!(keys[2].state(), &KeyState::Next);
+            Ok(true)
+        });
+
+    TestHandler { keys }.rotate().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_not_due() -> anyhow::Result<()> {
+    let active = SigningKey::generate(SigningAlgorithm::EdDSA)?.activate(OffsetDateTime::now_utc());
+
+    let mut keys = MockSigningKeyRepository::new();
+    keys.expect_find_all()
+        .returning(move || Ok(vec![active.clone()]));
+    keys.expect_rotate()
+        .with(always(), always(), always())
+        .times(1)
+        .returning(|active, next, keys| {
+            assert!(active.is_some() && next.is_none());
+            assert_eq!(keys.len(), 1);
+            assert_eq!(keys[0].state(), &KeyState::Next);
+            Ok(true)
+        });
+
+    TestHandler { keys }.rotate().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_by_another_instance() -> anyhow::Result<()> {
+    let mut keys = MockSigningKeyRepository::new();
+    keys.expect_find_all().returning(|| Ok(vec![]));
+    keys.expect_rotate()
+        .with(always(), always(), always())
+        .times(1)
+        .returning(|_, _, _| Ok(false));
+
+    TestHandler { keys }.rotate().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_jwks() -> anyhow::Result<()> {
+    let now = OffsetDateTime::now_utc();
+    let active = SigningKey::generate(SigningAlgorithm::ES256)?.activate(now);
+    let next = SigningKey::generate(SigningAlgorithm::ES256)?;
+    let retired = SigningKey::generate(SigningAlgorithm::ES256)?.retire(now);
+
+    let published = [
+        active.kid().clone(),
+        next.kid().clone(),
+        retired.kid().clone(),
+    ];
+
+    let mut keys = MockSigningKeyRepository::new();
+    keys.expect_find_all()
+        .returning(move || Ok(vec![active.clone(), next.clone(), retired.clone()]));
+
+    let jwks = TestHandler { keys }.jwks().await?;
+
+    assert_eq!(jwks.keys.len(), 3);
+    for (key, kid) in jwks.keys.iter().zip(published.iter()) {
+        assert!(key.contains(kid.as_ref()));
+        assert!(!key.contains("\"d\""));
+    }
+
+    Ok(())
+}
@@ -1,11 +1,16 @@
 mod a_gen;
 mod a_load;
 mod admin;
+mod issuer;
 mod model;
+mod resource;
 mod stellar;
 
 use self::{a_gen::*, a_load::*, admin::*, model::*, stellar::*};
 
+pub use self::issuer::TrustedIssuerConfig;
+pub use self::resource::ProtectedResourceConfig;
+
 use crate::database::{AccountDataBase, ClientDataBase};
 use crate::DriverError;
 use kernel::interfaces::repository::{AccountRepository, ClientRegistry};
@@ -16,6 +21,8 @@ pub(in crate::config) mod constants {
     pub const CONFIG: &str = "stellar.toml";
     pub const GENNED: &str = "stellar.g.bin";
     pub const CACHED: &str = "stellar.c.bin";
+    pub const TRUSTED_ISSUERS: &str = "trusted_issuers.toml";
+    pub const PROTECTED_RESOURCES: &str = "protected_resources.toml";
 }
 
 static BASE: Lazy<String> =
@@ -0,0 +1,215 @@
+use super::constants::TRUSTED_ISSUERS;
+use super::BASE;
+use crate::DriverError;
+use kernel::external::Uuid;
+use kernel::interfaces::repository::TrustedIssuerRepository;
+use kernel::prelude::entities::{ClientCertificate, ClientJwkSet, TrustedIssuer};
+use kernel::KernelError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Debug, Default, Deserialize)]
+struct TrustedIssuers {
+    #[serde(default)]
+    issuers: Vec<TrustedIssuerEntry>,
+}
+
+/// One `[[issuers]]` table of the config.
+///
+/// ```toml
+/// [[issuers]]
+/// issuer = "https://idp.internal.example.com"
+/// jwks_uri = "https://idp.internal.example.com/.well-known/jwks.json"
+/// clients = ["7c9e6679-7425-40de-944b-e07fc1f90ae7"]
+/// subjects = ["batch-service"]
+/// audiences = ["https://stellar.example.com/token"]
+///
+/// [[issuers]]
+/// issuer = "https://saml.internal.example.com"
+/// clients = ["7c9e6679-7425-40de-944b-e07fc1f90ae7"]
+/// subjects = ["alice@example.com"]
+/// certificate = """
+/// -----BEGIN CERTIFICATE-----
+/// ...
+/// -----END CERTIFICATE-----
+/// """
+/// ```
+#[derive(Debug, Deserialize)]
+struct TrustedIssuerEntry {
+    issuer: String,
+    /// JWK Set document, used instead of `jwks_uri`.
+    jwks: Option<String>,
+    jwks_uri: Option<String>,
+    /// PEM encoded certificate of a SAML identity provider.
+    certificate: Option<String>,
+    /// Clients allowed to present the assertions of the issuer.
+    #[serde(default)]
+    clients: Vec<Uuid>,
+    /// Subjects the issuer may assert, `"*"` for any.
+    #[serde(default)]
+    subjects: Vec<String>,
+    #[serde(default)]
+    audiences: Vec<String>,
+}
+
+/// Issuers trusted for the JWT and SAML bearer grants, read from `trusted_issuers.toml`
+/// in `STELLAR_CONF_DIR` at startup.
+///
+/// No issuer is trusted if the file does not exist.
+#[derive(Clone, Default)]
+pub struct TrustedIssuerConfig {
+    issuers: Arc<HashMap<String, TrustedIssuer>>,
+}
+
+impl TrustedIssuerConfig {
+    pub fn load() -> Result<Self, DriverError> {
+        Self::load_from(Path::new(&*BASE).join(TRUSTED_ISSUERS))
+    }
+
+    fn load_from(path: impl AsRef<Path>) -> Result<Self, DriverError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            tracing::info!("{} does not exist. no issuer is trusted.", path.display());
+            return Ok(Self::default());
+        }
+
+        let config = toml::from_str::<TrustedIssuers>(&std::fs::read_to_string(path)?)?;
+        Self::from_config(config)
+    }
+
+    fn from_config(config: TrustedIssuers) -> Result<Self, DriverError> {
+        let issuers = config
+            .issuers
+            .into_iter()
+            .map(|entry| {
+                let jwks = entry
+                    .jwks
+                    .as_deref()
+                    .map(ClientJwkSet::from_document)
+                    .transpose()?;
+                let certificate = entry
+                    .certificate
+                    .as_deref()
+                    .map(ClientCertificate::from_pem)
+                    .transpose()?;
+                let issuer = TrustedIssuer::new(
+                    entry.issuer,
+                    jwks,
+                    entry.jwks_uri,
+                    certificate,
+                    entry.clients,
+                    entry.subjects,
+                    entry.audiences,
+                )?;
+                Ok((issuer.issuer().to_string(), issuer))
+            })
+            .collect::<Result<HashMap<_, _>, KernelError>>()?;
+
+        Ok(Self {
+            issuers: Arc::new(issuers),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TrustedIssuerRepository for TrustedIssuerConfig {
+    async fn find_by_issuer(&self, issuer: &str) -> Result<Option<TrustedIssuer>, KernelError> {
+        Ok(self.issuers.get(issuer).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TrustedIssuerConfig, TrustedIssuers};
+    use kernel::external::Uuid;
+    use kernel::interfaces::repository::TrustedIssuerRepository;
+
+    const CONFIG: &str = r#"
+[[issuers]]
+issuer = "https://idp.internal.example.com"
+jwks_uri = "https://idp.internal.example.com/.well-known/jwks.json"
+clients = ["7c9e6679-7425-40de-944b-e07fc1f90ae7"]
+subjects = ["batch-service"]
+
+[[issuers]]
+issuer = "https://keys.internal.example.com"
+subjects = ["*"]
+jwks = '{"kty":"OKP","crv":"Ed25519","x":"Oonq02ndwT_9kGpNP7QPQFKRHwXdbGwmOqq5cCLB9SQ"}'
+
+[[issuers]]
+issuer = "https://saml.internal.example.com"
+certificate = """
+-----BEGIN CERTIFICATE-----
+MIIDZTCCAk2gAwIBAgIUau5pp0g99tJiZ6epB5uiamlRABMwDQYJKoZIhvcNAQEL
+BQAwQTELMAkGA1UEBhMCSlAxEDAOBgNVBAoMB1N0ZWxsYXIxIDAeBgNVBAMMF2lk
+cC5zdGVsbGFyLmV4YW1wbGUuY29tMCAXDTI2MTAxODEwMTM0M1oYDzIxMjYwOTI0
+MTAxMzQzWjBBMQswCQYDVQQGEwJKUDEQMA4GA1UECgwHU3RlbGxhcjEgMB4GA1UE
+AwwXaWRwLnN0ZWxsYXIuZXhhbXBsZS5jb20wggEiMA0GCSqGSIb3DQEBAQUAA4IB
+DwAwggEKAoIBAQCufYFllBkjLDrJgar3vayeJQXdA6xp2+cD3W9U0QWAdMVYqoad
+sEPGK6yaTpNfzJWg0MdUvBWsSTFZmO8fPml5jLOmbPcp89cBScNwevbrXWjTbXa+
+G4ebWjKJv7PII5/PjVPPUV/SPDFSp2zyt6cRNEoPoZiOx5bolR1+7xtLf5bDTEHq
+QnYVAfkCrXhJg2+ChoFtsxCE89puTBC0nSjLCLfNa76UEdX7BAHmrVMQHaf6wdw6
+7tyDMfYSLn9V2EUhgWSrE/eK+m1y3J9meZd4Pryo8Czpe1i4drL+3KEd9Qxh8LeM
+XTR5oMtt8hJy6bKNhYkYSujed9gSjoN51OgbAgMBAAGjUzBRMB0GA1UdDgQWBBQ1
+3UcBKtInn7Lex4kI65QV80lbpDAfBgNVHSMEGDAWgBQ13UcBKtInn7Lex4kI65QV
+80lbpDAPBgNVHRMBAf8EBTADAQH/MA0GCSqGSIb3DQEBCwUAA4IBAQBvo7CQVZEi
+X4RJDft49fXGxIRv8wqJBHYwZPJxKVsTFULNNPx7i0yAjvKjYL/zdh8CLzM8Gaia
+dr0XGoHFmxk4xEUR3UO4dQeDfFJy4nBFV2JYKI/9MT+gg5QVybDN+8MaX1I3SGEN
+SJ7ZgU4dJ6/QcPaOb/IdQ44Muzk4b5IY5A8l+I67pO7nfIN501TslzGpPh/3NJR5
+/4Zikachyza90baAu7epvRXqftanLTHy+Lzj0+XYs/hJEGKj0PxLblv8OmWFpxYd
+aa4Kgs9iaCXERaMNe/UsZELHyQ3tB/RjTccwMHOWVTxNdrA9mE6sjXa6XK/gNRne
+EABgr7rDmrOO
+-----END CERTIFICATE-----
+"""
+"#;
+
+    #[tokio::test]
+    async fn find_by_issuer() -> anyhow::Result<()> {
+        let config = TrustedIssuerConfig::from_config(toml::from_str::<TrustedIssuers>(CONFIG)?)?;
+
+        let issuer = config
+            .find_by_issuer("https://idp.internal.example.com")
+            .await?
+            .unwrap();
+        assert!(issuer.allows_subject("batch-service"));
+        assert!(!issuer.allows_subject("other-service"));
+        assert!(issuer.allows_client(&Uuid::parse_str("7c9e6679-7425-40de-944b-e07fc1f90ae7")?));
+        assert!(!issuer.allows_client(&Uuid::new_v4()));
+
+        let issuer = config
+            .find_by_issuer("https://keys.internal.example.com")
+            .await?
+            .unwrap();
+        assert!(issuer.jwks().is_some());
+        assert!(issuer.allows_subject("other-service"));
+
+        let issuer = config
+            .find_by_issuer("https://saml.internal.example.com")
+            .await?
+            .unwrap();
+        assert!(issuer.certificate().is_some());
+        assert!(!issuer.allows_subject("other-service"));
+        assert!(!issuer.allows_client(&Uuid::new_v4()));
+
+        assert!(config
+            .find_by_issuer("https://other.example.com")
+            .await?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn without_keys() {
+        let config = toml::from_str::<TrustedIssuers>(
+            r#"
+[[issuers]]
+issuer = "https://idp.internal.example.com"
+"#,
+        )
+        .unwrap();
+        assert!(TrustedIssuerConfig::from_config(config).is_err());
+    }
+}
@@ -63,7 +63,7 @@ impl Default for Stellar {
             tos_uri: "https://stellar.example.com/terms".into(),
             policy_uri: "https://stellar.example.com/policy".into(),
             jwks: None,
-            jwks_uri: Some("https://stellar.example.com/.well-known".into()),
+            jwks_uri: Some("https://stellar.example.com/.well-known/jwks.json".into()),
         }
     }
 }
@@ -113,7 +113,7 @@ client_uri = "https://stellar.example.com/"
 logo_uri = "https://stellar.example.com/logo"
 tos_uri = "https://stellar.example.com/terms"
 policy_uri = "https://stellar.example.com/policy"
-jwks_uri = "https://stellar.example.com/.well-known"
+jwks_uri = "https://stellar.example.com/.well-known/jwks.json"
 "#;
 
         let config = load_config(toml)?;
@@ -0,0 +1,194 @@
+use super::constants::PROTECTED_RESOURCES;
+use super::BASE;
+use crate::DriverError;
+use kernel::interfaces::repository::ProtectedResourceRepository;
+use kernel::prelude::entities::{AuthorizationDetailsType, ProtectedResource, ScopeMethod};
+use kernel::KernelError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Debug, Default, Deserialize)]
+struct ProtectedResources {
+    #[serde(default)]
+    resources: Vec<ProtectedResourceEntry>,
+}
+
+/// One `[[resources]]` table of the config.
+///
+/// ```toml
+/// [[resources]]
+/// resource = "https://api.example.com/"
+/// scopes = ["read", "write"]
+///
+/// [[resources.authorization_details_types]]
+/// type = "payment_initiation"
+/// schema = '{"type":"object","required":["instructedAmount"]}'
+/// ```
+#[derive(Debug, Deserialize)]
+struct ProtectedResourceEntry {
+    resource: String,
+    #[serde(default)]
+    scopes: Vec<String>,
+    #[serde(default)]
+    authorization_details_types: Vec<AuthorizationDetailsTypeEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthorizationDetailsTypeEntry {
+    #[serde(rename = "type")]
+    detail_type: String,
+    /// JSON Schema document the authorization details of this type must satisfy.
+    schema: String,
+}
+
+/// Protected resources that can be named by the `resource` parameter,
+/// and the authorization details types they accept,
+/// read from `protected_resources.toml` in `STELLAR_CONF_DIR` at startup.
+///
+/// No resource can be requested if the file does not exist.
+/// A type can be registered by only one resource.
+#[derive(Clone, Default)]
+pub struct ProtectedResourceConfig {
+    resources: Arc<HashMap<String, ProtectedResource>>,
+    types: Arc<HashMap<String, AuthorizationDetailsType>>,
+}
+
+impl ProtectedResourceConfig {
+    pub fn load() -> Result<Self, DriverError> {
+        Self::load_from(Path::new(&*BASE).join(PROTECTED_RESOURCES))
+    }
+
+    fn load_from(path: impl AsRef<Path>) -> Result<Self, DriverError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            tracing::info!(
+                "{} does not exist. no resource can be requested.",
+                path.display()
+            );
+            return Ok(Self::default());
+        }
+
+        let config = toml::from_str::<ProtectedResources>(&std::fs::read_to_string(path)?)?;
+        Self::from_config(config)
+    }
+
+    fn from_config(config: ProtectedResources) -> Result<Self, DriverError> {
+        let mut resources = HashMap::new();
+        let mut types = HashMap::new();
+        for entry in config.resources {
+            let scopes = entry
+                .scopes
+                .into_iter()
+                .map(ScopeMethod::new)
+                .collect::<Vec<ScopeMethod>>();
+            let detail_types = entry
+                .authorization_details_types
+                .into_iter()
+                .map(|ty| {
+                    let schema = serde_json::from_str(&ty.schema)?;
+                    Ok(AuthorizationDetailsType::new(ty.detail_type, &schema)?)
+                })
+                .collect::<Result<Vec<_>, DriverError>>()?;
+            let resource = ProtectedResource::new(entry.resource, scopes, detail_types)?;
+
+            for ty in resource.authorization_details_types() {
+                if types
+                    .insert(ty.detail_type().to_string(), ty.clone())
+                    .is_some()
+                {
+                    return Err(KernelError::InvalidValue {
+                        method: "protected resource",
+                        value: format!("`{}` is registered more than once.", ty.detail_type()),
+                    }
+                    .into());
+                }
+            }
+            resources.insert(resource.resource().to_string(), resource);
+        }
+
+        Ok(Self {
+            resources: Arc::new(resources),
+            types: Arc::new(types),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ProtectedResourceRepository for ProtectedResourceConfig {
+    async fn find_by_resource(
+        &self,
+        resource: &str,
+    ) -> Result<Option<ProtectedResource>, KernelError> {
+        Ok(self.resources.get(resource).cloned())
+    }
+
+    async fn find_authorization_details_type(
+        &self,
+        detail_type: &str,
+    ) -> Result<Option<AuthorizationDetailsType>, KernelError> {
+        Ok(self.types.get(detail_type).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProtectedResourceConfig, ProtectedResources};
+    use kernel::interfaces::repository::ProtectedResourceRepository;
+    use kernel::prelude::entities::ScopeMethod;
+
+    const CONFIG: &str = r#"
+[[resources]]
+resource = "https://api.example.com/"
+scopes = ["read", "write"]
+
+[[resources]]
+resource = "https://billing.example.com/"
+scopes = ["billing"]
+
+[[resources.authorization_details_types]]
+type = "payment_initiation"
+schema = '{"type":"object","required":["instructedAmount"]}'
+"#;
+
+    #[tokio::test]
+    async fn find_by_resource() -> anyhow::Result<()> {
+        let config =
+            ProtectedResourceConfig::from_config(toml::from_str::<ProtectedResources>(CONFIG)?)?;
+
+        let resource = config
+            .find_by_resource("https://api.example.com/")
+            .await?
+            .unwrap();
+        assert_eq!(
+            resource.scopes(),
+            &[ScopeMethod::new("read"), ScopeMethod::new("write")]
+        );
+
+        assert!(config
+            .find_by_resource("https://other.example.com/")
+            .await?
+            .is_none());
+
+        let ty = config
+            .find_authorization_details_type("payment_initiation")
+            .await?
+            .unwrap();
+        assert_eq!(ty.detail_type(), "payment_initiation");
+
+        Ok(())
+    }
+
+    #[test]
+    fn relative_resource() {
+        let config = toml::from_str::<ProtectedResources>(
+            r#"
+[[resources]]
+resource = "/api"
+"#,
+        )
+        .unwrap();
+        assert!(ProtectedResourceConfig::from_config(config).is_err());
+    }
+}
@@ -69,7 +69,7 @@ impl Default for StellarClient {
                 .collect::<Vec<_>>(),
             ),
             contacts: Contacts::new(vec![Address::new("admin.example@stellar.example.com")]),
-            jwk: Jwks::new("https://stellar.example.com/.well-known").unwrap(),
+            jwk: Jwks::new("https://stellar.example.com/.well-known/jwks.json").unwrap(),
             conf_access_token: RegistrationAccessToken::default(),
             conf_endpoint: RegistrationEndPoint::default(),
         }
@@ -1,15 +1,20 @@
 mod account;
 mod client;
+mod device;
+mod dpop;
+mod jti;
 mod mfa_code;
+mod par;
 mod pkce;
 mod session;
+mod signing_key;
 mod state;
 mod ticket;
 mod tokens;
 
 pub use self::{
-    account::*, client::*, mfa_code::*, pkce::*, redis_pool::*, session::*, state::*, ticket::*,
-    tokens::*,
+    account::*, client::*, device::*, dpop::*, jti::*, mfa_code::*, par::*, pkce::*, redis_pool::*,
+    session::*, signing_key::*, state::*, ticket::*, tokens::*,
 };
 
 pub(in crate::database) mod redis_pool {
@@ -2,8 +2,9 @@ use crate::DriverError;
 use kernel::external::{JsonWebKey, OffsetDateTime, Uuid};
 use kernel::interfaces::repository::ClientRegistry;
 use kernel::prelude::entities::{
-    Address, Client, ClientId, ClientName, ClientSecret, ClientTypes, GrantType, RedirectUri,
-    ResponseType, ScopeDescription, ScopeMethod, TokenEndPointAuthMethod,
+    AccessTokenFormat, Address, Client, ClientId, ClientName, ClientSecret, ClientTypes, GrantType,
+    RedirectUri, ResponseType, ScopeDescription, ScopeMethod, TlsClientAuthSubject,
+    TokenEndPointAuthMethod,
 };
 use kernel::prelude::services::JwkSelectionService;
 use kernel::KernelError;
@@ -100,12 +101,25 @@ struct ClientRow {
     scope: Json<HashMap<String, Option<String>>>,
     registration_token: String,
     registration_endpoint: String,
+    access_token_format: String,
+    require_pushed_authorization_requests: bool,
+    require_signed_request_object: bool,
+    request_uris: Vec<String>,
+    tls_client_auth_subject_type: Option<String>,
+    tls_client_auth_subject: Option<String>,
+    token_exchange_audiences: Vec<String>,
 }
 
 impl TryInto<Client> for ClientRow {
     type Error = DriverError;
     fn try_into(self) -> Result<Client, Self::Error> {
-        Ok(Client::new(
+        let access_token_format = AccessTokenFormat::try_from(self.access_token_format)?;
+        let tls_client_auth_subject = self
+            .tls_client_auth_subject_type
+            .zip(self.tls_client_auth_subject)
+            .map(|(ty, subject)| TlsClientAuthSubject::new(&ty, subject))
+            .transpose()?;
+        let mut client = Client::new(
             ClientId::new(self.client_id, self.client_id_iat),
             self.client_name,
             self.client_uri,
@@ -113,7 +127,7 @@ impl TryInto<Client> for ClientRow {
             ClientTypes::new(merge_opt_i2(
                 self.client_secret,
                 self.client_secret_exp,
-                |secret, exp| ClientSecret::new(secret?, exp?).into(),
+                |secret, exp| ClientSecret::new_unchecked(secret?, exp?).into(),
             )),
             self.logo_uri,
             self.tos_uri,
@@ -144,7 +158,15 @@ impl TryInto<Client> for ClientRow {
             JwkSelectionService::check(self.jwks.map(|json| json.to_string()), self.jwks_uri)?,
             self.registration_token,
             self.registration_endpoint,
-        )?)
+        )?
+        .into_destruct();
+        client.access_token_format = access_token_format;
+        client.require_pushed_authorization_requests = self.require_pushed_authorization_requests;
+        client.require_signed_request_object = self.require_signed_request_object;
+        client.request_uris = self.request_uris;
+        client.tls_client_auth_subject = tls_client_auth_subject;
+        client.token_exchange_audiences = self.token_exchange_audiences;
+        Ok(client.freeze())
     }
 }
 
@@ -218,14 +240,28 @@ impl PgClientInternal {
               client_secret_exp,
               auth_method,
               grant_types,
-              response_types
+              response_types,
+              access_token_format,
+              require_pushed_authorization_requests,
+              require_signed_request_object,
+              tls_client_auth_subject_type,
+              tls_client_auth_subject,
+              token_exchange_audiences,
+              request_uris
             ) VALUES (
               $1,
               $2,
               $3,
               $4::TEP_AM,
               $5::GRANT_TYPE[],
-              $6::RESPONSE_TYPE[]
+              $6::RESPONSE_TYPE[],
+              $7,
+              $8,
+              $9,
+              $10,
+              $11,
+              $12,
+              $13
             )
         "#,
         )
@@ -259,6 +295,23 @@ impl PgClientInternal {
                 .map(AsRef::as_ref)
                 .collect::<Vec<_>>(),
         )
+        .bind(client.access_token_format().as_ref())
+        .bind(client.require_pushed_authorization_requests())
+        .bind(client.require_signed_request_object())
+        .bind(
+            client
+                .tls_client_auth_subject()
+                .as_ref()
+                .map(TlsClientAuthSubject::name),
+        )
+        .bind(
+            client
+                .tls_client_auth_subject()
+                .as_ref()
+                .map(TlsClientAuthSubject::value),
+        )
+        .bind(client.token_exchange_audiences())
+        .bind(client.request_uris())
         .execute(&mut *con)
         .await?;
 
@@ -424,9 +477,16 @@ impl PgClientInternal {
                 client_secret_exp = $2,
                 auth_method = $3::TEP_AM,
                 grant_types = $4::GRANT_TYPE[],
-                response_types = $5::RESPONSE_TYPE[]
+                response_types = $5::RESPONSE_TYPE[],
+                access_token_format = $6,
+                require_pushed_authorization_requests = $7,
+                require_signed_request_object = $8,
+                tls_client_auth_subject_type = $9,
+                tls_client_auth_subject = $10,
+                token_exchange_audiences = $11,
+                request_uris = $12
             WHERE
-              client_id = $6
+              client_id = $13
         "#,
         )
         .bind(
@@ -458,6 +518,23 @@ impl PgClientInternal {
                 .map(AsRef::as_ref)
                 .collect::<Vec<_>>(),
         )
+        .bind(client.access_token_format().as_ref())
+        .bind(client.require_pushed_authorization_requests())
+        .bind(client.require_signed_request_object())
+        .bind(
+            client
+                .tls_client_auth_subject()
+                .as_ref()
+                .map(TlsClientAuthSubject::name),
+        )
+        .bind(
+            client
+                .tls_client_auth_subject()
+                .as_ref()
+                .map(TlsClientAuthSubject::value),
+        )
+        .bind(client.token_exchange_audiences())
+        .bind(client.request_uris())
         .bind(client.id().id())
         .execute(&mut *con)
         .await?;
@@ -574,6 +651,13 @@ impl PgClientInternal {
               cc.auth_method::TEXT,
               cc.grant_types::TEXT[],
               cc.response_types::TEXT[],
+              cc.access_token_format,
+              cc.require_pushed_authorization_requests,
+              cc.require_signed_request_object,
+              cc.tls_client_auth_subject_type,
+              cc.tls_client_auth_subject,
+              cc.token_exchange_audiences,
+              cc.request_uris,
               cjk.jwks,
               cju.jwks_uri,
               cru.uri as redirect_uris,
@@ -624,6 +708,13 @@ impl PgClientInternal {
               cc.auth_method::TEXT,
               cc.grant_types::TEXT[],
               cc.response_types::TEXT[],
+              cc.access_token_format,
+              cc.require_pushed_authorization_requests,
+              cc.require_signed_request_object,
+              cc.tls_client_auth_subject_type,
+              cc.tls_client_auth_subject,
+              cc.token_exchange_audiences,
+              cc.request_uris,
               cjk.jwks,
               cju.jwks_uri,
               cru.uri as redirect_uris,
@@ -652,7 +743,7 @@ impl PgClientInternal {
 }
 
 #[cfg(test)]
-mod tests {
+pub(in crate::database) mod tests {
     use crate::database::account::PgAccountInternal;
     use crate::database::client::PgClientInternal;
     use kernel::external::{OffsetDateTime, Uuid};
@@ -665,7 +756,7 @@ mod tests {
     use sqlx::{PgConnection, Pool, Postgres};
     use std::time::{Duration, Instant};
 
-    async fn test_pool() -> anyhow::Result<Pool<Postgres>> {
+    pub(in crate::database) async fn test_pool() -> anyhow::Result<Pool<Postgres>> {
         dotenvy::dotenv().ok();
 
         let url = dotenvy::var("PG_DATABASE_URL")
@@ -679,7 +770,9 @@ mod tests {
         Ok(pool)
     }
 
-    async fn create_dummy_data(con: &mut PgConnection) -> anyhow::Result<Client> {
+    pub(in crate::database) async fn create_dummy_data(
+        con: &mut PgConnection,
+    ) -> anyhow::Result<Client> {
         let client_id = ClientId::new_at_now(Uuid::new_v4());
         let client_name = "Test Client";
         let client_uri = "https://test.client.example.com/";
@@ -716,7 +809,7 @@ mod tests {
             .into_iter()
             .map(Address::new)
             .collect::<Contacts>();
-        let jwks = Jwks::new("https://stellar.example.com/.well-known")?;
+        let jwks = Jwks::new("https://stellar.example.com/.well-known/jwks.json")?;
         let regi_token = RegistrationAccessToken::default();
         let regi_endpoint = RegistrationEndPoint::default();
 
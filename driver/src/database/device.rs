@@ -0,0 +1,174 @@
+use crate::DriverError;
+use deadpool_redis::{redis, Connection as RedisConnection, Pool};
+use kernel::interfaces::repository::DeviceAuthorizationVolatileRepository;
+use kernel::prelude::entities::{DeviceAuthorization, DeviceCode, UserCode};
+use kernel::KernelError;
+
+#[derive(Clone)]
+pub struct DeviceAuthorizationVolatileDataBase {
+    pool: Pool,
+}
+
+impl DeviceAuthorizationVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeviceAuthorizationVolatileRepository for DeviceAuthorizationVolatileDataBase {
+    async fn save(&self, device: &DeviceAuthorization) -> Result<(), KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        DeviceAuthorizationRedisInternal::save(device, &mut con).await?;
+        Ok(())
+    }
+
+    async fn dele(&self, device: &DeviceAuthorization) -> Result<(), KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        DeviceAuthorizationRedisInternal::dele(device, &mut con).await?;
+        Ok(())
+    }
+
+    async fn find_by_device_code(
+        &self,
+        code: &DeviceCode,
+    ) -> Result<Option<DeviceAuthorization>, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let found = DeviceAuthorizationRedisInternal::find_by_device_code(code, &mut con).await?;
+        Ok(found)
+    }
+
+    async fn find_by_user_code(
+        &self,
+        code: &UserCode,
+    ) -> Result<Option<DeviceAuthorization>, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let found = DeviceAuthorizationRedisInternal::find_by_user_code(code, &mut con).await?;
+        Ok(found)
+    }
+}
+
+/// The request is stored under its device code,
+/// and the user code points to the device code.
+/// Both keys expire together with the request.
+pub(in crate::database) struct DeviceAuthorizationRedisInternal;
+
+impl DeviceAuthorizationRedisInternal {
+    pub async fn save(
+        device: &DeviceAuthorization,
+        con: &mut RedisConnection,
+    ) -> Result<(), DriverError> {
+        let expire_at = device.expired_in().as_ref_i64();
+        redis::pipe()
+            .atomic()
+            .cmd("SET")
+            .arg(device_namespace(device.device_code()))
+            .arg(serde_json::to_string(device)?)
+            .arg("EXAT")
+            .arg(expire_at)
+            .ignore()
+            .cmd("SET")
+            .arg(user_namespace(device.user_code()))
+            .arg(device.device_code().as_ref())
+            .arg("EXAT")
+            .arg(expire_at)
+            .ignore()
+            .query_async(&mut *con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn dele(
+        device: &DeviceAuthorization,
+        con: &mut RedisConnection,
+    ) -> Result<(), DriverError> {
+        redis::cmd("DEL")
+            .arg(device_namespace(device.device_code()))
+            .arg(user_namespace(device.user_code()))
+            .query_async(&mut *con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_device_code(
+        code: &DeviceCode,
+        con: &mut RedisConnection,
+    ) -> Result<Option<DeviceAuthorization>, DriverError> {
+        let raw: Option<String> = redis::cmd("GET")
+            .arg(device_namespace(code))
+            .query_async(&mut *con)
+            .await?;
+        let device = raw
+            .map(|raw| serde_json::from_str::<DeviceAuthorization>(&raw))
+            .transpose()?;
+        Ok(device)
+    }
+
+    pub async fn find_by_user_code(
+        code: &UserCode,
+        con: &mut RedisConnection,
+    ) -> Result<Option<DeviceAuthorization>, DriverError> {
+        let device_code: Option<String> = redis::cmd("GET")
+            .arg(user_namespace(code))
+            .query_async(&mut *con)
+            .await?;
+        match device_code {
+            Some(device_code) => {
+                Self::find_by_device_code(&DeviceCode::new(device_code), con).await
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn device_namespace(key: impl AsRef<str>) -> String {
+    format!("{}-device-code", key.as_ref())
+}
+
+fn user_namespace(key: impl AsRef<str>) -> String {
+    format!("{}-user-code", key.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeviceAuthorizationRedisInternal;
+    use deadpool_redis::{Config, Runtime};
+    use kernel::external::{Duration, Uuid};
+    use kernel::prelude::entities::{ClientId, DeviceAuthorization, ScopeMethod, UserCode};
+
+    #[ignore = "It depends on Redis and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let device = DeviceAuthorization::new(
+            ClientId::new_at_now(Uuid::new_v4()),
+            vec![ScopeMethod::new("read")],
+            "https://stellar.example.com/device",
+            Duration::new(600, 0),
+        );
+
+        let cfg = Config::from_url("redis://localhost:6379/");
+        let pool = cfg.create_pool(Some(Runtime::Tokio1))?;
+        let mut con = pool.get().await?;
+
+        DeviceAuthorizationRedisInternal::save(&device, &mut con).await?;
+
+        let found =
+            DeviceAuthorizationRedisInternal::find_by_device_code(device.device_code(), &mut con)
+                .await?;
+        assert_eq!(found.as_ref(), Some(&device));
+
+        let user_code = UserCode::new(device.user_code().formatted().to_lowercase());
+        let found =
+            DeviceAuthorizationRedisInternal::find_by_user_code(&user_code, &mut con).await?;
+        assert_eq!(found.as_ref(), Some(&device));
+
+        DeviceAuthorizationRedisInternal::dele(&device, &mut con).await?;
+
+        let found =
+            DeviceAuthorizationRedisInternal::find_by_device_code(device.device_code(), &mut con)
+                .await?;
+        assert!(found.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::database::jti::JtiRedisInternal;
+use crate::DriverError;
+use deadpool_redis::{redis, Connection as RedisConnection, Pool};
+use kernel::external::OffsetDateTime;
+use kernel::interfaces::repository::{DPoPJtiVolatileRepository, DPoPNonceVolatileRepository};
+use kernel::prelude::entities::{DPoPDecodeJwt, DPoPNonce, DPoPProof};
+use kernel::KernelError;
+
+#[derive(Clone)]
+pub struct DPoPJtiVolatileDataBase {
+    pool: Pool,
+}
+
+impl DPoPJtiVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DPoPJtiVolatileRepository for DPoPJtiVolatileDataBase {
+    async fn save(&self, proof: &DPoPDecodeJwt) -> Result<bool, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let ttl = proof.iat() + DPoPProof::MAX_AGE - OffsetDateTime::now_utc().unix_timestamp();
+        let saved = JtiRedisInternal::save(
+            &jti_namespace(proof.jkt(), proof.jti()),
+            ttl.max(1),
+            &mut con,
+        )
+        .await?;
+        Ok(saved)
+    }
+}
+
+#[derive(Clone)]
+pub struct DPoPNonceVolatileDataBase {
+    pool: Pool,
+}
+
+impl DPoPNonceVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DPoPNonceVolatileRepository for DPoPNonceVolatileDataBase {
+    async fn save(&self, nonce: &DPoPNonce) -> Result<(), KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        DPoPNonceRedisInternal::save(nonce, &mut con).await?;
+        Ok(())
+    }
+
+    async fn find(&self, nonce: &DPoPNonce) -> Result<Option<DPoPNonce>, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let found = DPoPNonceRedisInternal::find(nonce, &mut con).await?;
+        Ok(found)
+    }
+}
+
+pub(in crate::database) struct DPoPNonceRedisInternal;
+
+impl DPoPNonceRedisInternal {
+    pub async fn save(nonce: &DPoPNonce, con: &mut RedisConnection) -> Result<(), DriverError> {
+        redis::cmd("SET")
+            .arg(nonce_namespace(nonce))
+            .arg(1)
+            .arg("EX")
+            .arg(DPoPNonce::EXPIRES_IN)
+            .query_async(&mut *con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find(
+        nonce: &DPoPNonce,
+        con: &mut RedisConnection,
+    ) -> Result<Option<DPoPNonce>, DriverError> {
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(nonce_namespace(nonce))
+            .query_async(&mut *con)
+            .await?;
+        Ok(exists.then(|| nonce.clone()))
+    }
+}
+
+fn jti_namespace(jkt: impl AsRef<str>, jti: impl AsRef<str>) -> String {
+    format!("{}-{}-dpop-jti", jkt.as_ref(), jti.as_ref())
+}
+
+fn nonce_namespace(nonce: &DPoPNonce) -> String {
+    format!("{}-dpop-nonce", nonce.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::dpop::DPoPNonceRedisInternal;
+    use deadpool_redis::{Config, Runtime};
+    use kernel::prelude::entities::DPoPNonce;
+
+    #[ignore = "It depends on Redis and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let cfg = Config::from_url("redis://localhost:6379/");
+        let pool = cfg.create_pool(Some(Runtime::Tokio1))?;
+
+        let mut con = pool.get().await?;
+
+        let nonce = DPoPNonce::default();
+        assert!(DPoPNonceRedisInternal::find(&nonce, &mut con)
+            .await?
+            .is_none());
+
+        DPoPNonceRedisInternal::save(&nonce, &mut con).await?;
+        let found = DPoPNonceRedisInternal::find(&nonce, &mut con).await?;
+        assert_eq!(found, Some(nonce));
+        Ok(())
+    }
+}
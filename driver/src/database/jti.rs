@@ -0,0 +1,129 @@
+use crate::DriverError;
+use deadpool_redis::{redis, Connection as RedisConnection, Pool};
+use kernel::external::OffsetDateTime;
+use kernel::interfaces::repository::{
+    JtiVolatileRepository, JwtBearerJtiVolatileRepository, SamlAssertionIdVolatileRepository,
+};
+use kernel::prelude::entities::{ClientDecodeJwt, JwtBearerDecodeJwt, SamlDecodeAssertion};
+use kernel::KernelError;
+
+#[derive(Clone)]
+pub struct JtiVolatileDataBase {
+    pool: Pool,
+}
+
+impl JtiVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl JtiVolatileRepository for JtiVolatileDataBase {
+    async fn save(&self, assertion: &ClientDecodeJwt) -> Result<bool, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let ttl = (assertion.exp()? - OffsetDateTime::now_utc()).whole_seconds();
+        let saved = JtiRedisInternal::save(
+            &namespace(assertion.iss(), assertion.jti()),
+            ttl.max(1),
+            &mut con,
+        )
+        .await?;
+        Ok(saved)
+    }
+}
+
+#[derive(Clone)]
+pub struct JwtBearerJtiVolatileDataBase {
+    pool: Pool,
+}
+
+impl JwtBearerJtiVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl JwtBearerJtiVolatileRepository for JwtBearerJtiVolatileDataBase {
+    async fn save(&self, assertion: &JwtBearerDecodeJwt) -> Result<bool, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let ttl = (assertion.exp()? - OffsetDateTime::now_utc()).whole_seconds();
+        let saved = JtiRedisInternal::save(
+            &namespace(assertion.iss(), assertion.jti()),
+            ttl.max(1),
+            &mut con,
+        )
+        .await?;
+        Ok(saved)
+    }
+}
+
+#[derive(Clone)]
+pub struct SamlAssertionIdVolatileDataBase {
+    pool: Pool,
+}
+
+impl SamlAssertionIdVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SamlAssertionIdVolatileRepository for SamlAssertionIdVolatileDataBase {
+    async fn save(&self, assertion: &SamlDecodeAssertion) -> Result<bool, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let ttl = (assertion.not_on_or_after() - OffsetDateTime::now_utc()).whole_seconds();
+        let saved = JtiRedisInternal::save(
+            &namespace(assertion.issuer(), assertion.id()),
+            ttl.max(1),
+            &mut con,
+        )
+        .await?;
+        Ok(saved)
+    }
+}
+
+pub(in crate::database) struct JtiRedisInternal;
+
+impl JtiRedisInternal {
+    /// `SET NX` so that a `jti` is saved only once until it expires.
+    pub async fn save(key: &str, ttl: i64, con: &mut RedisConnection) -> Result<bool, DriverError> {
+        let saved: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl)
+            .query_async(&mut *con)
+            .await?;
+        Ok(saved.is_some())
+    }
+}
+
+fn namespace(iss: impl ToString, jti: impl AsRef<str>) -> String {
+    format!("{}-{}-jti", iss.to_string(), jti.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::jti::JtiRedisInternal;
+    use deadpool_redis::{Config, Runtime};
+    use kernel::external::Uuid;
+
+    #[ignore = "It depends on Redis and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let key = super::namespace(Uuid::new_v4(), "jti");
+
+        let cfg = Config::from_url("redis://localhost:6379/");
+        let pool = cfg.create_pool(Some(Runtime::Tokio1))?;
+
+        let mut con = pool.get().await?;
+
+        assert!(JtiRedisInternal::save(&key, 60, &mut con).await?);
+        assert!(!JtiRedisInternal::save(&key, 60, &mut con).await?);
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::DriverError;
+use deadpool_redis::{redis, Connection as RedisConnection, Pool};
+use kernel::interfaces::repository::PushedAuthorizationRequestVolatileRepository;
+use kernel::prelude::entities::{PushedAuthorizationRequest, RequestUri};
+use kernel::KernelError;
+
+#[derive(Clone)]
+pub struct PushedAuthorizationRequestVolatileDataBase {
+    pool: Pool,
+}
+
+impl PushedAuthorizationRequestVolatileDataBase {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PushedAuthorizationRequestVolatileRepository for PushedAuthorizationRequestVolatileDataBase {
+    async fn save(&self, request: &PushedAuthorizationRequest) -> Result<(), KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        PushedAuthorizationRequestRedisInternal::save(request, &mut con).await?;
+        Ok(())
+    }
+
+    async fn dele(&self, request_uri: &RequestUri) -> Result<(), KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        PushedAuthorizationRequestRedisInternal::dele(request_uri, &mut con).await?;
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        request_uri: &RequestUri,
+    ) -> Result<Option<PushedAuthorizationRequest>, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let found = PushedAuthorizationRequestRedisInternal::find(request_uri, &mut con).await?;
+        Ok(found)
+    }
+}
+
+pub(in crate::database) struct PushedAuthorizationRequestRedisInternal;
+
+impl PushedAuthorizationRequestRedisInternal {
+    pub async fn save(
+        request: &PushedAuthorizationRequest,
+        con: &mut RedisConnection,
+    ) -> Result<(), DriverError> {
+        redis::cmd("SET")
+            .arg(namespace(request.request_uri()))
+            .arg(serde_json::to_string(request)?)
+            .arg("EXAT")
+            .arg(request.expired_in().as_ref_i64())
+            .query_async(&mut *con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn dele(
+        request_uri: &RequestUri,
+        con: &mut RedisConnection,
+    ) -> Result<(), DriverError> {
+        redis::cmd("DEL")
+            .arg(namespace(request_uri))
+            .query_async(&mut *con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find(
+        request_uri: &RequestUri,
+        con: &mut RedisConnection,
+    ) -> Result<Option<PushedAuthorizationRequest>, DriverError> {
+        let raw: Option<String> = redis::cmd("GET")
+            .arg(namespace(request_uri))
+            .query_async(&mut *con)
+            .await?;
+        let request = raw
+            .map(|raw| serde_json::from_str::<PushedAuthorizationRequest>(&raw))
+            .transpose()?;
+        Ok(request)
+    }
+}
+
+fn namespace(key: impl AsRef<str>) -> String {
+    format!("{}-par", key.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PushedAuthorizationRequestRedisInternal;
+    use deadpool_redis::{Config, Runtime};
+    use kernel::external::{Duration, OffsetDateTime, Uuid};
+    use kernel::prelude::entities::{
+        AuthorizeToken, AuthorizeTokenId, CodeChallenge, PushedAuthorizationRequest, ResponseType,
+        ScopeMethod, State,
+    };
+
+    #[ignore = "It depends on Redis and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let token = AuthorizeToken::new(
+            AuthorizeTokenId::default(),
+            OffsetDateTime::now_utc(),
+            OffsetDateTime::now_utc(),
+            None,
+            Uuid::new_v4(),
+            vec![ScopeMethod::new("read")],
+            ResponseType::Code,
+            "https://test.client.example.com/callback",
+            None,
+            None,
+            None,
+            Duration::new(600, 0),
+        );
+        let request = PushedAuthorizationRequest::new(
+            token,
+            CodeChallenge::new("R4SUkGMHJj_GM8aS5NUGrXbtF5_npYMbiJhPZAqgk9o=")?,
+            State::new("state"),
+        );
+
+        let cfg = Config::from_url("redis://localhost:6379/");
+        let pool = cfg.create_pool(Some(Runtime::Tokio1))?;
+        let mut con = pool.get().await?;
+
+        PushedAuthorizationRequestRedisInternal::save(&request, &mut con).await?;
+
+        let found =
+            PushedAuthorizationRequestRedisInternal::find(request.request_uri(), &mut con).await?;
+        assert_eq!(found.as_ref(), Some(&request));
+
+        PushedAuthorizationRequestRedisInternal::dele(request.request_uri(), &mut con).await?;
+
+        let found =
+            PushedAuthorizationRequestRedisInternal::find(request.request_uri(), &mut con).await?;
+        assert!(found.is_none());
+
+        Ok(())
+    }
+}
@@ -42,6 +42,15 @@ impl PKCEVolatileRepository for PKCEVolatileDataBase {
         let found = PKCERedisInternal::find(token_id, &mut con).await?;
         Ok(found)
     }
+
+    async fn take(
+        &self,
+        token_id: &AuthorizeTokenId,
+    ) -> Result<Option<CodeChallenge>, KernelError> {
+        let mut con = self.pool.get().await.map_err(DriverError::from)?;
+        let taken = PKCERedisInternal::take(token_id, &mut con).await?;
+        Ok(taken)
+    }
 }
 
 pub(in crate::database) struct PKCERedisInternal;
@@ -82,6 +91,18 @@ impl PKCERedisInternal {
         let pkce = raw.map(CodeChallenge::from);
         Ok(pkce)
     }
+
+    async fn take(
+        token_id: &AuthorizeTokenId,
+        con: &mut RedisConnection,
+    ) -> Result<Option<CodeChallenge>, DriverError> {
+        let raw: Option<Vec<u8>> = redis::cmd("GETDEL")
+            .arg(token_id.as_ref())
+            .query_async(&mut *con)
+            .await?;
+        let pkce = raw.map(CodeChallenge::from);
+        Ok(pkce)
+    }
 }
 
 #[cfg(test)]
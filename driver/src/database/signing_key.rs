@@ -0,0 +1,422 @@
+use crate::DriverError;
+use kernel::external::OffsetDateTime;
+use kernel::interfaces::repository::SigningKeyRepository;
+use kernel::prelude::entities::{KeyId, KeyState, SigningAlgorithm, SigningKey};
+use kernel::KernelError;
+use once_cell::sync::Lazy;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use sqlx::{PgConnection, Pool, Postgres};
+
+#[derive(Clone)]
+pub struct SigningKeyDataBase {
+    pool: Pool<Postgres>,
+}
+
+impl SigningKeyDataBase {
+    /// Fails if `SIGNING_KEY_SECRET` is unusable,
+    /// so that it is reported at startup instead of on the first key access.
+    pub fn new(pool: Pool<Postgres>) -> Result<Self, DriverError> {
+        KeyEncryption::secret()?;
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningKeyRepository for SigningKeyDataBase {
+    async fn create(&self, create: &SigningKey) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgSigningKeyInternal::create(create, &mut con).await?;
+        Ok(())
+    }
+
+    async fn update(&self, update: &SigningKey) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgSigningKeyInternal::update(update, &mut con).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, delete: &KeyId) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgSigningKeyInternal::delete(delete, &mut con).await?;
+        Ok(())
+    }
+
+    async fn rotate(
+        &self,
+        active: Option<KeyId>,
+        next: Option<KeyId>,
+        keys: &[SigningKey],
+    ) -> Result<bool, KernelError> {
+        let mut transaction = self.pool.begin().await.map_err(DriverError::SqlX)?;
+
+        let rotated = match PgSigningKeyInternal::rotate(active, next, keys, &mut transaction).await
+        {
+            Ok(rotated) => rotated,
+            Err(r) => {
+                transaction.rollback().await.map_err(DriverError::SqlX)?;
+                return Err(KernelError::Driver(anyhow::Error::new(r)));
+            }
+        };
+
+        transaction.commit().await.map_err(DriverError::SqlX)?;
+
+        Ok(rotated)
+    }
+
+    async fn find_all(&self) -> Result<Vec<SigningKey>, KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        let found = PgSigningKeyInternal::find_all(&mut con).await?;
+        Ok(found)
+    }
+
+    async fn find_active(&self) -> Result<Option<SigningKey>, KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        let found = PgSigningKeyInternal::find_active(&mut con).await?;
+        Ok(found)
+    }
+}
+
+/// Secret that private keys are encrypted with before being stored.
+static SIGNING_KEY_SECRET: Lazy<Option<String>> =
+    Lazy::new(|| dotenvy::var("SIGNING_KEY_SECRET").ok());
+
+#[derive(sqlx::FromRow, Debug)]
+struct SigningKeyRow {
+    kid: String,
+    alg: String,
+    state: String,
+    private_key: Vec<u8>,
+    public_key: String,
+    activated_at: Option<OffsetDateTime>,
+    retired_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl TryFrom<SigningKeyRow> for SigningKey {
+    type Error = DriverError;
+    fn try_from(value: SigningKeyRow) -> Result<Self, Self::Error> {
+        let private_key = KeyEncryption::decrypt(&value.kid, &value.private_key)?;
+        Ok(SigningKey::new(
+            value.kid,
+            SigningAlgorithm::try_from(value.alg)?,
+            KeyState::try_from(value.state)?,
+            private_key,
+            value.public_key,
+            value.created_at,
+            value.updated_at,
+            value.activated_at,
+            value.retired_at,
+        )?)
+    }
+}
+
+/// AES-256-GCM with a key derived from `SIGNING_KEY_SECRET`.
+///
+/// The `kid` is authenticated as associated data,
+/// so an encrypted private key cannot be moved to another row.
+pub(in crate::database) struct KeyEncryption;
+
+impl KeyEncryption {
+    const SALT: &'static [u8] = b"stellar-signing-key";
+    /// Shortest secret accepted, as long as the derived key.
+    const MIN_SECRET_LEN: usize = 32;
+
+    fn secret() -> Result<&'static [u8], DriverError> {
+        Self::check_secret(SIGNING_KEY_SECRET.as_deref())
+    }
+
+    fn check_secret(secret: Option<&str>) -> Result<&[u8], DriverError> {
+        match secret {
+            Some(secret) if secret.len() >= Self::MIN_SECRET_LEN => Ok(secret.as_bytes()),
+            _ => Err(DriverError::Encryption(anyhow::Error::msg(format!(
+                "`SIGNING_KEY_SECRET` must be set to at least {} bytes.",
+                Self::MIN_SECRET_LEN
+            )))),
+        }
+    }
+
+    fn key() -> Result<LessSafeKey, DriverError> {
+        let prk = Salt::new(HKDF_SHA256, Self::SALT).extract(Self::secret()?);
+        let mut key = [0u8; 32];
+        prk.expand(&[b"aes-256-gcm"], &AES_256_GCM)
+            .and_then(|okm| okm.fill(&mut key))
+            .map_err(|_| DriverError::Encryption(anyhow::Error::msg("failed to derive key.")))?;
+        let key = UnboundKey::new(&AES_256_GCM, &key)
+            .map_err(|_| DriverError::Encryption(anyhow::Error::msg("invalid key length.")))?;
+        Ok(LessSafeKey::new(key))
+    }
+
+    /// Returns `nonce || ciphertext || tag`.
+    pub fn encrypt(kid: &str, plain: &[u8]) -> Result<Vec<u8>, DriverError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| DriverError::Encryption(anyhow::Error::msg("failed to make nonce.")))?;
+
+        let mut sealed = plain.to_vec();
+        Self::key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(kid.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| DriverError::Encryption(anyhow::Error::msg("failed to encrypt.")))?;
+
+        Ok([nonce.as_slice(), &sealed].concat())
+    }
+
+    pub fn decrypt(kid: &str, sealed: &[u8]) -> Result<Vec<u8>, DriverError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(DriverError::Encryption(anyhow::Error::msg(
+                "encrypted private key is too short.",
+            )));
+        }
+        let (nonce, sealed) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| DriverError::Encryption(anyhow::Error::msg("invalid nonce.")))?;
+
+        let mut opened = sealed.to_vec();
+        let plain = Self::key()?
+            .open_in_place(nonce, Aad::from(kid.as_bytes()), &mut opened)
+            .map_err(|_| DriverError::Encryption(anyhow::Error::msg("failed to decrypt.")))?;
+
+        Ok(plain.to_vec())
+    }
+}
+
+pub(in crate::database) struct PgSigningKeyInternal;
+
+impl PgSigningKeyInternal {
+    /// Advisory lock serializing the rotations of all instances, `stellar` in ASCII.
+    const ROTATION_LOCK: i64 = 0x0073_7465_6c6c_6172;
+
+    /// Must be called in a transaction, which releases the lock.
+    pub async fn rotate(
+        active: Option<KeyId>,
+        next: Option<KeyId>,
+        keys: &[SigningKey],
+        con: &mut PgConnection,
+    ) -> Result<bool, DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            SELECT pg_advisory_xact_lock($1)
+        "#,
+        )
+        .bind(Self::ROTATION_LOCK)
+        .execute(&mut *con)
+        .await?;
+
+        // language=SQL
+        let current = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT state, kid FROM signing_keys WHERE state <> 'retired'
+        "#,
+        )
+        .fetch_all(&mut *con)
+        .await?;
+
+        let kid_of = |state: &str| {
+            current
+                .iter()
+                .find(|(current, _)| current == state)
+                .map(|(_, kid)| kid.as_str())
+        };
+        if kid_of("active") != active.as_ref().map(AsRef::as_ref)
+            || kid_of("next") != next.as_ref().map(AsRef::as_ref)
+        {
+            return Ok(false);
+        }
+
+        for key in keys {
+            if current.iter().any(|(_, kid)| kid == key.kid().as_ref()) {
+                Self::update(key, con).await?;
+            } else {
+                Self::create(key, con).await?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub async fn create(create: &SigningKey, con: &mut PgConnection) -> Result<(), DriverError> {
+        let private_key =
+            KeyEncryption::encrypt(create.kid().as_ref(), create.private_key().as_ref())?;
+        // language=SQL
+        sqlx::query(
+            r#"
+            INSERT INTO signing_keys (
+                kid,
+                alg,
+                state,
+                private_key,
+                public_key,
+                activated_at,
+                retired_at,
+                created_at,
+                updated_at
+            )
+            VALUES (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8,
+                $9
+            )
+        "#,
+        )
+        .bind(create.kid().as_ref())
+        .bind(create.alg().as_ref())
+        .bind(create.state().as_ref())
+        .bind(private_key)
+        .bind(create.public_document()?)
+        .bind(create.activated_at())
+        .bind(create.retired_at())
+        .bind(create.date().created_at().as_ref())
+        .bind(create.date().updated_at().as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update(update: &SigningKey, con: &mut PgConnection) -> Result<(), DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            UPDATE signing_keys
+            SET
+                state = $1,
+                activated_at = $2,
+                retired_at = $3,
+                updated_at = $4
+            WHERE
+                kid = $5
+        "#,
+        )
+        .bind(update.state().as_ref())
+        .bind(update.activated_at())
+        .bind(update.retired_at())
+        .bind(update.date().updated_at().as_ref())
+        .bind(update.kid().as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(delete: &KeyId, con: &mut PgConnection) -> Result<(), DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            DELETE FROM signing_keys WHERE kid = $1
+        "#,
+        )
+        .bind(delete.as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_all(con: &mut PgConnection) -> Result<Vec<SigningKey>, DriverError> {
+        // language=SQL
+        let found = sqlx::query_as::<_, SigningKeyRow>(
+            r#"
+            SELECT * FROM signing_keys ORDER BY created_at
+        "#,
+        )
+        .fetch_all(&mut *con)
+        .await?
+        .into_iter()
+        .map(SigningKey::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(found)
+    }
+
+    pub async fn find_active(con: &mut PgConnection) -> Result<Option<SigningKey>, DriverError> {
+        // language=SQL
+        let found = sqlx::query_as::<_, SigningKeyRow>(
+            r#"
+            SELECT * FROM signing_keys WHERE state = 'active'
+        "#,
+        )
+        .fetch_optional(&mut *con)
+        .await?
+        .map(SigningKey::try_from)
+        .transpose()?;
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyEncryption, PgSigningKeyInternal};
+    use crate::database::client::tests::test_pool;
+    use kernel::external::OffsetDateTime;
+    use kernel::prelude::entities::{KeyState, SigningAlgorithm, SigningKey};
+
+    #[test]
+    fn encryption() -> anyhow::Result<()> {
+        std::env::set_var(
+            "SIGNING_KEY_SECRET",
+            "test-signing-key-secret-of-at-least-32-bytes",
+        );
+
+        let sealed = KeyEncryption::encrypt("kid", b"private key")?;
+        assert_ne!(&sealed[..], b"private key");
+
+        let opened = KeyEncryption::decrypt("kid", &sealed)?;
+        assert_eq!(&opened[..], b"private key");
+
+        assert!(KeyEncryption::decrypt("other", &sealed).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn short_secret() {
+        assert!(KeyEncryption::check_secret(None).is_err());
+        assert!(KeyEncryption::check_secret(Some("")).is_err());
+        assert!(KeyEncryption::check_secret(Some("test-signing-key-secret")).is_err());
+        assert!(KeyEncryption::check_secret(Some(&"s".repeat(32))).is_ok());
+    }
+
+    #[ignore = "It depends on Postgres and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let pool = test_pool().await?;
+
+        let mut transaction = pool.begin().await?;
+
+        let key = SigningKey::generate(SigningAlgorithm::ES256)?;
+        PgSigningKeyInternal::create(&key, &mut transaction).await?;
+
+        let key = key.activate(OffsetDateTime::now_utc());
+        PgSigningKeyInternal::update(&key, &mut transaction).await?;
+
+        let found = PgSigningKeyInternal::find_all(&mut transaction).await?;
+        let found = found.iter().find(|found| found.kid() == key.kid());
+        println!("{:?}", found);
+        assert!(found
+            .is_some_and(|found| found.state() == &KeyState::Active
+                && found.private_key() == key.private_key()));
+
+        let active = PgSigningKeyInternal::find_active(&mut transaction).await?;
+        assert!(active.is_some_and(|active| active.kid() == key.kid()));
+
+        PgSigningKeyInternal::delete(key.kid(), &mut transaction).await?;
+
+        transaction.rollback().await?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+mod access;
 mod authorize;
 mod pending;
+mod refresh;
 
-pub use self::{authorize::*, pending::*};
+pub use self::{access::*, authorize::*, pending::*, refresh::*};
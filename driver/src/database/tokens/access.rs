@@ -0,0 +1,293 @@
+use crate::DriverError;
+use kernel::external::{OffsetDateTime, Uuid};
+use kernel::interfaces::repository::AccessTokenRepository;
+use kernel::prelude::entities::{
+    AccessToken, AccessTokenId, Actor, Audience, AuthorizationDetails, ClientId, Confirmation,
+    DestructAccessToken, DestructAccessTokenContext, ExpiredIn, IssuedAt, Issuer, LoggedAt,
+    NotBefore, ScopeMethod, Subject, TokenDigest, UserId,
+};
+use kernel::KernelError;
+use sqlx::types::Json;
+use sqlx::{PgConnection, Pool, Postgres};
+
+#[derive(Clone)]
+pub struct AccessTokenDataBase {
+    pool: Pool<Postgres>,
+}
+
+impl AccessTokenDataBase {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AccessTokenRepository for AccessTokenDataBase {
+    async fn create(&self, create: &AccessToken) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgAccessTokenInternal::create(create, &mut con).await?;
+        Ok(())
+    }
+
+    async fn update(&self, update: &AccessToken) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgAccessTokenInternal::update(update, &mut con).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, delete: &AccessTokenId) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgAccessTokenInternal::delete(delete, &mut con).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &AccessTokenId) -> Result<Option<AccessToken>, KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        let found = PgAccessTokenInternal::find_by_id(id, &mut con).await?;
+        Ok(found)
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct AccessTokenRow {
+    client_id: Uuid,
+    client_id_iat: OffsetDateTime,
+    account: Option<Uuid>,
+    scope: Vec<String>,
+    aud: String,
+    iss: String,
+    sub: String,
+    exp: OffsetDateTime,
+    iat: OffsetDateTime,
+    nbf: OffsetDateTime,
+    dpop_jkt: Option<String>,
+    x5t_s256: Option<String>,
+    act: Option<Json<Actor>>,
+    authorization_details: Option<Json<AuthorizationDetails>>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl AccessTokenRow {
+    /// Only the digest is stored, so the id has to be supplied by the caller who looked it up.
+    fn into_access_token(self, id: AccessTokenId) -> AccessToken {
+        let value = self;
+        let ctx = DestructAccessTokenContext {
+            scope: value.scope.into_iter().map(ScopeMethod::new).collect(),
+            client_id: ClientId::new(value.client_id, value.client_id_iat),
+            account: value.account.map(UserId::new),
+            aud: Audience::new(value.aud),
+            exp: ExpiredIn::from(value.exp),
+            iat: IssuedAt::from(value.iat),
+            iss: Issuer::new(value.iss),
+            nbf: NotBefore::from(value.nbf),
+            sub: Subject::new(value.sub),
+            cnf: Confirmation::from_parts(value.dpop_jkt, value.x5t_s256),
+            act: value.act.map(|act| act.0),
+            authorization_details: value.authorization_details.map(|details| details.0),
+        };
+
+        DestructAccessToken {
+            id,
+            date: LoggedAt::new(value.created_at, value.updated_at),
+            ctx: ctx.freeze(),
+        }
+        .freeze()
+    }
+}
+
+pub(in crate::database) struct PgAccessTokenInternal;
+
+impl PgAccessTokenInternal {
+    pub async fn create(create: &AccessToken, con: &mut PgConnection) -> Result<(), DriverError> {
+        let ctx = create.context();
+        // language=SQL
+        sqlx::query(
+            r#"
+            INSERT INTO access_tokens (
+                id,
+                client_id,
+                account,
+                scope,
+                aud,
+                iss,
+                sub,
+                exp,
+                iat,
+                nbf,
+                dpop_jkt,
+                x5t_s256,
+                act,
+                authorization_details,
+                created_at,
+                updated_at
+            )
+            VALUES (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8,
+                $9,
+                $10,
+                $11,
+                $12,
+                $13,
+                $14,
+                $15,
+                $16
+            )
+        "#,
+        )
+        .bind(TokenDigest::from(create.id()).as_ref())
+        .bind(ctx.client_id().id())
+        .bind(ctx.account().as_ref().map(AsRef::<Uuid>::as_ref))
+        .bind(
+            ctx.scope()
+                .iter()
+                .map(AsRef::<str>::as_ref)
+                .collect::<Vec<&str>>(),
+        )
+        .bind(ctx.audience().as_ref())
+        .bind(ctx.issuer().as_ref())
+        .bind(ctx.subject().as_ref())
+        .bind(ctx.expired_in().as_ref())
+        .bind(ctx.issued_at().as_ref())
+        .bind(ctx.not_before().as_ref())
+        .bind(ctx.confirmation().as_ref().and_then(Confirmation::jkt))
+        .bind(ctx.confirmation().as_ref().and_then(Confirmation::x5t_s256))
+        .bind(ctx.actor().as_ref().map(Json))
+        .bind(ctx.authorization_details().as_ref().map(Json))
+        .bind(create.date().created_at().as_ref())
+        .bind(create.date().updated_at().as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update(update: &AccessToken, con: &mut PgConnection) -> Result<(), DriverError> {
+        let ctx = update.context();
+        // language=SQL
+        sqlx::query(
+            r#"
+            UPDATE access_tokens
+            SET
+                scope = $1,
+                aud = $2,
+                exp = $3,
+                nbf = $4,
+                updated_at = $5
+            WHERE
+                id = $6
+        "#,
+        )
+        .bind(
+            ctx.scope()
+                .iter()
+                .map(AsRef::<str>::as_ref)
+                .collect::<Vec<&str>>(),
+        )
+        .bind(ctx.audience().as_ref())
+        .bind(ctx.expired_in().as_ref())
+        .bind(ctx.not_before().as_ref())
+        .bind(update.date().updated_at().as_ref())
+        .bind(TokenDigest::from(update.id()).as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(delete: &AccessTokenId, con: &mut PgConnection) -> Result<(), DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            DELETE FROM access_tokens WHERE id = $1
+        "#,
+        )
+        .bind(TokenDigest::from(delete).as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(
+        id: &AccessTokenId,
+        con: &mut PgConnection,
+    ) -> Result<Option<AccessToken>, DriverError> {
+        // language=SQL
+        let found = sqlx::query_as::<_, AccessTokenRow>(
+            r#"
+            SELECT
+                access_tokens.*,
+                clients.client_id_iat
+            FROM access_tokens
+            JOIN clients ON access_tokens.client_id = clients.client_id
+            WHERE access_tokens.id = $1
+        "#,
+        )
+        .bind(TokenDigest::from(id).as_ref())
+        .fetch_optional(&mut *con)
+        .await?
+        .map(|row| row.into_access_token(id.clone()));
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgAccessTokenInternal;
+    use crate::database::client::tests::{create_dummy_data, test_pool};
+    use kernel::external::{Duration, OffsetDateTime};
+    use kernel::prelude::entities::{AccessToken, AccessTokenId, Issuer, ScopeMethod};
+
+    #[ignore = "It depends on Postgres and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let pool = test_pool().await?;
+
+        let mut transaction = pool.begin().await?;
+
+        let client = create_dummy_data(&mut transaction).await?;
+
+        let created_at = OffsetDateTime::now_utc();
+        let scopes = vec!["read", "write"]
+            .into_iter()
+            .map(ScopeMethod::new)
+            .collect::<Vec<ScopeMethod>>();
+
+        let token = AccessToken::new(
+            AccessTokenId::default(),
+            created_at,
+            created_at,
+            *client.id(),
+            *client.owner(),
+            scopes,
+            Issuer::new("https://stellar.example.com"),
+            client.id().id().to_string(),
+            client.owner().to_string(),
+            Duration::new(3600, 0),
+        );
+
+        PgAccessTokenInternal::create(&token, &mut transaction).await?;
+
+        let found = PgAccessTokenInternal::find_by_id(token.id(), &mut transaction).await?;
+        println!("{:?}", found);
+        assert!(found.is_some());
+
+        PgAccessTokenInternal::delete(token.id(), &mut transaction).await?;
+
+        let found = PgAccessTokenInternal::find_by_id(token.id(), &mut transaction).await?;
+        assert!(found.is_none());
+
+        transaction.rollback().await?;
+
+        Ok(())
+    }
+}
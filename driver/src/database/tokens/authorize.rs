@@ -39,6 +39,12 @@ impl AuthorizeTokenRepository for AuthorizeTokenVolatileDataBase {
         let found = AuthorizeTokenRedisInternal::find(id, &mut con).await?;
         Ok(found)
     }
+
+    async fn take(&self, id: &AuthorizeTokenId) -> Result<Option<AuthorizeToken>, KernelError> {
+        let mut con = self.acquire().await?;
+        let taken = AuthorizeTokenRedisInternal::take(id, &mut con).await?;
+        Ok(taken)
+    }
 }
 
 pub(in crate::database) struct AuthorizeTokenRedisInternal;
@@ -81,6 +87,20 @@ impl AuthorizeTokenRedisInternal {
             .transpose()?;
         Ok(token)
     }
+
+    pub async fn take(
+        id: &AuthorizeTokenId,
+        con: &mut RedisConnection,
+    ) -> Result<Option<AuthorizeToken>, DriverError> {
+        let raw: Option<String> = redis::cmd("GETDEL")
+            .arg(namespace(id))
+            .query_async(&mut *con)
+            .await?;
+        let token = raw
+            .map(|raw| serde_json::from_str::<AuthorizeToken>(&raw))
+            .transpose()?;
+        Ok(token)
+    }
 }
 
 fn namespace(key: impl AsRef<str>) -> String {
@@ -120,6 +140,9 @@ mod tests {
             scopes,
             response_type,
             redirect_uri,
+            None,
+            None,
+            None,
             expired_in,
         );
 
@@ -134,6 +157,12 @@ mod tests {
         println!("{:?}", found);
         assert_eq!(Some(token.clone()), found);
 
+        let taken = AuthorizeTokenRedisInternal::take(token.id(), &mut con).await?;
+        assert_eq!(Some(token.clone()), taken);
+        let taken = AuthorizeTokenRedisInternal::take(token.id(), &mut con).await?;
+        assert!(taken.is_none());
+
+        AuthorizeTokenRedisInternal::save(token.id(), &token, &mut con).await?;
         AuthorizeTokenRedisInternal::dele(token.id(), &mut con).await?;
 
         let found = AuthorizeTokenRedisInternal::find(token.id(), &mut con).await?;
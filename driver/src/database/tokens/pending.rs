@@ -116,6 +116,9 @@ mod tests {
             scopes,
             response_type,
             redirect_uri,
+            None,
+            None,
+            None,
             expired_in,
         );
 
@@ -0,0 +1,345 @@
+use crate::DriverError;
+use kernel::external::{OffsetDateTime, Uuid};
+use kernel::interfaces::repository::RefreshTokenRepository;
+use kernel::prelude::entities::{
+    AuthorizationDetails, ClientId, Confirmation, DestructRefreshToken, ExpiredIn, LoggedAt,
+    RefreshToken, RefreshTokenFamily, RefreshTokenId, ScopeMethod, TokenDigest, UserId,
+};
+use kernel::KernelError;
+use sqlx::types::Json;
+use sqlx::{PgConnection, Pool, Postgres};
+
+#[derive(Clone)]
+pub struct RefreshTokenDataBase {
+    pool: Pool<Postgres>,
+}
+
+impl RefreshTokenDataBase {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl RefreshTokenRepository for RefreshTokenDataBase {
+    async fn create(&self, create: &RefreshToken) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgRefreshTokenInternal::create(create, &mut con).await?;
+        Ok(())
+    }
+
+    async fn rotate(&self, rotated: &RefreshToken) -> Result<bool, KernelError> {
+        let mut transaction = self.pool.begin().await.map_err(DriverError::SqlX)?;
+
+        let swapped = match PgRefreshTokenInternal::rotate(rotated, &mut transaction).await {
+            Ok(swapped) => swapped,
+            Err(r) => {
+                transaction.rollback().await.map_err(DriverError::SqlX)?;
+                return Err(KernelError::Driver(anyhow::Error::new(r)));
+            }
+        };
+
+        transaction.commit().await.map_err(DriverError::SqlX)?;
+
+        Ok(swapped)
+    }
+
+    async fn delete(&self, delete: &RefreshTokenId) -> Result<(), KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        PgRefreshTokenInternal::delete(delete, &mut con).await?;
+        Ok(())
+    }
+
+    async fn revoke_family(&self, family: &RefreshTokenFamily) -> Result<(), KernelError> {
+        let mut transaction = self.pool.begin().await.map_err(DriverError::SqlX)?;
+
+        if let Err(r) = PgRefreshTokenInternal::revoke_family(family, &mut transaction).await {
+            transaction.rollback().await.map_err(DriverError::SqlX)?;
+            return Err(KernelError::Driver(anyhow::Error::new(r)));
+        }
+
+        transaction.commit().await.map_err(DriverError::SqlX)?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &RefreshTokenId) -> Result<Option<RefreshToken>, KernelError> {
+        let mut con = self.pool.acquire().await.map_err(DriverError::SqlX)?;
+        let found = PgRefreshTokenInternal::find_by_id(id, &mut con).await?;
+        Ok(found)
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct RefreshTokenRow {
+    family: Uuid,
+    access_token: String,
+    client_id: Uuid,
+    client_id_iat: OffsetDateTime,
+    account: Uuid,
+    scope: Vec<String>,
+    resource: Option<String>,
+    authorization_details: Option<Json<AuthorizationDetails>>,
+    dpop_jkt: Option<String>,
+    x5t_s256: Option<String>,
+    exp: OffsetDateTime,
+    rotated: bool,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl RefreshTokenRow {
+    /// Only the digest is stored, so the id has to be supplied by the caller who looked it up.
+    fn into_refresh_token(self, id: RefreshTokenId) -> RefreshToken {
+        let value = self;
+        DestructRefreshToken {
+            id,
+            date: LoggedAt::new(value.created_at, value.updated_at),
+            family: RefreshTokenFamily::new(value.family),
+            access_token: TokenDigest::new(value.access_token),
+            client_id: ClientId::new(value.client_id, value.client_id_iat),
+            account: UserId::new(value.account),
+            scope: value.scope.into_iter().map(ScopeMethod::new).collect(),
+            resource: value.resource,
+            authorization_details: value.authorization_details.map(|details| details.0),
+            cnf: Confirmation::from_parts(value.dpop_jkt, value.x5t_s256),
+            exp: ExpiredIn::from(value.exp),
+            rotated: value.rotated,
+        }
+        .freeze()
+    }
+}
+
+pub(in crate::database) struct PgRefreshTokenInternal;
+
+impl PgRefreshTokenInternal {
+    pub async fn create(create: &RefreshToken, con: &mut PgConnection) -> Result<(), DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (
+                id,
+                family,
+                access_token,
+                client_id,
+                account,
+                scope,
+                resource,
+                authorization_details,
+                dpop_jkt,
+                x5t_s256,
+                exp,
+                rotated,
+                created_at,
+                updated_at
+            )
+            VALUES (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8,
+                $9,
+                $10,
+                $11,
+                $12,
+                $13,
+                $14
+            )
+        "#,
+        )
+        .bind(TokenDigest::from(create.id()).as_ref())
+        .bind(create.family().as_ref())
+        .bind(create.access_token().as_ref())
+        .bind(create.client_id().id())
+        .bind(AsRef::<Uuid>::as_ref(create.account()))
+        .bind(
+            create
+                .scope()
+                .iter()
+                .map(AsRef::<str>::as_ref)
+                .collect::<Vec<&str>>(),
+        )
+        .bind(create.resource())
+        .bind(create.authorization_details().as_ref().map(Json))
+        .bind(create.confirmation().as_ref().and_then(Confirmation::jkt))
+        .bind(
+            create
+                .confirmation()
+                .as_ref()
+                .and_then(Confirmation::x5t_s256),
+        )
+        .bind(create.expired_in().as_ref())
+        .bind(create.is_rotated())
+        .bind(create.date().created_at().as_ref())
+        .bind(create.date().updated_at().as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Compare-and-set on `rotated`, so that only one of concurrent refreshes wins.
+    pub async fn rotate(
+        rotated: &RefreshToken,
+        con: &mut PgConnection,
+    ) -> Result<bool, DriverError> {
+        // language=SQL
+        let updated = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET
+                rotated = true,
+                updated_at = $1
+            WHERE
+                id = $2 AND rotated = false
+        "#,
+        )
+        .bind(rotated.date().updated_at().as_ref())
+        .bind(TokenDigest::from(rotated.id()).as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        // language=SQL
+        sqlx::query(
+            r#"
+            DELETE FROM access_tokens WHERE id = $1
+        "#,
+        )
+        .bind(rotated.access_token().as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(true)
+    }
+
+    pub async fn delete(
+        delete: &RefreshTokenId,
+        con: &mut PgConnection,
+    ) -> Result<(), DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            DELETE FROM refresh_tokens WHERE id = $1
+        "#,
+        )
+        .bind(TokenDigest::from(delete).as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn revoke_family(
+        family: &RefreshTokenFamily,
+        con: &mut PgConnection,
+    ) -> Result<(), DriverError> {
+        // language=SQL
+        sqlx::query(
+            r#"
+            DELETE FROM access_tokens
+            WHERE id IN (SELECT access_token FROM refresh_tokens WHERE family = $1)
+        "#,
+        )
+        .bind(family.as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        // language=SQL
+        sqlx::query(
+            r#"
+            DELETE FROM refresh_tokens WHERE family = $1
+        "#,
+        )
+        .bind(family.as_ref())
+        .execute(&mut *con)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(
+        id: &RefreshTokenId,
+        con: &mut PgConnection,
+    ) -> Result<Option<RefreshToken>, DriverError> {
+        // language=SQL
+        let found = sqlx::query_as::<_, RefreshTokenRow>(
+            r#"
+            SELECT
+                refresh_tokens.*,
+                clients.client_id_iat
+            FROM refresh_tokens
+            JOIN clients ON refresh_tokens.client_id = clients.client_id
+            WHERE refresh_tokens.id = $1
+        "#,
+        )
+        .bind(TokenDigest::from(id).as_ref())
+        .fetch_optional(&mut *con)
+        .await?
+        .map(|row| row.into_refresh_token(id.clone()));
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgRefreshTokenInternal;
+    use crate::database::client::tests::{create_dummy_data, test_pool};
+    use kernel::external::{Duration, OffsetDateTime};
+    use kernel::prelude::entities::{
+        AccessTokenId, RefreshToken, RefreshTokenFamily, RefreshTokenId, ScopeMethod,
+    };
+
+    #[ignore = "It depends on Postgres and does not work as is."]
+    #[tokio::test]
+    async fn all() -> anyhow::Result<()> {
+        let pool = test_pool().await?;
+
+        let mut transaction = pool.begin().await?;
+
+        let client = create_dummy_data(&mut transaction).await?;
+
+        let created_at = OffsetDateTime::now_utc();
+        let family = RefreshTokenFamily::default();
+
+        let token = RefreshToken::new(
+            RefreshTokenId::default(),
+            created_at,
+            created_at,
+            family,
+            &AccessTokenId::default(),
+            *client.id(),
+            *client.owner(),
+            vec![ScopeMethod::new("read")],
+            None,
+            None,
+            Duration::new(3600, 0),
+        );
+
+        PgRefreshTokenInternal::create(&token, &mut transaction).await?;
+
+        let found = PgRefreshTokenInternal::find_by_id(token.id(), &mut transaction).await?;
+        println!("{:?}", found);
+        assert!(found.is_some());
+
+        assert!(PgRefreshTokenInternal::rotate(&token, &mut transaction).await?);
+        assert!(!PgRefreshTokenInternal::rotate(&token, &mut transaction).await?);
+
+        PgRefreshTokenInternal::revoke_family(&family, &mut transaction).await?;
+
+        let found = PgRefreshTokenInternal::find_by_id(token.id(), &mut transaction).await?;
+        assert!(found.is_none());
+
+        transaction.rollback().await?;
+
+        Ok(())
+    }
+}
@@ -19,6 +19,8 @@ pub enum DriverError {
     #[error(transparent)]
     FileSystem(anyhow::Error),
     #[error(transparent)]
+    Encryption(anyhow::Error),
+    #[error(transparent)]
     Kernel(#[from] KernelError),
 }
 
@@ -33,6 +35,7 @@ impl From<DriverError> for KernelError {
             DriverError::Lettre(e) => KernelError::Driver(e),
             DriverError::Serde(e) => KernelError::Driver(e),
             DriverError::FileSystem(e) => KernelError::Driver(e),
+            DriverError::Encryption(e) => KernelError::Driver(e),
             DriverError::Kernel(internal) => internal,
         }
     }
@@ -4,6 +4,7 @@ mod driver;
 mod error;
 pub mod transport;
 
+pub use self::config::{ProtectedResourceConfig, TrustedIssuerConfig};
 pub use self::driver::*;
 pub use self::error::*;
 
@@ -1,4 +1,7 @@
 mod blacklist;
+mod bounded;
+mod jwks;
+mod request_object;
 mod verify_mail;
 
-pub use self::{blacklist::*, verify_mail::*};
+pub use self::{blacklist::*, jwks::*, request_object::*, verify_mail::*};
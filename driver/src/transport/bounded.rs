@@ -0,0 +1,49 @@
+use crate::DriverError;
+use reqwest::redirect::Policy;
+use reqwest::{Client, Response};
+use std::time::Duration;
+
+/// Documents fetched on behalf of a client, such as request objects and JWK sets, are small,
+/// so anything slower or larger than this is given up on.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// A client that does not follow redirects,
+/// so it never leaves the uri that was checked before the fetch.
+pub(in crate::transport) fn bounded_client() -> Client {
+    Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(TIMEOUT)
+        .redirect(Policy::none())
+        .build()
+        .expect("TLS backend cannot be initialized")
+}
+
+pub(in crate::transport) async fn read_bounded(
+    mut response: Response,
+) -> Result<String, DriverError> {
+    let too_large = || {
+        DriverError::Reqwest(anyhow::anyhow!(
+            "response body exceeds {} bytes.",
+            MAX_BODY_SIZE
+        ))
+    };
+
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_BODY_SIZE as u64)
+    {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_BODY_SIZE {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    String::from_utf8(body).map_err(|e| DriverError::Reqwest(anyhow::Error::new(e)))
+}
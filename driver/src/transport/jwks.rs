@@ -0,0 +1,47 @@
+use super::bounded::{bounded_client, read_bounded};
+use crate::DriverError;
+use kernel::interfaces::transport::JwksTransporter;
+use kernel::prelude::entities::ClientJwkSet;
+use kernel::KernelError;
+use reqwest::Client;
+
+#[derive(Clone)]
+pub struct JwkSetFetcher {
+    client: Client,
+}
+
+impl JwkSetFetcher {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Default for JwkSetFetcher {
+    fn default() -> Self {
+        Self::new(bounded_client())
+    }
+}
+
+#[async_trait::async_trait]
+impl JwksTransporter for JwkSetFetcher {
+    async fn fetch(&self, uri: &str) -> Result<ClientJwkSet, KernelError> {
+        JwksRequestInternal::request(&self.client, uri).await
+    }
+}
+
+pub(in crate::transport) struct JwksRequestInternal;
+
+impl JwksRequestInternal {
+    async fn request(client: &Client, uri: &str) -> Result<ClientJwkSet, KernelError> {
+        let response = client
+            .get(uri)
+            .send()
+            .await
+            .map_err(DriverError::from)?
+            .error_for_status()
+            .map_err(DriverError::from)?;
+        let document = read_bounded(response).await?;
+
+        ClientJwkSet::from_document(&document)
+    }
+}
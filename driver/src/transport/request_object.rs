@@ -0,0 +1,48 @@
+use super::bounded::{bounded_client, read_bounded};
+use crate::DriverError;
+use kernel::interfaces::transport::RequestObjectTransporter;
+use kernel::prelude::entities::RequestObject;
+use kernel::KernelError;
+use reqwest::Client;
+
+#[derive(Clone)]
+pub struct RequestObjectFetcher {
+    client: Client,
+}
+
+impl RequestObjectFetcher {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Default for RequestObjectFetcher {
+    fn default() -> Self {
+        Self::new(bounded_client())
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestObjectTransporter for RequestObjectFetcher {
+    async fn fetch(&self, uri: &str) -> Result<RequestObject, KernelError> {
+        RequestObjectRequestInternal::request(&self.client, uri).await
+    }
+}
+
+pub(in crate::transport) struct RequestObjectRequestInternal;
+
+impl RequestObjectRequestInternal {
+    async fn request(client: &Client, uri: &str) -> Result<RequestObject, KernelError> {
+        let response = client
+            .get(uri)
+            .header(reqwest::header::ACCEPT, "application/oauth-authz-req+jwt")
+            .send()
+            .await
+            .map_err(DriverError::from)?
+            .error_for_status()
+            .map_err(DriverError::from)?;
+        let jwt = read_bounded(response).await?;
+
+        Ok(RequestObject::new(jwt.trim()))
+    }
+}
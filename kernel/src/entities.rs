@@ -1,7 +1,8 @@
 mod account;
 mod client;
+mod signing_key;
 mod time;
 mod token;
 mod volatiles;
 
-pub use self::{account::*, client::*, time::*, token::*, volatiles::*};
+pub use self::{account::*, client::*, signing_key::*, time::*, token::*, volatiles::*};
@@ -87,6 +87,11 @@ impl Account {
     pub fn verified_at(&self) -> &VerifiedAt {
         &self.verified_at
     }
+
+    /// `true` once the address has been verified, that is when `verified_at` has passed.
+    pub fn is_verified(&self) -> bool {
+        *self.verified_at.as_ref() <= OffsetDateTime::now_utc()
+    }
 }
 
 #[derive(Debug, Hash, Serialize, Deserialize, Destructure)]
@@ -4,7 +4,9 @@ use destructure::Destructure;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+mod access_token_format;
 mod auth_method;
+mod certificate;
 mod client_desc;
 mod client_id;
 mod client_name;
@@ -20,15 +22,16 @@ mod policy_uri;
 mod redirect;
 mod regi_access_token;
 mod regi_endpoint;
+mod request_object;
 mod response_type;
 mod scope;
 mod tos_uri;
 
 pub use self::{
-    auth_method::*, client_desc::*, client_id::*, client_name::*, client_secret::*,
-    client_types::*, client_uri::*, contacts::*, grant_type::*, jwt::*, keys::*, logo_uri::*,
-    policy_uri::*, redirect::*, regi_access_token::*, regi_endpoint::*, response_type::*, scope::*,
-    tos_uri::*,
+    access_token_format::*, auth_method::*, certificate::*, client_desc::*, client_id::*,
+    client_name::*, client_secret::*, client_types::*, client_uri::*, contacts::*, grant_type::*,
+    jwt::*, keys::*, logo_uri::*, policy_uri::*, redirect::*, regi_access_token::*,
+    regi_endpoint::*, request_object::*, response_type::*, scope::*, tos_uri::*,
 };
 
 /// Client.
@@ -56,6 +59,12 @@ pub struct Client {
     jwks: Option<Jwks>,
     conf_token: RegistrationAccessToken,
     conf_endpoint: RegistrationEndPoint,
+    access_token_format: AccessTokenFormat,
+    require_pushed_authorization_requests: bool,
+    require_signed_request_object: bool,
+    request_uris: Vec<String>,
+    tls_client_auth_subject: Option<TlsClientAuthSubject>,
+    token_exchange_audiences: Vec<String>,
 }
 // Fixme: Should consider adopting Builder pattern as it requires very long parameters.
 impl Client {
@@ -99,6 +108,12 @@ impl Client {
             jwks: jwk.into(),
             conf_token: RegistrationAccessToken::new(conf_access_token),
             conf_endpoint: RegistrationEndPoint::new(conf_endpoint),
+            access_token_format: AccessTokenFormat::default(),
+            require_pushed_authorization_requests: false,
+            require_signed_request_object: false,
+            request_uris: Vec::new(),
+            tls_client_auth_subject: None,
+            token_exchange_audiences: Vec::new(),
         })
     }
 }
@@ -175,4 +190,43 @@ impl Client {
     pub fn conf_endpoint(&self) -> &RegistrationEndPoint {
         &self.conf_endpoint
     }
+
+    pub fn access_token_format(&self) -> &AccessTokenFormat {
+        &self.access_token_format
+    }
+
+    /// If `true`, the authorization endpoint only accepts requests pushed beforehand.
+    ///
+    /// See [RFC9126 Section 6](https://datatracker.ietf.org/doc/html/rfc9126#section-6)
+    pub fn require_pushed_authorization_requests(&self) -> bool {
+        self.require_pushed_authorization_requests
+    }
+
+    /// If `true`, authorization requests must be sent as a signed [RequestObject].
+    ///
+    /// See [RFC9101 Section 10.5](https://datatracker.ietf.org/doc/html/rfc9101#section-10.5)
+    pub fn require_signed_request_object(&self) -> bool {
+        self.require_signed_request_object
+    }
+
+    /// The `request_uri` values this client has registered, the only ones the server fetches.
+    ///
+    /// See [RFC9101 Section 10.4.1](https://datatracker.ietf.org/doc/html/rfc9101#section-10.4.1)
+    pub fn request_uris(&self) -> &[String] {
+        &self.request_uris
+    }
+
+    /// Subject the certificate of a `tls_client_auth` client is expected to have.
+    ///
+    /// See [RFC8705 Section 2.1.2](https://datatracker.ietf.org/doc/html/rfc8705#section-2.1.2)
+    pub fn tls_client_auth_subject(&self) -> &Option<TlsClientAuthSubject> {
+        &self.tls_client_auth_subject
+    }
+
+    /// Audiences this client may exchange tokens into with the token exchange grant.
+    ///
+    /// See [RFC8693 Section 2.1](https://datatracker.ietf.org/doc/html/rfc8693#section-2.1)
+    pub fn token_exchange_audiences(&self) -> &[String] {
+        &self.token_exchange_audiences
+    }
 }
@@ -0,0 +1,37 @@
+use crate::KernelError;
+use serde::{Deserialize, Serialize};
+
+/// Format of access tokens issued to the client.
+///
+/// `Jwt` tokens can be validated by resource servers offline.
+///
+/// See [RFC9068](https://datatracker.ietf.org/doc/html/rfc9068)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AccessTokenFormat {
+    #[default]
+    Opaque,
+    Jwt,
+}
+
+impl AsRef<str> for AccessTokenFormat {
+    fn as_ref(&self) -> &str {
+        match self {
+            AccessTokenFormat::Opaque => "opaque",
+            AccessTokenFormat::Jwt => "jwt",
+        }
+    }
+}
+
+impl TryFrom<String> for AccessTokenFormat {
+    type Error = KernelError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "opaque" => Ok(AccessTokenFormat::Opaque),
+            "jwt" => Ok(AccessTokenFormat::Jwt),
+            _ => Err(KernelError::InvalidValue {
+                method: "access token format",
+                value,
+            }),
+        }
+    }
+}
@@ -9,6 +9,23 @@ pub enum TokenEndPointAuthMethod {
     None,
 
     PrivateKeyJWT,
+
+    TlsClientAuth,
+    SelfSignedTlsClientAuth,
+}
+
+impl TokenEndPointAuthMethod {
+    /// Client authentication methods the token endpoint accepts.
+    ///
+    /// Published as `token_endpoint_auth_methods_supported` in the server metadata.
+    pub const SUPPORTED: [TokenEndPointAuthMethod; 6] = [
+        TokenEndPointAuthMethod::ClientSecretBasic,
+        TokenEndPointAuthMethod::ClientSecretPost,
+        TokenEndPointAuthMethod::PrivateKeyJWT,
+        TokenEndPointAuthMethod::TlsClientAuth,
+        TokenEndPointAuthMethod::SelfSignedTlsClientAuth,
+        TokenEndPointAuthMethod::None,
+    ];
 }
 
 impl TryFrom<String> for TokenEndPointAuthMethod {
@@ -26,6 +43,8 @@ impl FromStr for TokenEndPointAuthMethod {
             "client_secret_basic" => Ok(Self::ClientSecretBasic),
             "none" => Ok(Self::None),
             "private_key_jwt" => Ok(Self::PrivateKeyJWT),
+            "tls_client_auth" => Ok(Self::TlsClientAuth),
+            "self_signed_tls_client_auth" => Ok(Self::SelfSignedTlsClientAuth),
             _ => Err(KernelError::InvalidValue {
                 method: "from_str",
                 value: s.to_string(),
@@ -41,6 +60,8 @@ impl AsRef<str> for TokenEndPointAuthMethod {
             TokenEndPointAuthMethod::ClientSecretBasic => "client_secret_basic",
             TokenEndPointAuthMethod::None => "none",
             TokenEndPointAuthMethod::PrivateKeyJWT => "private_key_jwt",
+            TokenEndPointAuthMethod::TlsClientAuth => "tls_client_auth",
+            TokenEndPointAuthMethod::SelfSignedTlsClientAuth => "self_signed_tls_client_auth",
         }
     }
 }
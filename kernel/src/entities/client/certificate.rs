@@ -0,0 +1,424 @@
+use crate::KernelError;
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use simple_asn1::{ASN1Block, ASN1Class, BigUint, OID};
+use std::net::IpAddr;
+
+/// OID of the Subject Alternative Name extension.
+const SUBJECT_ALT_NAME: [u64; 4] = [2, 5, 29, 17];
+
+/// OID of `rsaEncryption` in `subjectPublicKeyInfo`.
+const RSA_ENCRYPTION: [u64; 7] = [1, 2, 840, 113549, 1, 1, 1];
+
+/// X.509 certificate presented by the client on a mutual-TLS connection.
+///
+/// See [RFC8705 Section 2](https://datatracker.ietf.org/doc/html/rfc8705#section-2)
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    der: Vec<u8>,
+    subject_dn: String,
+    sans: Vec<SubjectAltName>,
+    rsa_public_key: Option<Vec<u8>>,
+}
+
+/// Subject Alternative Name entries that can be registered for `tls_client_auth`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SubjectAltName {
+    Dns(String),
+    Uri(String),
+    Ip(IpAddr),
+    Email(String),
+}
+
+impl ClientCertificate {
+    /// Parses a PEM encoded certificate.
+    pub fn from_pem(pem: &str) -> Result<Self, KernelError> {
+        let pem = pem::parse(pem.trim()).map_err(|e| KernelError::InvalidValue {
+            method: "parse client certificate",
+            value: e.to_string(),
+        })?;
+        if pem.tag() != "CERTIFICATE" {
+            return Err(KernelError::InvalidValue {
+                method: "parse client certificate",
+                value: format!("`{}` is not a certificate.", pem.tag()),
+            });
+        }
+        Self::from_der(pem.into_contents())
+    }
+
+    pub fn from_der(der: Vec<u8>) -> Result<Self, KernelError> {
+        let invalid = |value: &str| KernelError::InvalidValue {
+            method: "parse client certificate",
+            value: value.to_string(),
+        };
+
+        let blocks = simple_asn1::from_der(&der).map_err(|e| invalid(&e.to_string()))?;
+        let Some(ASN1Block::Sequence(_, certificate)) = blocks.first() else {
+            return Err(invalid("certificate must be a sequence."));
+        };
+        let Some(ASN1Block::Sequence(_, tbs)) = certificate.first() else {
+            return Err(invalid("`tbsCertificate` must be a sequence."));
+        };
+
+        // `version` is omitted for v1 certificates.
+        let fields = match tbs.first() {
+            Some(ASN1Block::Explicit(ASN1Class::ContextSpecific, _, tag, _))
+                if *tag == BigUint::from(0u8) =>
+            {
+                &tbs[1..]
+            }
+            _ => &tbs[..],
+        };
+
+        // serialNumber, signature, issuer, validity, subject
+        let Some(ASN1Block::Sequence(_, subject)) = fields.get(4) else {
+            return Err(invalid("`subject` must be a sequence."));
+        };
+
+        let Some(ASN1Block::Sequence(_, public_key_info)) = fields.get(5) else {
+            return Err(invalid("`subjectPublicKeyInfo` must be a sequence."));
+        };
+
+        let extensions = fields.iter().find_map(|field| match field {
+            ASN1Block::Explicit(ASN1Class::ContextSpecific, _, tag, extensions)
+                if *tag == BigUint::from(3u8) =>
+            {
+                match extensions.as_ref() {
+                    ASN1Block::Sequence(_, extensions) => Some(extensions.as_slice()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+
+        Ok(Self {
+            subject_dn: distinguished_name(subject)?,
+            sans: extensions
+                .map(subject_alt_names)
+                .transpose()?
+                .unwrap_or_default(),
+            rsa_public_key: rsa_public_key(public_key_info)?,
+            der,
+        })
+    }
+
+    /// Subject of the certificate, in the string representation of RFC4514.
+    pub fn subject_dn(&self) -> &str {
+        &self.subject_dn
+    }
+
+    /// Base64Url encoded SHA-256 hash of the DER encoded certificate, used as `x5t#S256`.
+    ///
+    /// See [RFC8705 Section 3.1](https://datatracker.ietf.org/doc/html/rfc8705#section-3.1)
+    pub fn thumbprint(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(&self.der))
+    }
+
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// DER encoded `RSAPublicKey` (PKCS#1) of the subject, if the key is an RSA key.
+    pub fn rsa_public_key(&self) -> Option<&[u8]> {
+        self.rsa_public_key.as_deref()
+    }
+
+    /// Whether the certificate was issued for the subject registered with the client.
+    ///
+    /// See [RFC8705 Section 2.1.2](https://datatracker.ietf.org/doc/html/rfc8705#section-2.1.2)
+    pub fn matches(&self, subject: &TlsClientAuthSubject) -> bool {
+        match subject {
+            TlsClientAuthSubject::SubjectDn(dn) => self.subject_dn.eq(dn),
+            TlsClientAuthSubject::SanDns(dns) => self.sans.iter().any(
+                |san| matches!(san, SubjectAltName::Dns(name) if name.eq_ignore_ascii_case(dns)),
+            ),
+            TlsClientAuthSubject::SanUri(uri) => self
+                .sans
+                .iter()
+                .any(|san| matches!(san, SubjectAltName::Uri(name) if name.eq(uri))),
+            TlsClientAuthSubject::SanIp(ip) => ip.parse::<IpAddr>().is_ok_and(|ip| {
+                self.sans
+                    .iter()
+                    .any(|san| matches!(san, SubjectAltName::Ip(name) if name.eq(&ip)))
+            }),
+            TlsClientAuthSubject::SanEmail(email) => self
+                .sans
+                .iter()
+                .any(|san| matches!(san, SubjectAltName::Email(name) if name.eq(email))),
+        }
+    }
+}
+
+/// The one subject a client registers for the `tls_client_auth` method.
+///
+/// See [RFC8705 Section 2.1.2](https://datatracker.ietf.org/doc/html/rfc8705#section-2.1.2)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TlsClientAuthSubject {
+    SubjectDn(String),
+    SanDns(String),
+    SanUri(String),
+    SanIp(String),
+    SanEmail(String),
+}
+
+impl TlsClientAuthSubject {
+    /// Builds the subject from the name of the client metadata it was registered with.
+    pub fn new(name: &str, value: impl Into<String>) -> Result<Self, KernelError> {
+        let value = value.into();
+        match name {
+            "tls_client_auth_subject_dn" => Ok(Self::SubjectDn(value)),
+            "tls_client_auth_san_dns" => Ok(Self::SanDns(value)),
+            "tls_client_auth_san_uri" => Ok(Self::SanUri(value)),
+            "tls_client_auth_san_ip" => Ok(Self::SanIp(value)),
+            "tls_client_auth_san_email" => Ok(Self::SanEmail(value)),
+            _ => Err(KernelError::InvalidValue {
+                method: "tls client auth subject",
+                value: name.to_string(),
+            }),
+        }
+    }
+
+    /// Name of the client metadata.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TlsClientAuthSubject::SubjectDn(_) => "tls_client_auth_subject_dn",
+            TlsClientAuthSubject::SanDns(_) => "tls_client_auth_san_dns",
+            TlsClientAuthSubject::SanUri(_) => "tls_client_auth_san_uri",
+            TlsClientAuthSubject::SanIp(_) => "tls_client_auth_san_ip",
+            TlsClientAuthSubject::SanEmail(_) => "tls_client_auth_san_email",
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        match self {
+            TlsClientAuthSubject::SubjectDn(value)
+            | TlsClientAuthSubject::SanDns(value)
+            | TlsClientAuthSubject::SanUri(value)
+            | TlsClientAuthSubject::SanIp(value)
+            | TlsClientAuthSubject::SanEmail(value) => value,
+        }
+    }
+}
+
+fn oid(oid: &OID) -> Result<Vec<u64>, KernelError> {
+    oid.as_vec::<u64>().map_err(|e| KernelError::InvalidValue {
+        method: "parse client certificate",
+        value: e.to_string(),
+    })
+}
+
+/// Formats a `Name` as RFC4514 does, starting from the last RDN.
+///
+/// See [RFC4514 Section 2](https://datatracker.ietf.org/doc/html/rfc4514#section-2)
+fn distinguished_name(rdns: &[ASN1Block]) -> Result<String, KernelError> {
+    let mut formatted = Vec::with_capacity(rdns.len());
+    for rdn in rdns.iter().rev() {
+        let ASN1Block::Set(_, attributes) = rdn else {
+            continue;
+        };
+        let mut rdn = Vec::with_capacity(attributes.len());
+        for attribute in attributes {
+            let ASN1Block::Sequence(_, attribute) = attribute else {
+                continue;
+            };
+            let (Some(ASN1Block::ObjectIdentifier(_, ty)), Some(value)) =
+                (attribute.first(), attribute.get(1))
+            else {
+                continue;
+            };
+            let ty = oid(ty)?;
+            let ty = match ty.as_slice() {
+                [2, 5, 4, 3] => "CN".to_string(),
+                [2, 5, 4, 6] => "C".to_string(),
+                [2, 5, 4, 7] => "L".to_string(),
+                [2, 5, 4, 8] => "ST".to_string(),
+                [2, 5, 4, 9] => "STREET".to_string(),
+                [2, 5, 4, 10] => "O".to_string(),
+                [2, 5, 4, 11] => "OU".to_string(),
+                [0, 9, 2342, 19200300, 100, 1, 1] => "UID".to_string(),
+                [0, 9, 2342, 19200300, 100, 1, 25] => "DC".to_string(),
+                other => other
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("."),
+            };
+            let value = match value {
+                ASN1Block::UTF8String(_, value)
+                | ASN1Block::PrintableString(_, value)
+                | ASN1Block::TeletexString(_, value)
+                | ASN1Block::IA5String(_, value)
+                | ASN1Block::UniversalString(_, value)
+                | ASN1Block::BMPString(_, value) => escape(value),
+                // https://datatracker.ietf.org/doc/html/rfc4514#section-2.4
+                other => {
+                    let der =
+                        simple_asn1::to_der(other).map_err(|e| KernelError::InvalidValue {
+                            method: "parse client certificate",
+                            value: e.to_string(),
+                        })?;
+                    format!(
+                        "#{}",
+                        der.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+                    )
+                }
+            };
+            rdn.push(format!("{}={}", ty, value));
+        }
+        formatted.push(rdn.join("+"));
+    }
+    Ok(formatted.join(","))
+}
+
+/// See [RFC4514 Section 2.4](https://datatracker.ietf.org/doc/html/rfc4514#section-2.4)
+fn escape(value: &str) -> String {
+    let last = value.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => escaped.push('\\'),
+            '#' if i == 0 => escaped.push('\\'),
+            ' ' if i == 0 || i == last => escaped.push('\\'),
+            _ => {}
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Reads the `subjectPublicKey` of `subjectPublicKeyInfo` if the algorithm is `rsaEncryption`.
+///
+/// See [RFC3279 Section 2.3.1](https://datatracker.ietf.org/doc/html/rfc3279#section-2.3.1)
+fn rsa_public_key(public_key_info: &[ASN1Block]) -> Result<Option<Vec<u8>>, KernelError> {
+    let (Some(ASN1Block::Sequence(_, algorithm)), Some(ASN1Block::BitString(_, _, key))) =
+        (public_key_info.first(), public_key_info.get(1))
+    else {
+        return Ok(None);
+    };
+    let Some(ASN1Block::ObjectIdentifier(_, id)) = algorithm.first() else {
+        return Ok(None);
+    };
+    if oid(id)? != RSA_ENCRYPTION {
+        return Ok(None);
+    }
+    Ok(Some(key.clone()))
+}
+
+/// Reads the names of the Subject Alternative Name extension, if there is one.
+///
+/// See [RFC5280 Section 4.2.1.6](https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.6)
+fn subject_alt_names(extensions: &[ASN1Block]) -> Result<Vec<SubjectAltName>, KernelError> {
+    for extension in extensions {
+        let ASN1Block::Sequence(_, extension) = extension else {
+            continue;
+        };
+        let Some(ASN1Block::ObjectIdentifier(_, id)) = extension.first() else {
+            continue;
+        };
+        if oid(id)? != SUBJECT_ALT_NAME {
+            continue;
+        }
+        // `critical` is omitted when it is `false`.
+        let Some(ASN1Block::OctetString(_, value)) = extension.last() else {
+            continue;
+        };
+        let names = simple_asn1::from_der(value).map_err(|e| KernelError::InvalidValue {
+            method: "parse client certificate",
+            value: e.to_string(),
+        })?;
+        let Some(ASN1Block::Sequence(_, names)) = names.first() else {
+            return Ok(Vec::new());
+        };
+
+        return Ok(names
+            .iter()
+            .filter_map(|name| match name {
+                ASN1Block::Unknown(ASN1Class::ContextSpecific, false, _, tag, value) => {
+                    let value = value.as_slice();
+                    match u8::try_from(tag).ok()? {
+                        1 => Some(SubjectAltName::Email(
+                            String::from_utf8(value.to_vec()).ok()?,
+                        )),
+                        2 => Some(SubjectAltName::Dns(String::from_utf8(value.to_vec()).ok()?)),
+                        6 => Some(SubjectAltName::Uri(String::from_utf8(value.to_vec()).ok()?)),
+                        7 => match value.len() {
+                            4 => Some(SubjectAltName::Ip(IpAddr::from(
+                                <[u8; 4]>::try_from(value).ok()?,
+                            ))),
+                            16 => Some(SubjectAltName::Ip(IpAddr::from(
+                                <[u8; 16]>::try_from(value).ok()?,
+                            ))),
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect());
+    }
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::{ClientCertificate, TlsClientAuthSubject};
+
+    const TEST_CERT: &str = r#"-----BEGIN CERTIFICATE-----
+MIICTjCCAfSgAwIBAgIUBv0Onvjgq2R2xtO3JyuUe1kzwzUwCgYIKoZIzj0EAwIw
+RDELMAkGA1UEBhMCSlAxEDAOBgNVBAoMB1N0ZWxsYXIxIzAhBgNVBAMMGmNsaWVu
+dC5zdGVsbGFyLmV4YW1wbGUuY29tMCAXDTI2MTAxODA5NDM0OVoYDzIxMjYwOTI0
+MDk0MzQ5WjBEMQswCQYDVQQGEwJKUDEQMA4GA1UECgwHU3RlbGxhcjEjMCEGA1UE
+AwwaY2xpZW50LnN0ZWxsYXIuZXhhbXBsZS5jb20wWTATBgcqhkjOPQIBBggqhkjO
+PQMBBwNCAASkUMEbL/WRX5XtRXA926J9qLeKNtCfLP0xNM9uA/X13yYBjQyE6Guv
+98XUcmRt/zzPHWwFDd/8doiyQlB6CQUuo4HBMIG+MB0GA1UdDgQWBBS9l6ieiJca
+EpreSQxmcvOCZeyJfzAfBgNVHSMEGDAWgBS9l6ieiJcaEpreSQxmcvOCZeyJfzAP
+BgNVHRMBAf8EBTADAQH/MGsGA1UdEQRkMGKCGmNsaWVudC5zdGVsbGFyLmV4YW1w
+bGUuY29thiJodHRwczovL2NsaWVudC5zdGVsbGFyLmV4YW1wbGUuY29thwTAAAIB
+gRpjbGllbnRAc3RlbGxhci5leGFtcGxlLmNvbTAKBggqhkjOPQQDAgNIADBFAiEA
+kEbtH9l8PRacs2iJIiVazuFzCTTXrXbtKnP0Usdrg5QCIBYvDoLrhdxa2D3St+qg
+VHrl8/VvelKWc3TR20OpXe+h
+-----END CERTIFICATE-----
+"#;
+
+    #[test]
+    fn parse() -> anyhow::Result<()> {
+        let cert = ClientCertificate::from_pem(TEST_CERT)?;
+        assert_eq!(
+            cert.subject_dn(),
+            "CN=client.stellar.example.com,O=Stellar,C=JP"
+        );
+        assert_eq!(
+            cert.thumbprint(),
+            "s1ijKfBrKI0LAYT0BVNeso2Asty4bYUpg1Mm506z_hs"
+        );
+
+        assert!(cert.rsa_public_key().is_none());
+
+        assert!(cert.matches(&TlsClientAuthSubject::SubjectDn(
+            "CN=client.stellar.example.com,O=Stellar,C=JP".to_string()
+        )));
+        assert!(cert.matches(&TlsClientAuthSubject::SanDns(
+            "client.stellar.example.com".to_string()
+        )));
+        assert!(cert.matches(&TlsClientAuthSubject::SanUri(
+            "https://client.stellar.example.com".to_string()
+        )));
+        assert!(cert.matches(&TlsClientAuthSubject::SanIp("192.0.2.1".to_string())));
+        assert!(cert.matches(&TlsClientAuthSubject::SanEmail(
+            "client@stellar.example.com".to_string()
+        )));
+
+        assert!(!cert.matches(&TlsClientAuthSubject::SubjectDn(
+            "CN=other.stellar.example.com,O=Stellar,C=JP".to_string()
+        )));
+        assert!(!cert.matches(&TlsClientAuthSubject::SanDns(
+            "other.stellar.example.com".to_string()
+        )));
+        assert!(!cert.matches(&TlsClientAuthSubject::SanIp("192.0.2.2".to_string())));
+
+        assert!(ClientCertificate::from_pem("not a certificate").is_err());
+        Ok(())
+    }
+}
@@ -1,11 +1,25 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::SaltString;
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use destructure::Destructure;
+use once_cell::sync::Lazy;
 use rand::distributions::{Alphanumeric, Distribution};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::time::UNIX_EPOCH;
 use time::OffsetDateTime;
 
 use crate::KernelError;
 
+static ARGON: Lazy<Argon2> = Lazy::new(Argon2::default);
+
+/// Prefix of secrets that were stored in plaintext and digested by a migration.
+const LEGACY_SHA256: &str = "$sha256$";
+
+/// Client secret.
+///
+/// Only the Argon2 hash is held, so the plain secret can be shown
+/// to the client just once, when it is generated.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize, Destructure)]
 pub struct ClientSecret {
     secret: String,
@@ -13,13 +27,48 @@ pub struct ClientSecret {
 }
 
 impl ClientSecret {
-    pub fn new(secret: impl Into<String>, exp: impl Into<Option<OffsetDateTime>>) -> Self {
+    pub fn new(
+        secret: impl Into<String>,
+        exp: impl Into<Option<OffsetDateTime>>,
+    ) -> Result<Self, KernelError> {
+        let secret: String = secret.into();
+        let salt = SaltString::generate(&mut OsRng);
+        let secret = ARGON
+            .hash_password(secret.as_bytes(), &salt)
+            .map_err(KernelError::Cryption)?
+            .to_string();
+        Ok(Self {
+            secret,
+            expires_at: exp.into(),
+        })
+    }
+
+    /// Initializes with an already hashed secret, such as one read from storage.
+    pub fn new_unchecked(
+        secret: impl Into<String>,
+        exp: impl Into<Option<OffsetDateTime>>,
+    ) -> Self {
         Self {
             secret: secret.into(),
             expires_at: exp.into(),
         }
     }
 
+    /// Generates a random secret.
+    ///
+    /// The plain secret is returned together with the hashed one
+    /// and cannot be recovered afterwards.
+    pub fn generate(exp: impl Into<Option<OffsetDateTime>>) -> Result<(String, Self), KernelError> {
+        let plain = Alphanumeric
+            .sample_iter(&mut rand::thread_rng())
+            .take(64)
+            .map(char::from)
+            .collect::<String>();
+        let secret = Self::new(plain.as_str(), exp)?;
+        Ok((plain, secret))
+    }
+
+    /// Hashed secret.
     pub fn secret(&self) -> &str {
         &self.secret
     }
@@ -32,11 +81,53 @@ impl ClientSecret {
         ((UNIX_EPOCH - self.expires_at?).abs().whole_seconds() as u64).into()
     }
 
-    pub fn verify(&self, _secret: impl Into<String>) -> Result<(), KernelError> {
-        todo!()
+    /// `true` once `expires_at` has passed. A secret without it never expires.
+    ///
+    /// See [RFC7591 Section 3.2.1](https://datatracker.ietf.org/doc/html/rfc7591#section-3.2.1)
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|exp| exp <= OffsetDateTime::now_utc())
+    }
+
+    /// `true` if the secret was digested by the migration and should be hashed with Argon2
+    /// the next time the client presents it.
+    pub fn needs_rehash(&self) -> bool {
+        self.secret.starts_with(LEGACY_SHA256)
+    }
+
+    /// Verifies the secret presented by the client in constant time.
+    ///
+    /// If a match is found, `()` is returned;
+    /// if no match is found or an error occurs, [KernelError] is returned.
+    pub fn verify(&self, secret: impl Into<String>) -> Result<(), KernelError> {
+        let secret: String = secret.into();
+
+        if let Some(digest) = self.secret.strip_prefix(LEGACY_SHA256) {
+            let presented = Sha256::digest(secret.as_bytes())
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>();
+            if !constant_time_eq(digest.as_bytes(), presented.as_bytes()) {
+                return Err(KernelError::InvalidValue {
+                    method: "verify",
+                    value: "client secret".to_string(),
+                });
+            }
+            return Ok(());
+        }
+
+        let hashed = PasswordHash::new(&self.secret).map_err(KernelError::Cryption)?;
+        ARGON
+            .verify_password(secret.as_bytes(), &hashed)
+            .map_err(KernelError::InvalidPassword)?;
+        Ok(())
     }
 }
 
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 impl From<ClientSecret> for (String, Option<OffsetDateTime>) {
     fn from(value: ClientSecret) -> Self {
         (value.secret, value.expires_at)
@@ -44,34 +135,59 @@ impl From<ClientSecret> for (String, Option<OffsetDateTime>) {
 }
 
 impl Default for ClientSecret {
+    /// Generates a random secret whose plain value is discarded.
+    ///
+    /// Use [ClientSecret::generate] when the secret has to be handed to the client.
     fn default() -> Self {
-        Self::new(
-            Alphanumeric
-                .sample_iter(&mut rand::thread_rng())
-                .take(64)
-                .map(char::from)
-                .collect::<String>(),
-            OffsetDateTime::now_utc(),
-        )
+        Self::generate(None)
+            .map(|(_, secret)| secret)
+            .expect("hashing with the default Argon2 parameters does not fail")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::entities::ClientSecret;
-    use rand::distributions::{Alphanumeric, Distribution};
     use time::OffsetDateTime;
 
     #[test]
     fn test() -> anyhow::Result<()> {
-        let code = Alphanumeric
-            .sample_iter(&mut rand::thread_rng())
-            .take(64)
-            .map(char::from)
-            .collect::<String>();
-        let secret = ClientSecret::new(code, OffsetDateTime::now_utc());
+        let (plain, secret) = ClientSecret::generate(OffsetDateTime::now_utc())?;
         let exp = secret.expires_at_as_u64();
         println!("{:?}", exp);
+
+        assert_ne!(plain, secret.secret());
+        secret.verify(plain)?;
+        assert!(secret.verify("wrong").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn legacy_test() {
+        // `sha256("secret")` as digested by the migration.
+        let secret = ClientSecret::new_unchecked(
+            "$sha256$2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b",
+            None,
+        );
+        assert!(secret.needs_rehash());
+        assert!(secret.verify("secret").is_ok());
+        assert!(secret.verify("wrong").is_err());
+
+        let rehashed = ClientSecret::new("secret", None).unwrap();
+        assert!(!rehashed.needs_rehash());
+        assert!(rehashed.verify("secret").is_ok());
+    }
+
+    #[test]
+    fn expiry_test() -> anyhow::Result<()> {
+        let (_, secret) = ClientSecret::generate(None)?;
+        assert!(!secret.is_expired());
+        let (_, secret) =
+            ClientSecret::generate(OffsetDateTime::now_utc() + time::Duration::hours(1))?;
+        assert!(!secret.is_expired());
+        let (_, secret) =
+            ClientSecret::generate(OffsetDateTime::now_utc() - time::Duration::hours(1))?;
+        assert!(secret.is_expired());
         Ok(())
     }
 }
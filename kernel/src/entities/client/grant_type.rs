@@ -59,6 +59,23 @@ pub enum GrantType {
     RefreshToken,
     JWTBearer,
     Saml2Bearer,
+    DeviceCode,
+    TokenExchange,
+}
+
+impl GrantType {
+    /// Grant types the token endpoint accepts.
+    ///
+    /// Published as `grant_types_supported` in the server metadata.
+    pub const SUPPORTED: [GrantType; 7] = [
+        GrantType::AuthorizationCode,
+        GrantType::RefreshToken,
+        GrantType::ClientCredentials,
+        GrantType::JWTBearer,
+        GrantType::Saml2Bearer,
+        GrantType::DeviceCode,
+        GrantType::TokenExchange,
+    ];
 }
 
 impl TryFrom<String> for GrantType {
@@ -77,8 +94,10 @@ impl FromStr for GrantType {
             "password" => Self::Password,
             "client_credentials" => Self::ClientCredentials,
             "refresh_token" => Self::RefreshToken,
-            "jwt_bearer" => Self::JWTBearer,
-            "saml2_bearer" => Self::Saml2Bearer,
+            "urn:ietf:params:oauth:grant-type:jwt-bearer" => Self::JWTBearer,
+            "urn:ietf:params:oauth:grant-type:saml2-bearer" => Self::Saml2Bearer,
+            "urn:ietf:params:oauth:grant-type:device_code" => Self::DeviceCode,
+            "urn:ietf:params:oauth:grant-type:token-exchange" => Self::TokenExchange,
             _ => {
                 return Err(KernelError::InvalidValue {
                     method: "from_str",
@@ -97,8 +116,10 @@ impl AsRef<str> for GrantType {
             GrantType::Password => "password",
             GrantType::ClientCredentials => "client_credentials",
             GrantType::RefreshToken => "refresh_token",
-            GrantType::JWTBearer => "jwt_bearer",
-            GrantType::Saml2Bearer => "saml2_bearer",
+            GrantType::JWTBearer => "urn:ietf:params:oauth:grant-type:jwt-bearer",
+            GrantType::Saml2Bearer => "urn:ietf:params:oauth:grant-type:saml2-bearer",
+            GrantType::DeviceCode => "urn:ietf:params:oauth:grant-type:device_code",
+            GrantType::TokenExchange => "urn:ietf:params:oauth:grant-type:token-exchange",
         }
     }
 }
@@ -1,5 +1,8 @@
+use crate::entities::{ClientCertificate, CLIENT_ASSERTION_ALGORITHMS};
 use crate::KernelError;
+use base64::{prelude::BASE64_STANDARD, Engine};
 use jsonwebkey::JsonWebKey;
+use jsonwebtoken::{Algorithm, DecodingKey, Header};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use try_ref::TryAsRef;
@@ -85,3 +88,101 @@ impl TryAsRef<JsonWebKey> for Jwks {
         }
     }
 }
+
+/// Set of public keys used to verify JWTs signed by a client.
+///
+/// Built from the inline `jwks` or the document published at `jwks_uri`.
+#[derive(Debug, Clone)]
+pub struct ClientJwkSet(jsonwebtoken::jwk::JwkSet);
+
+impl ClientJwkSet {
+    /// Parses a JWK Set document, or a single JWK.
+    pub fn from_document(document: &str) -> Result<Self, KernelError> {
+        if let Ok(set) = serde_json::from_str::<jsonwebtoken::jwk::JwkSet>(document) {
+            return Ok(Self(set));
+        }
+        let key = serde_json::from_str::<jsonwebtoken::jwk::Jwk>(document).map_err(|e| {
+            KernelError::InvalidValue {
+                method: "parse jwk set",
+                value: e.to_string(),
+            }
+        })?;
+        Ok(Self(jsonwebtoken::jwk::JwkSet { keys: vec![key] }))
+    }
+
+    /// Selects the key with `kid`, or the only key when `kid` is absent.
+    pub fn find(&self, kid: Option<&str>) -> Option<&jsonwebtoken::jwk::Jwk> {
+        match kid {
+            Some(kid) => self.0.find(kid),
+            None if self.0.keys.len() == 1 => self.0.keys.first(),
+            None => None,
+        }
+    }
+
+    /// Key to verify a JWT signed by the holder of this set.
+    ///
+    /// The `alg` of `header` must be one of [CLIENT_ASSERTION_ALGORITHMS],
+    /// and the `alg` of the key selected by `kid` too if the key names one,
+    /// so that a key registered for one algorithm does not verify another one.
+    /// `method` names the verification in errors.
+    pub fn decoding_key(
+        &self,
+        header: &Header,
+        method: &'static str,
+    ) -> Result<DecodingKey, KernelError> {
+        if !CLIENT_ASSERTION_ALGORITHMS.contains(&header.alg) {
+            return Err(KernelError::InvalidValue {
+                method,
+                value: format!("`{:?}` is not accepted.", header.alg),
+            });
+        }
+
+        let Some(jwk) = self.find(header.kid.as_deref()) else {
+            return Err(KernelError::InvalidValue {
+                method,
+                value: format!("no key matches `kid`: {:?}", header.kid),
+            });
+        };
+
+        if let Some(alg) = jwk.common.key_algorithm {
+            if Algorithm::from_str(&alg.to_string()).ok() != Some(header.alg) {
+                return Err(KernelError::InvalidValue {
+                    method,
+                    value: format!("`{:?}` does not match the key's `alg`: {}", header.alg, alg),
+                });
+            }
+        }
+
+        Ok(DecodingKey::from_jwk(jwk)?)
+    }
+
+    /// Whether the certificate is registered as the first entry of `x5c`,
+    /// or by its `x5t#S256`, in any of the keys.
+    ///
+    /// See [RFC8705 Section 2.2.2](https://datatracker.ietf.org/doc/html/rfc8705#section-2.2.2)
+    pub fn contains_certificate(&self, certificate: &ClientCertificate) -> bool {
+        let thumbprint = certificate.thumbprint();
+        self.0.keys.iter().any(|key| {
+            let chain = key
+                .common
+                .x509_chain
+                .as_ref()
+                .and_then(|chain| chain.first())
+                .and_then(|x5c| BASE64_STANDARD.decode(x5c).ok())
+                .is_some_and(|der| der.eq(certificate.der()));
+            let x5t = key
+                .common
+                .x509_sha256_fingerprint
+                .as_ref()
+                .is_some_and(|x5t| x5t.eq(&thumbprint));
+            chain || x5t
+        })
+    }
+}
+
+impl TryFrom<&JsonWebKey> for ClientJwkSet {
+    type Error = KernelError;
+    fn try_from(value: &JsonWebKey) -> Result<Self, Self::Error> {
+        Self::from_document(&value.to_string())
+    }
+}
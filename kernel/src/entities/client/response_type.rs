@@ -56,6 +56,13 @@ pub enum ResponseType {
     Token,
 }
 
+impl ResponseType {
+    /// Response types the authorization endpoint accepts.
+    ///
+    /// Published as `response_types_supported` in the server metadata.
+    pub const SUPPORTED: [ResponseType; 1] = [ResponseType::Code];
+}
+
 impl PartialEq<String> for ResponseType {
     fn eq(&self, other: &String) -> bool {
         self.as_ref() == other
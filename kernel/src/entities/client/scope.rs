@@ -59,9 +59,22 @@ impl FromIterator<(ScopeMethod, ScopeDescription)> for Scopes {
 pub struct ScopeMethod(String);
 
 impl ScopeMethod {
+    /// Scope that makes the request an OpenID Connect request.
+    ///
+    /// See [OpenID Connect Core 1.0 Section 3.1.2.1](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest)
+    pub const OPENID: &'static str = "openid";
+    /// Grants access to the `name` and `preferred_username` claims.
+    pub const PROFILE: &'static str = "profile";
+    /// Grants access to the `email` and `email_verified` claims.
+    pub const EMAIL: &'static str = "email";
+
     pub fn new(method: impl Into<String>) -> Self {
         Self(method.into())
     }
+
+    pub fn is_openid(&self) -> bool {
+        self.0 == Self::OPENID
+    }
 }
 
 impl From<ScopeMethod> for String {
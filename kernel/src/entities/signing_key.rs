@@ -0,0 +1,292 @@
+use crate::entities::LoggedAt;
+use crate::KernelError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use destructure::Destructure;
+use jsonwebtoken::jwk::Jwk;
+use jsonwebtoken::{EncodingKey, Header};
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+
+mod algorithm;
+mod key_id;
+mod key_state;
+mod private_key;
+
+pub use self::{algorithm::*, key_id::*, key_state::*, private_key::*};
+
+/// Key this server signs tokens with.
+///
+/// See [RFC7517](https://datatracker.ietf.org/doc/html/rfc7517)
+#[derive(Debug, Clone, Destructure)]
+pub struct SigningKey {
+    kid: KeyId,
+    alg: SigningAlgorithm,
+    state: KeyState,
+    private_key: PrivateKey,
+    public_key: Jwk,
+    date: LoggedAt,
+    activated_at: Option<OffsetDateTime>,
+    retired_at: Option<OffsetDateTime>,
+}
+
+impl SigningKey {
+    /// How long a key stays `Active` before it is rotated.
+    pub const ROTATION_PERIOD: Duration = Duration::days(90);
+
+    /// How long a `Retired` key stays published.
+    ///
+    /// This must be longer than any token signed with it lives.
+    pub const RETENTION_PERIOD: Duration = Duration::days(7);
+
+    /// `public_key` is a JWK document, as returned by [Self::public_document].
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        kid: impl Into<String>,
+        alg: SigningAlgorithm,
+        state: KeyState,
+        private_key: impl Into<Vec<u8>>,
+        public_key: impl AsRef<str>,
+        created_at: impl Into<OffsetDateTime>,
+        updated_at: impl Into<OffsetDateTime>,
+        activated_at: impl Into<Option<OffsetDateTime>>,
+        retired_at: impl Into<Option<OffsetDateTime>>,
+    ) -> Result<Self, KernelError> {
+        let public_key = serde_json::from_str::<Jwk>(public_key.as_ref())
+            .map_err(|e| KernelError::Serde(anyhow::Error::new(e)))?;
+        Ok(Self {
+            kid: KeyId::new(kid),
+            alg,
+            state,
+            private_key: PrivateKey::new(private_key),
+            public_key,
+            date: LoggedAt::new(created_at, updated_at),
+            activated_at: activated_at.into(),
+            retired_at: retired_at.into(),
+        })
+    }
+
+    /// Generates a new key pair in the `Next` state.
+    pub fn generate(alg: SigningAlgorithm) -> Result<Self, KernelError> {
+        let kid = KeyId::default();
+        let (private_key, public_key) = match alg {
+            SigningAlgorithm::RS256 => generate_rsa()?,
+            SigningAlgorithm::ES256 => generate_ec()?,
+            SigningAlgorithm::EdDSA => generate_ed()?,
+        };
+
+        let mut public_key = serde_json::from_value::<Jwk>(public_key)
+            .map_err(|e| KernelError::Serde(anyhow::Error::new(e)))?;
+        public_key.common.key_id = Some(kid.as_ref().to_string());
+        public_key.common.key_algorithm = serde_json::from_value(alg.as_ref().into()).ok();
+        public_key.common.public_key_use = Some(jsonwebtoken::jwk::PublicKeyUse::Signature);
+
+        let now = OffsetDateTime::now_utc();
+        Ok(Self {
+            kid,
+            alg,
+            state: KeyState::Next,
+            private_key: PrivateKey::new(private_key),
+            public_key,
+            date: LoggedAt::new(now, now),
+            activated_at: None,
+            retired_at: None,
+        })
+    }
+
+    pub fn kid(&self) -> &KeyId {
+        &self.kid
+    }
+
+    pub fn alg(&self) -> &SigningAlgorithm {
+        &self.alg
+    }
+
+    pub fn state(&self) -> &KeyState {
+        &self.state
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    /// Public part of the key as a JWK, including `kid`, `alg` and `use`.
+    pub fn public_key(&self) -> &Jwk {
+        &self.public_key
+    }
+
+    /// [Self::public_key] serialized as a JWK document.
+    pub fn public_document(&self) -> Result<String, KernelError> {
+        serde_json::to_string(&self.public_key)
+            .map_err(|e| KernelError::Serde(anyhow::Error::new(e)))
+    }
+
+    pub fn date(&self) -> &LoggedAt {
+        &self.date
+    }
+
+    pub fn activated_at(&self) -> &Option<OffsetDateTime> {
+        &self.activated_at
+    }
+
+    pub fn retired_at(&self) -> &Option<OffsetDateTime> {
+        &self.retired_at
+    }
+
+    /// Returns `true` if an `Active` key has signed for [Self::ROTATION_PERIOD].
+    pub fn is_due(&self, now: OffsetDateTime) -> bool {
+        self.state == KeyState::Active
+            && !matches!(self.activated_at, Some(at) if now < at + Self::ROTATION_PERIOD)
+    }
+
+    /// Returns `true` if a `Retired` key has been kept for [Self::RETENTION_PERIOD].
+    pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.state == KeyState::Retired
+            && !matches!(self.retired_at, Some(at) if now < at + Self::RETENTION_PERIOD)
+    }
+
+    /// Signs `claims` as a JWT with this key, setting `alg`, `kid` and `typ` in the header.
+    pub fn sign(&self, typ: &str, claims: &impl Serialize) -> Result<String, KernelError> {
+        let der = self.private_key.as_ref();
+        let key = match self.alg {
+            SigningAlgorithm::RS256 => EncodingKey::from_rsa_der(der),
+            SigningAlgorithm::ES256 => EncodingKey::from_ec_der(der),
+            SigningAlgorithm::EdDSA => EncodingKey::from_ed_der(der),
+        };
+        let mut header = Header::new(self.alg.into());
+        header.kid = Some(self.kid.as_ref().to_string());
+        header.typ = Some(typ.to_string());
+        Ok(jsonwebtoken::encode(&header, claims, &key)?)
+    }
+
+    pub fn activate(mut self, now: OffsetDateTime) -> Self {
+        self.state = KeyState::Active;
+        self.activated_at = Some(now);
+        self.date = LoggedAt::new(*self.date.created_at().as_ref(), now);
+        self
+    }
+
+    pub fn retire(mut self, now: OffsetDateTime) -> Self {
+        self.state = KeyState::Retired;
+        self.retired_at = Some(now);
+        self.date = LoggedAt::new(*self.date.created_at().as_ref(), now);
+        self
+    }
+}
+
+fn generation_error(e: impl ToString) -> KernelError {
+    KernelError::External(anyhow::Error::msg(format!(
+        "failed to generate signing key: {}",
+        e.to_string()
+    )))
+}
+
+fn generate_rsa() -> Result<(Vec<u8>, serde_json::Value), KernelError> {
+    use rsa::pkcs1::EncodeRsaPrivateKey;
+    use rsa::traits::PublicKeyParts;
+
+    let key = rsa::RsaPrivateKey::new(&mut rand::rngs::OsRng, 2048).map_err(generation_error)?;
+    let der = key.to_pkcs1_der().map_err(generation_error)?;
+    let jwk = serde_json::json!({
+        "kty": "RSA",
+        "n": URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+        "e": URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+    });
+    Ok((der.as_bytes().to_vec(), jwk))
+}
+
+fn generate_ec() -> Result<(Vec<u8>, serde_json::Value), KernelError> {
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+
+    let rng = ring::rand::SystemRandom::new();
+    let der = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+        .map_err(generation_error)?;
+    let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, der.as_ref(), &rng)
+        .map_err(generation_error)?;
+    // Uncompressed point: 0x04 || x || y
+    let point = pair.public_key().as_ref();
+    let jwk = serde_json::json!({
+        "kty": "EC",
+        "crv": "P-256",
+        "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+        "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+    });
+    Ok((der.as_ref().to_vec(), jwk))
+}
+
+fn generate_ed() -> Result<(Vec<u8>, serde_json::Value), KernelError> {
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    let rng = ring::rand::SystemRandom::new();
+    let der = Ed25519KeyPair::generate_pkcs8(&rng).map_err(generation_error)?;
+    let pair = Ed25519KeyPair::from_pkcs8(der.as_ref()).map_err(generation_error)?;
+    let jwk = serde_json::json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "x": URL_SAFE_NO_PAD.encode(pair.public_key().as_ref()),
+    });
+    Ok((der.as_ref().to_vec(), jwk))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::{KeyState, SigningAlgorithm, SigningKey};
+    use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+    use serde::{Deserialize, Serialize};
+    use time::{Duration, OffsetDateTime};
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: i64,
+    }
+
+    fn sign_and_verify(alg: SigningAlgorithm) -> anyhow::Result<()> {
+        let key = SigningKey::generate(alg)?;
+        assert_eq!(key.state(), &KeyState::Next);
+        assert_eq!(
+            key.public_key().common.key_id.as_deref(),
+            Some(key.kid().as_ref())
+        );
+
+        let der = key.private_key().as_ref();
+        let enc = match alg {
+            SigningAlgorithm::RS256 => EncodingKey::from_rsa_der(der),
+            SigningAlgorithm::ES256 => EncodingKey::from_ec_der(der),
+            SigningAlgorithm::EdDSA => EncodingKey::from_ed_der(der),
+        };
+        let claims = Claims {
+            sub: "stellar".to_string(),
+            exp: (OffsetDateTime::now_utc() + Duration::minutes(5)).unix_timestamp(),
+        };
+        let jwt = jsonwebtoken::encode(&Header::new(alg.into()), &claims, &enc)?;
+
+        let dec = DecodingKey::from_jwk(key.public_key())?;
+        let dec = jsonwebtoken::decode::<Claims>(&jwt, &dec, &Validation::new(alg.into()))?;
+        assert_eq!(dec.claims.sub, "stellar");
+        Ok(())
+    }
+
+    #[test]
+    fn generate() -> anyhow::Result<()> {
+        sign_and_verify(SigningAlgorithm::RS256)?;
+        sign_and_verify(SigningAlgorithm::ES256)?;
+        sign_and_verify(SigningAlgorithm::EdDSA)?;
+        Ok(())
+    }
+
+    #[test]
+    fn rotation() -> anyhow::Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let key = SigningKey::generate(SigningAlgorithm::EdDSA)?;
+        assert!(!key.is_due(now));
+
+        let key = key.activate(now - SigningKey::ROTATION_PERIOD);
+        assert!(key.is_due(now));
+
+        let key = key.retire(now);
+        assert!(!key.is_expired(now));
+        assert!(key.is_expired(now + SigningKey::RETENTION_PERIOD));
+        Ok(())
+    }
+}
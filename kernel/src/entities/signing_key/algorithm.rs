@@ -0,0 +1,71 @@
+use crate::KernelError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+/// Algorithm of keys this server signs tokens with.
+///
+/// `RS256` is the default since it is the one every relying party must support.
+///
+/// See [RFC9068 Section 2.1](https://datatracker.ietf.org/doc/html/rfc9068#section-2.1)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SigningAlgorithm {
+    #[default]
+    RS256,
+    ES256,
+    EdDSA,
+}
+
+impl AsRef<str> for SigningAlgorithm {
+    fn as_ref(&self) -> &str {
+        match self {
+            SigningAlgorithm::RS256 => "RS256",
+            SigningAlgorithm::ES256 => "ES256",
+            SigningAlgorithm::EdDSA => "EdDSA",
+        }
+    }
+}
+
+impl SigningAlgorithm {
+    /// Base64url encoding of the left-most half of the hash of `value`,
+    /// using the hash function of this algorithm.
+    ///
+    /// Used for `at_hash` and `c_hash` in ID Tokens.
+    /// See [OpenID Connect Core 1.0 Section 3.1.3.6](https://openid.net/specs/openid-connect-core-1_0.html#CodeIDToken)
+    pub fn half_hash(&self, value: &str) -> String {
+        let digest = match self {
+            SigningAlgorithm::RS256 | SigningAlgorithm::ES256 => {
+                Sha256::digest(value.as_bytes()).to_vec()
+            }
+            // Ed25519 is defined over SHA-512.
+            SigningAlgorithm::EdDSA => Sha512::digest(value.as_bytes()).to_vec(),
+        };
+        URL_SAFE_NO_PAD.encode(&digest[..digest.len() / 2])
+    }
+}
+
+impl TryFrom<String> for SigningAlgorithm {
+    type Error = KernelError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "RS256" => Ok(SigningAlgorithm::RS256),
+            "ES256" => Ok(SigningAlgorithm::ES256),
+            "EdDSA" => Ok(SigningAlgorithm::EdDSA),
+            _ => Err(KernelError::InvalidValue {
+                method: "signing algorithm",
+                value,
+            }),
+        }
+    }
+}
+
+impl From<SigningAlgorithm> for jsonwebtoken::Algorithm {
+    fn from(value: SigningAlgorithm) -> Self {
+        match value {
+            SigningAlgorithm::RS256 => jsonwebtoken::Algorithm::RS256,
+            SigningAlgorithm::ES256 => jsonwebtoken::Algorithm::ES256,
+            SigningAlgorithm::EdDSA => jsonwebtoken::Algorithm::EdDSA,
+        }
+    }
+}
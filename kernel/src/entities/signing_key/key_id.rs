@@ -0,0 +1,30 @@
+use crate::services::RandomizeService;
+use serde::{Deserialize, Serialize};
+
+/// `kid` of a signing key, published in the JWK Set and in the header of signed tokens.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KeyId(String);
+
+impl KeyId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<KeyId> for String {
+    fn from(origin: KeyId) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for KeyId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for KeyId {
+    fn default() -> Self {
+        RandomizeService::gen_str(32, Self::new)
+    }
+}
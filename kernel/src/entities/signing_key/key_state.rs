@@ -0,0 +1,38 @@
+use crate::KernelError;
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle of a signing key.
+///
+/// A key is generated as `Next`, signs tokens while `Active`,
+/// and stays published as `Retired` until tokens signed with it have expired.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum KeyState {
+    Next,
+    Active,
+    Retired,
+}
+
+impl AsRef<str> for KeyState {
+    fn as_ref(&self) -> &str {
+        match self {
+            KeyState::Next => "next",
+            KeyState::Active => "active",
+            KeyState::Retired => "retired",
+        }
+    }
+}
+
+impl TryFrom<String> for KeyState {
+    type Error = KernelError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "next" => Ok(KeyState::Next),
+            "active" => Ok(KeyState::Active),
+            "retired" => Ok(KeyState::Retired),
+            _ => Err(KernelError::InvalidValue {
+                method: "key state",
+                value,
+            }),
+        }
+    }
+}
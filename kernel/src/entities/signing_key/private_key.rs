@@ -0,0 +1,26 @@
+use std::fmt::{Debug, Formatter};
+
+/// DER encoded private key.
+///
+/// `PKCS#1` for `RS256` and `PKCS#8` for the others, as `jsonwebtoken` expects.
+/// This must never be stored as is, `SigningKeyRepository` encrypts it at rest.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PrivateKey(Vec<u8>);
+
+impl PrivateKey {
+    pub fn new(der: impl Into<Vec<u8>>) -> Self {
+        Self(der.into())
+    }
+}
+
+impl AsRef<[u8]> for PrivateKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PrivateKey(**)")
+    }
+}
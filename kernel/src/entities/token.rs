@@ -1,5 +1,19 @@
 mod access;
+mod assertion;
+mod authorization_details;
 mod authorize;
 mod claims;
+mod digest;
+mod dpop;
+mod id;
+mod jarm;
+mod refresh;
+mod resource;
+mod response_mode;
+mod saml;
+mod trusted_issuer;
 
-pub use self::{access::*, authorize::*, claims::*};
+pub use self::{
+    access::*, assertion::*, authorization_details::*, authorize::*, claims::*, digest::*, dpop::*,
+    id::*, jarm::*, refresh::*, resource::*, response_mode::*, saml::*, trusted_issuer::*,
+};
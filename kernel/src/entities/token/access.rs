@@ -1,11 +1,14 @@
-use crate::entities::{ClientId, LoggedAt, ScopeMethod, UserId};
+use crate::entities::{AuthorizationDetails, ClientId, LoggedAt, ScopeMethod, SigningKey, UserId};
 use crate::services::RandomizeService;
+use crate::KernelError;
 use destructure::Destructure;
 use serde::{Deserialize, Serialize};
 use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
-use super::claims::{Audience, ExpiredIn, IssuedAt, Issuer, NotBefore, Subject};
+use super::claims::{
+    Actor, Audience, Confirmation, ExpiredIn, IssuedAt, Issuer, NotBefore, Subject,
+};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AccessTokenId(String);
@@ -46,13 +49,16 @@ pub struct AccessToken {
 pub struct AccessTokenContext {
     scope: Vec<ScopeMethod>,
     client_id: ClientId,
-    account: UserId,
+    account: Option<UserId>,
     aud: Audience,
     exp: ExpiredIn,
     iat: IssuedAt,
     iss: Issuer,
     nbf: NotBefore,
     sub: Subject,
+    cnf: Option<Confirmation>,
+    act: Option<Actor>,
+    authorization_details: Option<AuthorizationDetails>,
 }
 
 impl AccessTokenContext {
@@ -64,6 +70,13 @@ impl AccessTokenContext {
         &self.client_id
     }
 
+    /// The resource owner who authorized this token.
+    ///
+    /// `None` if the token was issued to the client itself, as in the Client Credentials Grant.
+    pub fn account(&self) -> &Option<UserId> {
+        &self.account
+    }
+
     pub fn expired_in(&self) -> &ExpiredIn {
         &self.exp
     }
@@ -87,6 +100,21 @@ impl AccessTokenContext {
     pub fn issuer(&self) -> &Issuer {
         &self.iss
     }
+
+    /// The key the token is bound to, if it is sender-constrained.
+    pub fn confirmation(&self) -> &Option<Confirmation> {
+        &self.cnf
+    }
+
+    /// The party acting on behalf of the subject, if the token was issued by a token exchange.
+    pub fn actor(&self) -> &Option<Actor> {
+        &self.act
+    }
+
+    /// See [RFC9396 Section 9](https://datatracker.ietf.org/doc/html/rfc9396#section-9)
+    pub fn authorization_details(&self) -> &Option<AuthorizationDetails> {
+        &self.authorization_details
+    }
 }
 
 impl AccessToken {
@@ -96,7 +124,7 @@ impl AccessToken {
         created_at: impl Into<OffsetDateTime>,
         updated_at: impl Into<OffsetDateTime>,
         linked_client: impl Into<Uuid>,
-        account: impl Into<Uuid>,
+        account: impl Into<Option<UserId>>,
         scoped: impl Into<Vec<ScopeMethod>>,
         issuer: impl Into<String>,
         audience: impl Into<String>,
@@ -109,17 +137,60 @@ impl AccessToken {
             ctx: AccessTokenContext {
                 scope: scoped.into(),
                 client_id: ClientId::new_at_now(linked_client),
-                account: UserId::new(account),
+                account: account.into(),
                 exp: ExpiredIn::new(expired_in),
                 iat: IssuedAt::default(),
                 nbf: NotBefore::default(),
                 sub: Subject::new(subject),
                 aud: Audience::new(audience),
                 iss: Issuer::new(issuer),
+                cnf: None,
+                act: None,
+                authorization_details: None,
             },
         }
     }
 
+    /// Binds the token to the key of the client, so that only its holder can present it.
+    pub fn bind(self, cnf: impl Into<Option<Confirmation>>) -> Self {
+        Self {
+            ctx: AccessTokenContext {
+                cnf: cnf.into(),
+                ..self.ctx
+            },
+            ..self
+        }
+    }
+
+    /// Records the party the token is delegated to.
+    ///
+    /// See [RFC8693 Section 1.1](https://datatracker.ietf.org/doc/html/rfc8693#section-1.1)
+    pub fn delegate(self, act: impl Into<Option<Actor>>) -> Self {
+        Self {
+            ctx: AccessTokenContext {
+                act: act.into(),
+                ..self.ctx
+            },
+            ..self
+        }
+    }
+
+    /// Records the authorization details the token is granted for.
+    ///
+    /// See [RFC9396 Section 7](https://datatracker.ietf.org/doc/html/rfc9396#section-7)
+    pub fn with_authorization_details(
+        self,
+        authorization_details: impl Into<Option<AuthorizationDetails>>,
+    ) -> Self {
+        Self {
+            ctx: AccessTokenContext {
+                authorization_details: authorization_details.into(),
+                ..self.ctx
+            },
+            ..self
+        }
+    }
+
     pub fn id(&self) -> &AccessTokenId {
         &self.id
     }
@@ -131,4 +202,61 @@ impl AccessToken {
     pub fn context(&self) -> &AccessTokenContext {
         &self.ctx
     }
+
+    /// Replaces the opaque id with a JWT signed by `key` that carries the token's context.
+    ///
+    /// See [RFC9068](https://datatracker.ietf.org/doc/html/rfc9068)
+    pub fn into_jwt(self, key: &SigningKey) -> Result<Self, KernelError> {
+        let ctx = &self.ctx;
+        let scope = ctx
+            .scope
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let claims = AccessTokenClaims {
+            iss: ctx.iss.as_ref(),
+            exp: ctx.exp.as_ref_i64(),
+            aud: ctx.aud.as_ref(),
+            sub: ctx.sub.as_ref(),
+            client_id: ctx.client_id.id().to_string(),
+            iat: ctx.iat.as_ref().unix_timestamp(),
+            nbf: ctx.nbf.as_ref().unix_timestamp(),
+            jti: RandomizeService::gen_str(32, |jti| jti),
+            scope: (!scope.is_empty()).then_some(scope),
+            cnf: ctx.cnf.as_ref(),
+            act: ctx.act.as_ref(),
+            authorization_details: ctx.authorization_details.as_ref(),
+        };
+        let jwt = key.sign(AccessTokenClaims::TYP, &claims)?;
+        Ok(Self {
+            id: AccessTokenId::new(jwt),
+            ..self
+        })
+    }
+}
+
+/// Claims of a JWT access token.
+#[derive(Serialize)]
+struct AccessTokenClaims<'a> {
+    iss: &'a str,
+    exp: i64,
+    aud: &'a str,
+    sub: &'a str,
+    client_id: String,
+    iat: i64,
+    nbf: i64,
+    jti: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cnf: Option<&'a Confirmation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    act: Option<&'a Actor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    authorization_details: Option<&'a AuthorizationDetails>,
+}
+
+impl AccessTokenClaims<'_> {
+    const TYP: &'static str = "at+jwt";
 }
@@ -0,0 +1,188 @@
+use crate::KernelError;
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Fine-grained authorization data requested with the `authorization_details` parameter,
+/// a JSON array of objects each identified by its `type`.
+///
+/// See [RFC9396 Section 2](https://datatracker.ietf.org/doc/html/rfc9396#section-2)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AuthorizationDetails(Vec<AuthorizationDetail>);
+
+impl AuthorizationDetails {
+    /// Parses the JSON encoded parameter value.
+    pub fn parse(raw: &str) -> Result<Self, KernelError> {
+        let values = serde_json::from_str::<Vec<Value>>(raw).map_err(|e| invalid(e.to_string()))?;
+        if values.is_empty() {
+            return Err(invalid("must contain at least one object."));
+        }
+        let details = values
+            .into_iter()
+            .map(AuthorizationDetail::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(details))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AuthorizationDetail> {
+        self.0.iter()
+    }
+
+    /// Returns `true` if every detail of `requested` is one of these,
+    /// as a token request can only ask for part of what was authorized.
+    ///
+    /// See [RFC9396 Section 6.1](https://datatracker.ietf.org/doc/html/rfc9396#section-6.1)
+    pub fn contains_all(&self, requested: &AuthorizationDetails) -> bool {
+        requested.iter().all(|detail| self.0.contains(detail))
+    }
+
+    /// The JSON encoded value, as returned in token and introspection responses.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).expect("details are always serializable")
+    }
+}
+
+/// An authorization details object, which must have a string `type`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "Value", into = "Value")]
+pub struct AuthorizationDetail(Value);
+
+impl AuthorizationDetail {
+    pub fn new(value: Value) -> Result<Self, KernelError> {
+        match value.get("type") {
+            Some(Value::String(_)) if value.is_object() => Ok(Self(value)),
+            _ => Err(invalid("each object must have a string `type`.")),
+        }
+    }
+
+    pub fn detail_type(&self) -> &str {
+        self.0["type"].as_str().unwrap_or_default()
+    }
+}
+
+impl TryFrom<Value> for AuthorizationDetail {
+    type Error = KernelError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<AuthorizationDetail> for Value {
+    fn from(origin: AuthorizationDetail) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<Value> for AuthorizationDetail {
+    fn as_ref(&self) -> &Value {
+        &self.0
+    }
+}
+
+/// Authorization details `type` accepted by a protected resource,
+/// with the JSON Schema its objects must satisfy.
+///
+/// See [RFC9396 Section 5](https://datatracker.ietf.org/doc/html/rfc9396#section-5)
+#[derive(Clone)]
+pub struct AuthorizationDetailsType {
+    detail_type: String,
+    schema: Arc<JSONSchema>,
+}
+
+impl AuthorizationDetailsType {
+    pub fn new(detail_type: impl Into<String>, schema: &Value) -> Result<Self, KernelError> {
+        let detail_type = detail_type.into();
+        let schema = JSONSchema::compile(schema).map_err(|e| KernelError::InvalidValue {
+            method: "authorization details type",
+            value: format!("schema of `{}` is invalid: {}", detail_type, e),
+        })?;
+        Ok(Self {
+            detail_type,
+            schema: Arc::new(schema),
+        })
+    }
+
+    pub fn detail_type(&self) -> &str {
+        &self.detail_type
+    }
+
+    pub fn validate(&self, detail: &AuthorizationDetail) -> Result<(), KernelError> {
+        self.schema.validate(detail.as_ref()).map_err(|errors| {
+            let reasons = errors.map(|e| e.to_string()).collect::<Vec<_>>();
+            invalid(format!("`{}`: {}", self.detail_type, reasons.join(", ")))
+        })
+    }
+}
+
+impl Debug for AuthorizationDetailsType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthorizationDetailsType")
+            .field("detail_type", &self.detail_type)
+            .finish_non_exhaustive()
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> KernelError {
+    KernelError::InvalidValue {
+        method: "authorization details",
+        value: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthorizationDetails, AuthorizationDetailsType};
+    use serde_json::json;
+
+    #[test]
+    fn validate() -> anyhow::Result<()> {
+        let ty = AuthorizationDetailsType::new(
+            "payment_initiation",
+            &json!({
+                "type": "object",
+                "properties": {
+                    "instructedAmount": {
+                        "type": "object",
+                        "properties": { "amount": { "type": "string" } },
+                        "required": ["amount"]
+                    }
+                },
+                "required": ["instructedAmount"]
+            }),
+        )?;
+
+        let details = AuthorizationDetails::parse(
+            r#"[{"type":"payment_initiation","instructedAmount":{"amount":"100"}}]"#,
+        )?;
+        for detail in details.iter() {
+            ty.validate(detail)?;
+        }
+
+        let details = AuthorizationDetails::parse(r#"[{"type":"payment_initiation"}]"#)?;
+        assert!(details.iter().all(|detail| ty.validate(detail).is_err()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn contains_all() -> anyhow::Result<()> {
+        let granted = AuthorizationDetails::parse(
+            r#"[{"type":"account_information","actions":["list"]},{"type":"payment_initiation"}]"#,
+        )?;
+        let requested = AuthorizationDetails::parse(r#"[{"type":"payment_initiation"}]"#)?;
+        assert!(granted.contains_all(&requested));
+        assert!(!requested.contains_all(&granted));
+        Ok(())
+    }
+
+    #[test]
+    fn reject() {
+        assert!(AuthorizationDetails::parse("[]").is_err());
+        assert!(AuthorizationDetails::parse(r#"{"type":"payment_initiation"}"#).is_err());
+        assert!(AuthorizationDetails::parse(r#"[{"actions":["list"]}]"#).is_err());
+    }
+}
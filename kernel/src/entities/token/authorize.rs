@@ -1,4 +1,4 @@
-use crate::entities::ResponseType;
+use crate::entities::{AuthorizationDetails, ResponseMode, ResponseType};
 use crate::{
     entities::{ClientId, LoggedAt, RedirectUri, ScopeMethod, UserId},
     services::RandomizeService,
@@ -10,7 +10,7 @@ use time::{Duration, OffsetDateTime};
 use try_ref::TryAsRef;
 use uuid::Uuid;
 
-use super::claims::ExpiredIn;
+use super::claims::{AuthTime, ExpiredIn, Nonce};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AuthorizeTokenId(String);
@@ -82,6 +82,24 @@ pub struct AuthorizeTokenContext {
     scopes: Vec<ScopeMethod>,
     response_type: ResponseType,
     redirect_uri: RedirectUri,
+    #[serde(default)]
+    nonce: Option<Nonce>,
+    /// Protected resource the authorization was requested for.
+    ///
+    /// See [RFC8707 Section 2.1](https://datatracker.ietf.org/doc/html/rfc8707#section-2.1)
+    #[serde(default)]
+    resource: Option<String>,
+    /// See [RFC9396 Section 2](https://datatracker.ietf.org/doc/html/rfc9396#section-2)
+    #[serde(default)]
+    authorization_details: Option<AuthorizationDetails>,
+    #[serde(default)]
+    response_mode: ResponseMode,
+    /// The request was made with the device authorization grant,
+    /// so there is no redirect to deliver the response to.
+    ///
+    /// See [RFC8628 Section 3.3](https://datatracker.ietf.org/doc/html/rfc8628#section-3.3)
+    #[serde(default)]
+    device: bool,
     expired_in: ExpiredIn,
 }
 
@@ -102,6 +120,27 @@ impl AuthorizeTokenContext {
         &self.redirect_uri
     }
 
+    pub fn nonce(&self) -> &Option<Nonce> {
+        &self.nonce
+    }
+
+    pub fn resource(&self) -> Option<&str> {
+        self.resource.as_deref()
+    }
+
+    pub fn authorization_details(&self) -> &Option<AuthorizationDetails> {
+        &self.authorization_details
+    }
+
+    /// How the authorization response is delivered, already resolved for the response type.
+    pub fn response_mode(&self) -> &ResponseMode {
+        &self.response_mode
+    }
+
+    pub fn is_device(&self) -> bool {
+        self.device
+    }
+
     pub fn expired_in(&self) -> &ExpiredIn {
         &self.expired_in
     }
@@ -112,6 +151,8 @@ pub struct AuthorizeToken {
     id: AuthorizeTokenId,
     date: LoggedAt,
     owned_by: TokenOwnedUser,
+    #[serde(default)]
+    auth_time: Option<AuthTime>,
     ctx: AuthorizeTokenContext,
 }
 
@@ -126,22 +167,57 @@ impl AuthorizeToken {
         scope: impl Into<Vec<ScopeMethod>>,
         response_type: impl Into<ResponseType>,
         redirect_uri: impl Into<String>,
+        nonce: impl Into<Option<String>>,
+        resource: impl Into<Option<String>>,
+        authorization_details: impl Into<Option<AuthorizationDetails>>,
         expired_in: impl Into<Duration>,
     ) -> Self {
+        let response_type = response_type.into();
         Self {
             id: AuthorizeTokenId::new(id),
             owned_by: TokenOwnedUser::new(owned_by.into().map(UserId::new)),
             date: LoggedAt::new(created_at, updated_at),
+            auth_time: None,
             ctx: AuthorizeTokenContext {
                 client_id: ClientId::new_at_now(client_id),
                 scopes: scope.into(),
-                response_type: response_type.into(),
+                response_mode: ResponseMode::default_for(&response_type),
+                response_type,
                 redirect_uri: RedirectUri::new(redirect_uri),
+                nonce: nonce.into().map(Nonce::new),
+                resource: resource.into(),
+                authorization_details: authorization_details.into(),
+                device: false,
                 expired_in: ExpiredIn::new(expired_in),
             },
         }
     }
 
+    /// Sets the `response_mode` requested in the authorization request.
+    ///
+    /// See [OAuth 2.0 Multiple Response Type Encoding Practices Section 2.1](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html#ResponseModes)
+    pub fn with_response_mode(self, response_mode: ResponseMode) -> Self {
+        let response_mode = response_mode.resolve(&self.ctx.response_type);
+        Self {
+            ctx: AuthorizeTokenContext {
+                response_mode,
+                ..self.ctx
+            },
+            ..self
+        }
+    }
+
+    /// Marks the token as issued for a device authorization request.
+    pub fn for_device(self) -> Self {
+        Self {
+            ctx: AuthorizeTokenContext {
+                device: true,
+                ..self.ctx
+            },
+            ..self
+        }
+    }
+
     pub fn id(&self) -> &AuthorizeTokenId {
         &self.id
     }
@@ -150,6 +226,17 @@ impl AuthorizeToken {
         &self.date
     }
 
+    pub fn owned_by(&self) -> &TokenOwnedUser {
+        &self.owned_by
+    }
+
+    /// When the resource owner authenticated to accept this request.
+    ///
+    /// `None` while the request is still pending.
+    pub fn auth_time(&self) -> &Option<AuthTime> {
+        &self.auth_time
+    }
+
     pub fn context(&self) -> &AuthorizeTokenContext {
         &self.ctx
     }
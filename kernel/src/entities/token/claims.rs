@@ -1,8 +1,14 @@
+mod act;
 mod aud;
+mod auth_time;
+mod cnf;
 mod exp;
 mod iat;
 mod iss;
 mod nbf;
+mod nonce;
 mod sub;
 
-pub use self::{aud::*, exp::*, iat::*, iss::*, nbf::*, sub::*};
+pub use self::{
+    act::*, aud::*, auth_time::*, cnf::*, exp::*, iat::*, iss::*, nbf::*, nonce::*, sub::*,
+};
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// The party acting on behalf of the subject, nesting the prior actors of a delegation chain.
+///
+/// See [RFC8693 Section 4.1](https://datatracker.ietf.org/doc/html/rfc8693#section-4.1)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Actor {
+    sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    act: Option<Box<Actor>>,
+}
+
+impl Actor {
+    pub fn new(sub: impl Into<String>, prior: impl Into<Option<Actor>>) -> Self {
+        Self {
+            sub: sub.into(),
+            act: prior.into().map(Box::new),
+        }
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.sub
+    }
+
+    /// The actor who delegated to this one, if the token was exchanged more than once.
+    pub fn prior(&self) -> Option<&Actor> {
+        self.act.as_deref()
+    }
+}
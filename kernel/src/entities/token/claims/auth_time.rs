@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// Time when the end-user authentication occurred.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AuthTime(OffsetDateTime);
+
+impl AuthTime {
+    fn new() -> Self {
+        Self(OffsetDateTime::now_utc())
+    }
+}
+
+impl From<OffsetDateTime> for AuthTime {
+    fn from(origin: OffsetDateTime) -> Self {
+        Self(origin)
+    }
+}
+
+impl From<AuthTime> for OffsetDateTime {
+    fn from(origin: AuthTime) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<OffsetDateTime> for AuthTime {
+    fn as_ref(&self) -> &OffsetDateTime {
+        &self.0
+    }
+}
+
+impl Default for AuthTime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// Key the token is bound to, so only its holder can present the token.
+///
+/// See [RFC7800 Section 3.1](https://datatracker.ietf.org/doc/html/rfc7800#section-3.1)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Confirmation {
+    /// JWK SHA-256 Thumbprint of the DPoP key.
+    ///
+    /// See [RFC9449 Section 6.1](https://datatracker.ietf.org/doc/html/rfc9449#section-6.1)
+    #[serde(rename = "jkt")]
+    Jkt(String),
+
+    /// X.509 Certificate SHA-256 Thumbprint of the mutual-TLS client certificate.
+    ///
+    /// See [RFC8705 Section 3.1](https://datatracker.ietf.org/doc/html/rfc8705#section-3.1)
+    #[serde(rename = "x5t#S256")]
+    X5tS256(String),
+}
+
+impl Confirmation {
+    /// The key proven with the request, a DPoP proof taking precedence over a client certificate.
+    pub fn from_parts(dpop_jkt: Option<String>, x5t_s256: Option<String>) -> Option<Self> {
+        dpop_jkt
+            .map(Confirmation::Jkt)
+            .or(x5t_s256.map(Confirmation::X5tS256))
+    }
+
+    pub fn jkt(&self) -> Option<&str> {
+        match self {
+            Confirmation::Jkt(jkt) => Some(jkt),
+            Confirmation::X5tS256(_) => None,
+        }
+    }
+
+    pub fn x5t_s256(&self) -> Option<&str> {
+        match self {
+            Confirmation::X5tS256(x5t) => Some(x5t),
+            Confirmation::Jkt(_) => None,
+        }
+    }
+
+    /// `token_type` of an access token bound to this key.
+    pub fn token_type(&self) -> &'static str {
+        match self {
+            Confirmation::Jkt(_) => "DPoP",
+            Confirmation::X5tS256(_) => "Bearer",
+        }
+    }
+}
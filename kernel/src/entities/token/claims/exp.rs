@@ -18,6 +18,12 @@ impl ExpiredIn {
     }
 }
 
+impl From<OffsetDateTime> for ExpiredIn {
+    fn from(origin: OffsetDateTime) -> Self {
+        Self(origin)
+    }
+}
+
 impl From<ExpiredIn> for OffsetDateTime {
     fn from(origin: ExpiredIn) -> Self {
         origin.0
@@ -10,6 +10,12 @@ impl IssuedAt {
     }
 }
 
+impl From<OffsetDateTime> for IssuedAt {
+    fn from(origin: OffsetDateTime) -> Self {
+        Self(origin)
+    }
+}
+
 impl From<IssuedAt> for OffsetDateTime {
     fn from(origin: IssuedAt) -> Self {
         origin.0
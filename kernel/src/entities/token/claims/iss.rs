@@ -1,3 +1,4 @@
+use crate::BASE_URL;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
@@ -20,3 +21,12 @@ impl AsRef<str> for Issuer {
         &self.0
     }
 }
+
+impl Default for Issuer {
+    /// The issuer identifier is this server's `BASE_URL` without a trailing slash.
+    ///
+    /// See [RFC8414 Section 2](https://datatracker.ietf.org/doc/html/rfc8414#section-2)
+    fn default() -> Self {
+        Self::new(BASE_URL.as_str().trim_end_matches('/'))
+    }
+}
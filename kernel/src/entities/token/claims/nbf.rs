@@ -10,6 +10,12 @@ impl NotBefore {
     }
 }
 
+impl From<OffsetDateTime> for NotBefore {
+    fn from(origin: OffsetDateTime) -> Self {
+        Self(origin)
+    }
+}
+
 impl From<NotBefore> for OffsetDateTime {
     fn from(origin: NotBefore) -> Self {
         origin.0
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Value the client passed with the authorization request,
+/// echoed back in the ID Token to mitigate replay attacks.
+///
+/// See [OpenID Connect Core 1.0 Section 3.1.2.1](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Nonce(String);
+
+impl Nonce {
+    pub fn new(nonce: impl Into<String>) -> Self {
+        Self(nonce.into())
+    }
+}
+
+impl From<Nonce> for String {
+    fn from(origin: Nonce) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for Nonce {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,65 @@
+use crate::entities::{AccessTokenId, RefreshTokenId};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digest of a bearer token, encoded in **Base64Url** without padding.
+///
+/// Tokens are stored and looked up by this value,
+/// so a leaked database does not expose tokens that can be presented as-is.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenDigest(String);
+
+impl TokenDigest {
+    /// Initializes with an already digested value, such as one read from storage.
+    pub fn new(digest: impl Into<String>) -> Self {
+        Self(digest.into())
+    }
+
+    fn digest(raw: impl AsRef<[u8]>) -> Self {
+        let mut hasher = Sha256::default();
+        hasher.update(raw);
+        Self(BASE64_URL_SAFE_NO_PAD.encode(hasher.finalize()))
+    }
+}
+
+impl From<&AccessTokenId> for TokenDigest {
+    fn from(origin: &AccessTokenId) -> Self {
+        Self::digest(origin.as_ref())
+    }
+}
+
+impl From<&RefreshTokenId> for TokenDigest {
+    fn from(origin: &RefreshTokenId) -> Self {
+        Self::digest(origin.as_ref())
+    }
+}
+
+impl From<TokenDigest> for String {
+    fn from(origin: TokenDigest) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for TokenDigest {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::{AccessTokenId, TokenDigest};
+
+    #[test]
+    fn digest_test() {
+        let id = AccessTokenId::new("abc");
+        let digest = TokenDigest::from(&id);
+        assert_eq!(
+            digest.as_ref(),
+            "ungWv48Bz-pBQUDeXa4iI7ADYaOWF3qctBD_YfIAFa0"
+        );
+        assert_eq!(digest, TokenDigest::from(&AccessTokenId::new("abc")));
+        assert_ne!(digest.as_ref(), id.as_ref());
+    }
+}
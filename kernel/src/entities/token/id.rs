@@ -0,0 +1,71 @@
+use crate::entities::{AccessToken, ClientId, SigningKey, UserId};
+use crate::KernelError;
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+
+use super::claims::{AuthTime, Issuer, Nonce};
+
+/// Signed assertion that the resource owner authenticated to the client.
+///
+/// See [OpenID Connect Core 1.0 Section 2](https://openid.net/specs/openid-connect-core-1_0.html#IDToken)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdToken(String);
+
+impl IdToken {
+    const TYP: &'static str = "JWT";
+    const EXPIRES_IN: Duration = Duration::hours(1);
+
+    /// Issues an ID Token for `account` to `client_id` alongside `access_token`.
+    ///
+    /// The token is only returned from the Token Endpoint together with an access token,
+    /// so `at_hash` is always set. `c_hash` is never set,
+    /// since no response type returns an ID Token from the Authorization Endpoint.
+    pub fn issue(
+        key: &SigningKey,
+        client_id: &ClientId,
+        account: &UserId,
+        auth_time: Option<&AuthTime>,
+        nonce: Option<&Nonce>,
+        access_token: &AccessToken,
+    ) -> Result<Self, KernelError> {
+        let now = OffsetDateTime::now_utc();
+        let claims = IdTokenClaims {
+            iss: Issuer::default().into(),
+            sub: account.to_string(),
+            aud: client_id.id().to_string(),
+            exp: (now + Self::EXPIRES_IN).unix_timestamp(),
+            iat: now.unix_timestamp(),
+            auth_time: auth_time.map(|at| at.as_ref().unix_timestamp()),
+            nonce: nonce.map(AsRef::as_ref),
+            at_hash: key.alg().half_hash(access_token.id().as_ref()),
+        };
+        key.sign(Self::TYP, &claims).map(Self)
+    }
+}
+
+impl From<IdToken> for String {
+    fn from(origin: IdToken) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for IdToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Claims of an ID Token.
+#[derive(Serialize)]
+struct IdTokenClaims<'a> {
+    iss: String,
+    sub: String,
+    aud: String,
+    exp: i64,
+    iat: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<&'a str>,
+    at_hash: String,
+}
@@ -0,0 +1,63 @@
+use crate::entities::{ClientId, SigningKey};
+use crate::KernelError;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use time::{Duration, OffsetDateTime};
+
+use super::claims::Issuer;
+
+/// Authorization response parameters signed as a JWT,
+/// delivered as the single `response` parameter.
+///
+/// See [JARM Section 2](https://openid.net/specs/oauth-v2-jarm.html#name-jwt-based-response-mode)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationResponseJwt(String);
+
+impl AuthorizationResponseJwt {
+    const TYP: &'static str = "JWT";
+    /// The response is consumed right after the redirect, so it is short-lived.
+    const EXPIRES_IN: Duration = Duration::minutes(10);
+
+    /// Signs `params`, either of a successful or an error response, for `client_id`.
+    pub fn issue(
+        key: &SigningKey,
+        client_id: &ClientId,
+        params: &[(String, String)],
+    ) -> Result<Self, KernelError> {
+        let now = OffsetDateTime::now_utc();
+        let claims = AuthorizationResponseClaims {
+            iss: Issuer::default().into(),
+            aud: client_id.id().to_string(),
+            exp: (now + Self::EXPIRES_IN).unix_timestamp(),
+            params: params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect(),
+        };
+        key.sign(Self::TYP, &claims).map(Self)
+    }
+}
+
+impl From<AuthorizationResponseJwt> for String {
+    fn from(origin: AuthorizationResponseJwt) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for AuthorizationResponseJwt {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Claims of a JWT secured authorization response.
+///
+/// See [JARM Section 2.1](https://openid.net/specs/oauth-v2-jarm.html#name-the-jwt-response-document)
+#[derive(Serialize)]
+struct AuthorizationResponseClaims<'a> {
+    iss: String,
+    aud: String,
+    exp: i64,
+    #[serde(flatten)]
+    params: BTreeMap<&'a str, &'a str>,
+}
@@ -0,0 +1,182 @@
+use crate::entities::{
+    AuthorizationDetails, ClientId, Confirmation, LoggedAt, ScopeMethod, TokenDigest, UserId,
+};
+use crate::services::RandomizeService;
+use destructure::Destructure;
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use super::claims::ExpiredIn;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RefreshTokenId(String);
+
+impl RefreshTokenId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<RefreshTokenId> for String {
+    fn from(origin: RefreshTokenId) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for RefreshTokenId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for RefreshTokenId {
+    //noinspection DuplicatedCode
+    fn default() -> Self {
+        RandomizeService::gen_str(128, Self::new)
+    }
+}
+
+/// Identifies the chain of refresh tokens created by rotating a single grant.
+///
+/// When a rotated refresh token is presented again,
+/// every token belonging to the same family is revoked.
+///
+/// See [OAuth 2.0 Security Best Current Practice Section 4.14.2](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-security-topics#section-4.14.2)
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RefreshTokenFamily(Uuid);
+
+impl RefreshTokenFamily {
+    pub fn new(id: impl Into<Uuid>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<RefreshTokenFamily> for Uuid {
+    fn from(origin: RefreshTokenFamily) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<Uuid> for RefreshTokenFamily {
+    fn as_ref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl Default for RefreshTokenFamily {
+    fn default() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Destructure)]
+pub struct RefreshToken {
+    id: RefreshTokenId,
+    date: LoggedAt,
+    family: RefreshTokenFamily,
+    access_token: TokenDigest,
+    client_id: ClientId,
+    account: UserId,
+    scope: Vec<ScopeMethod>,
+    /// Protected resource the grant was made for, kept across rotation.
+    resource: Option<String>,
+    authorization_details: Option<AuthorizationDetails>,
+    /// Key the token is bound to, kept across rotation.
+    ///
+    /// See [RFC9449 Section 5](https://datatracker.ietf.org/doc/html/rfc9449#section-5)
+    #[serde(default)]
+    cnf: Option<Confirmation>,
+    exp: ExpiredIn,
+    rotated: bool,
+}
+
+impl RefreshToken {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: impl Into<String>,
+        created_at: impl Into<OffsetDateTime>,
+        updated_at: impl Into<OffsetDateTime>,
+        family: impl Into<Uuid>,
+        access_token: impl Into<TokenDigest>,
+        linked_client: impl Into<Uuid>,
+        account: impl Into<Uuid>,
+        scoped: impl Into<Vec<ScopeMethod>>,
+        resource: impl Into<Option<String>>,
+        authorization_details: impl Into<Option<AuthorizationDetails>>,
+        expired_in: impl Into<Duration>,
+    ) -> Self {
+        Self {
+            id: RefreshTokenId::new(id),
+            date: LoggedAt::new(created_at, updated_at),
+            family: RefreshTokenFamily::new(family),
+            access_token: access_token.into(),
+            client_id: ClientId::new_at_now(linked_client),
+            account: UserId::new(account),
+            scope: scoped.into(),
+            resource: resource.into(),
+            authorization_details: authorization_details.into(),
+            cnf: None,
+            exp: ExpiredIn::new(expired_in),
+            rotated: false,
+        }
+    }
+
+    /// Binds the token to the key of the client, so that only its holder can refresh with it.
+    pub fn bind(self, cnf: impl Into<Option<Confirmation>>) -> Self {
+        Self {
+            cnf: cnf.into(),
+            ..self
+        }
+    }
+
+    pub fn id(&self) -> &RefreshTokenId {
+        &self.id
+    }
+
+    pub fn date(&self) -> &LoggedAt {
+        &self.date
+    }
+
+    pub fn family(&self) -> &RefreshTokenFamily {
+        &self.family
+    }
+
+    /// Digest of the access token issued together with this refresh token.
+    pub fn access_token(&self) -> &TokenDigest {
+        &self.access_token
+    }
+
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    pub fn account(&self) -> &UserId {
+        &self.account
+    }
+
+    pub fn scope(&self) -> &Vec<ScopeMethod> {
+        &self.scope
+    }
+
+    pub fn resource(&self) -> Option<&str> {
+        self.resource.as_deref()
+    }
+
+    pub fn authorization_details(&self) -> &Option<AuthorizationDetails> {
+        &self.authorization_details
+    }
+
+    pub fn confirmation(&self) -> &Option<Confirmation> {
+        &self.cnf
+    }
+
+    pub fn expired_in(&self) -> &ExpiredIn {
+        &self.exp
+    }
+
+    /// Returns `true` if this token has already been exchanged for a new one.
+    pub fn is_rotated(&self) -> bool {
+        self.rotated
+    }
+}
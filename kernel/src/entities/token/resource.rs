@@ -0,0 +1,92 @@
+use crate::entities::{AuthorizationDetailsType, ScopeMethod};
+use crate::KernelError;
+use url::Url;
+
+/// Protected resource that access tokens can be issued for,
+/// named by the `resource` parameter of authorization and token requests.
+///
+/// The `resource` URI becomes the `aud` of the access token,
+/// and only `scopes` are granted for it.
+/// `authorization_details_types` are the types of authorization details the resource accepts.
+///
+/// See [RFC8707 Section 2](https://datatracker.ietf.org/doc/html/rfc8707#section-2)
+/// and [RFC9396 Section 5](https://datatracker.ietf.org/doc/html/rfc9396#section-5)
+#[derive(Debug, Clone)]
+pub struct ProtectedResource {
+    resource: String,
+    scopes: Vec<ScopeMethod>,
+    authorization_details_types: Vec<AuthorizationDetailsType>,
+}
+
+impl ProtectedResource {
+    pub fn new(
+        resource: impl Into<String>,
+        scopes: impl Into<Vec<ScopeMethod>>,
+        authorization_details_types: impl Into<Vec<AuthorizationDetailsType>>,
+    ) -> Result<Self, KernelError> {
+        let resource = resource.into();
+        // The value must be an absolute URI without a fragment component.
+        let url = Url::parse(&resource).map_err(|e| KernelError::InvalidValue {
+            method: "protected resource",
+            value: format!("`{}` is not an absolute uri: {}", resource, e),
+        })?;
+        if url.fragment().is_some() {
+            return Err(KernelError::InvalidValue {
+                method: "protected resource",
+                value: format!("`{}` must not include a fragment.", resource),
+            });
+        }
+        Ok(Self {
+            resource,
+            scopes: scopes.into(),
+            authorization_details_types: authorization_details_types.into(),
+        })
+    }
+
+    pub fn resource(&self) -> &str {
+        &self.resource
+    }
+
+    pub fn scopes(&self) -> &[ScopeMethod] {
+        &self.scopes
+    }
+
+    pub fn authorization_details_types(&self) -> &[AuthorizationDetailsType] {
+        &self.authorization_details_types
+    }
+
+    /// Keeps only the requested scopes this resource allows.
+    pub fn narrow(&self, scope: &[ScopeMethod]) -> Vec<ScopeMethod> {
+        scope
+            .iter()
+            .filter(|method| self.scopes.contains(method))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProtectedResource;
+    use crate::entities::ScopeMethod;
+
+    #[test]
+    fn narrow() -> anyhow::Result<()> {
+        let resource = ProtectedResource::new(
+            "https://api.example.com/",
+            vec![ScopeMethod::new("read"), ScopeMethod::new("write")],
+            vec![],
+        )?;
+        let narrowed = resource.narrow(&[ScopeMethod::new("openid"), ScopeMethod::new("read")]);
+        assert_eq!(narrowed, vec![ScopeMethod::new("read")]);
+        Ok(())
+    }
+
+    #[test]
+    fn reject() {
+        assert!(ProtectedResource::new("api.example.com", vec![], vec![]).is_err());
+        assert!(
+            ProtectedResource::new("https://api.example.com/#section", vec![], vec![]).is_err()
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::entities::ResponseType;
+use crate::KernelError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How the authorization response is delivered to the redirect uri.
+///
+/// The `*.jwt` modes deliver the parameters as a single signed `response` parameter,
+/// and `jwt` stands for the default mode of the response type.
+///
+/// See [OAuth 2.0 Multiple Response Type Encoding Practices Section 2.1](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html#ResponseModes),
+/// [OAuth 2.0 Form Post Response Mode Section 2](https://openid.net/specs/oauth-v2-form-post-response-mode-1_0.html#FormPostResponseMode)
+/// and [JARM Section 2.3](https://openid.net/specs/oauth-v2-jarm.html#name-response-mode-jwt)
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq, Deserialize, Serialize)]
+pub enum ResponseMode {
+    #[default]
+    Query,
+    Fragment,
+    FormPost,
+    QueryJwt,
+    FragmentJwt,
+    FormPostJwt,
+    Jwt,
+}
+
+impl ResponseMode {
+    /// Response modes the authorization endpoint accepts.
+    ///
+    /// Published as `response_modes_supported` in the server metadata.
+    pub const SUPPORTED: [ResponseMode; 7] = [
+        ResponseMode::Query,
+        ResponseMode::Fragment,
+        ResponseMode::FormPost,
+        ResponseMode::QueryJwt,
+        ResponseMode::FragmentJwt,
+        ResponseMode::FormPostJwt,
+        ResponseMode::Jwt,
+    ];
+
+    /// The mode used when `response_mode` is not given.
+    pub fn default_for(response_type: &ResponseType) -> Self {
+        match response_type {
+            ResponseType::Code => Self::Query,
+            ResponseType::Token => Self::Fragment,
+        }
+    }
+
+    /// Replaces `jwt` with the concrete mode for the response type.
+    pub fn resolve(self, response_type: &ResponseType) -> Self {
+        match (self, Self::default_for(response_type)) {
+            (Self::Jwt, Self::Fragment) => Self::FragmentJwt,
+            (Self::Jwt, _) => Self::QueryJwt,
+            (mode, _) => mode,
+        }
+    }
+
+    /// Returns `true` if the parameters are delivered as a signed JWT.
+    pub fn is_jwt(&self) -> bool {
+        matches!(
+            self,
+            Self::QueryJwt | Self::FragmentJwt | Self::FormPostJwt | Self::Jwt
+        )
+    }
+
+    /// The mode the (possibly signed) parameters are delivered with.
+    pub fn delivery(&self) -> Self {
+        match self {
+            Self::QueryJwt | Self::Jwt => Self::Query,
+            Self::FragmentJwt => Self::Fragment,
+            Self::FormPostJwt => Self::FormPost,
+            mode => *mode,
+        }
+    }
+}
+
+impl FromStr for ResponseMode {
+    type Err = KernelError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "query" => Self::Query,
+            "fragment" => Self::Fragment,
+            "form_post" => Self::FormPost,
+            "query.jwt" => Self::QueryJwt,
+            "fragment.jwt" => Self::FragmentJwt,
+            "form_post.jwt" => Self::FormPostJwt,
+            "jwt" => Self::Jwt,
+            _ => {
+                return Err(KernelError::InvalidValue {
+                    method: "from_str",
+                    value: s.to_string(),
+                })
+            }
+        })
+    }
+}
+
+impl AsRef<str> for ResponseMode {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Query => "query",
+            Self::Fragment => "fragment",
+            Self::FormPost => "form_post",
+            Self::QueryJwt => "query.jwt",
+            Self::FragmentJwt => "fragment.jwt",
+            Self::FormPostJwt => "form_post.jwt",
+            Self::Jwt => "jwt",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseMode;
+    use crate::entities::ResponseType;
+    use std::str::FromStr;
+
+    #[test]
+    fn resolve() -> anyhow::Result<()> {
+        let mode = ResponseMode::from_str("jwt")?.resolve(&ResponseType::Code);
+        assert_eq!(mode, ResponseMode::QueryJwt);
+        assert!(mode.is_jwt());
+        assert_eq!(mode.delivery(), ResponseMode::Query);
+
+        let mode = ResponseMode::from_str("form_post")?.resolve(&ResponseType::Code);
+        assert!(!mode.is_jwt());
+        assert_eq!(mode.delivery(), ResponseMode::FormPost);
+
+        assert!(ResponseMode::from_str("web_message").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,538 @@
+use crate::KernelError;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Namespace bound to the `xml` prefix without being declared.
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Deepest nesting accepted, which keeps the recursive parser off the end of the stack.
+const MAX_DEPTH: usize = 64;
+
+/// Element of a parsed document, with the namespaces resolved.
+///
+/// Only what a SAML assertion needs is supported.
+/// Documents with a DTD, or processing instructions inside the root element, are rejected.
+#[derive(Debug)]
+pub(super) struct Element {
+    prefix: Option<String>,
+    local: String,
+    namespace: Option<String>,
+    attributes: Vec<Attribute>,
+    /// Namespaces in scope, keyed by the prefix and the default namespace by `""`.
+    scope: BTreeMap<String, String>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+struct Attribute {
+    prefix: Option<String>,
+    local: String,
+    namespace: Option<String>,
+    value: String,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+fn invalid(value: impl Into<String>) -> KernelError {
+    KernelError::InvalidValue {
+        method: "parse saml assertion",
+        value: value.into(),
+    }
+}
+
+impl Element {
+    pub(super) fn parse(document: &str) -> Result<Self, KernelError> {
+        // https://www.w3.org/TR/xml/#sec-line-ends
+        let document = document.replace("\r\n", "\n").replace('\r', "\n");
+        let mut parser = Parser {
+            src: document.trim_start_matches('\u{feff}'),
+            pos: 0,
+        };
+
+        parser.skip_misc(true)?;
+        let mut scope = BTreeMap::new();
+        scope.insert("xml".to_string(), XML_NAMESPACE.to_string());
+        let root = parser.element(&scope, 0)?;
+        parser.skip_misc(false)?;
+        if parser.pos < parser.src.len() {
+            return Err(invalid("content after the root element."));
+        }
+        Ok(root)
+    }
+
+    pub(super) fn is(&self, namespace: &str, local: &str) -> bool {
+        self.namespace.as_deref() == Some(namespace) && self.local == local
+    }
+
+    /// Attribute without a namespace.
+    pub(super) fn attribute(&self, local: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attr| attr.namespace.is_none() && attr.local == local)
+            .map(|attr| attr.value.as_str())
+    }
+
+    pub(super) fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub(super) fn children<'a>(
+        &'a self,
+        namespace: &'a str,
+        local: &'a str,
+    ) -> impl Iterator<Item = &'a Element> {
+        self.elements()
+            .filter(move |element| element.is(namespace, local))
+    }
+
+    pub(super) fn child(&self, namespace: &str, local: &str) -> Option<&Element> {
+        self.elements().find(|element| element.is(namespace, local))
+    }
+
+    /// Text directly inside the element, with surrounding whitespace removed.
+    pub(super) fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    /// Exclusive Canonical XML, without comments, of the element leaving out `exclude`.
+    ///
+    /// Prefixes in `inclusive` are rendered as Canonical XML does.
+    ///
+    /// See [Exclusive XML Canonicalization](https://www.w3.org/TR/xml-exc-c14n/)
+    pub(super) fn canonicalize(&self, inclusive: &[&str], exclude: Option<&Element>) -> String {
+        let mut out = String::new();
+        self.write_canonical(&BTreeMap::new(), inclusive, exclude, &mut out);
+        out
+    }
+
+    fn write_canonical(
+        &self,
+        rendered: &BTreeMap<String, String>,
+        inclusive: &[&str],
+        exclude: Option<&Element>,
+        out: &mut String,
+    ) {
+        // https://www.w3.org/TR/xml-exc-c14n/#def-visibly-utilizes
+        let mut utilized = BTreeSet::new();
+        utilized.insert(self.prefix.as_deref().unwrap_or(""));
+        for attr in &self.attributes {
+            if let Some(prefix) = &attr.prefix {
+                utilized.insert(prefix.as_str());
+            }
+        }
+        for prefix in inclusive {
+            let prefix = if *prefix == "#default" { "" } else { prefix };
+            if self.scope.contains_key(prefix) {
+                utilized.insert(prefix);
+            }
+        }
+        utilized.remove("xml");
+
+        let mut in_effect = rendered.clone();
+        out.push('<');
+        out.push_str(&qname(&self.prefix, &self.local));
+        for prefix in utilized {
+            let namespace = self.scope.get(prefix).map(String::as_str).unwrap_or("");
+            if rendered.get(prefix).map(String::as_str).unwrap_or("") == namespace {
+                continue;
+            }
+            if prefix.is_empty() {
+                out.push_str(" xmlns=\"");
+            } else {
+                out.push_str(" xmlns:");
+                out.push_str(prefix);
+                out.push_str("=\"");
+            }
+            escape_attribute(namespace, out);
+            out.push('"');
+            in_effect.insert(prefix.to_string(), namespace.to_string());
+        }
+
+        let mut attributes = self.attributes.iter().collect::<Vec<_>>();
+        attributes.sort_by(|a, b| {
+            (a.namespace.as_deref().unwrap_or(""), &a.local)
+                .cmp(&(b.namespace.as_deref().unwrap_or(""), &b.local))
+        });
+        for attr in attributes {
+            out.push(' ');
+            out.push_str(&qname(&attr.prefix, &attr.local));
+            out.push_str("=\"");
+            escape_attribute(&attr.value, out);
+            out.push('"');
+        }
+        out.push('>');
+
+        for child in &self.children {
+            match child {
+                Node::Text(text) => escape_text(text, out),
+                Node::Element(element) => {
+                    if exclude.is_some_and(|exclude| std::ptr::eq(exclude, element)) {
+                        continue;
+                    }
+                    element.write_canonical(&in_effect, inclusive, exclude, out)
+                }
+            }
+        }
+
+        out.push_str("</");
+        out.push_str(&qname(&self.prefix, &self.local));
+        out.push('>');
+    }
+}
+
+fn qname(prefix: &Option<String>, local: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{}:{}", prefix, local),
+        None => local.to_string(),
+    }
+}
+
+fn escape_text(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\r' => out.push_str("&#xD;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn escape_attribute(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' => out.push_str("&#x9;"),
+            '\n' => out.push_str("&#xA;"),
+            '\r' => out.push_str("&#xD;"),
+            c => out.push(c),
+        }
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n']).len();
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), KernelError> {
+        if !self.rest().starts_with(token) {
+            return Err(invalid(format!("`{}` is expected.", token)));
+        }
+        self.pos += token.len();
+        Ok(())
+    }
+
+    /// Moves past `end` and returns what was before it.
+    fn until(&mut self, end: &str) -> Result<&'a str, KernelError> {
+        let rest = self.rest();
+        let Some(len) = rest.find(end) else {
+            return Err(invalid(format!("`{}` is missing.", end)));
+        };
+        self.pos += len + end.len();
+        Ok(&rest[..len])
+    }
+
+    /// Skips whitespace, comments and processing instructions outside the root element.
+    fn skip_misc(&mut self, prolog: bool) -> Result<(), KernelError> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.until("-->")?;
+            } else if prolog && self.rest().starts_with("<!") {
+                return Err(invalid("document type declaration is not allowed."));
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<(Option<String>, String), KernelError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '<'))
+            .unwrap_or(rest.len());
+        let name = &rest[..len];
+        self.pos += len;
+        match name.split_once(':') {
+            _ if name.is_empty() => Err(invalid("name is expected.")),
+            Some((prefix, local)) if !prefix.is_empty() && !local.is_empty() => {
+                Ok((Some(prefix.to_string()), local.to_string()))
+            }
+            Some(_) => Err(invalid(format!("`{}` is not a qualified name.", name))),
+            None => Ok((None, name.to_string())),
+        }
+    }
+
+    fn element(
+        &mut self,
+        parent: &BTreeMap<String, String>,
+        depth: usize,
+    ) -> Result<Element, KernelError> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("elements are nested too deeply."));
+        }
+
+        self.expect("<")?;
+        let (prefix, local) = self.name()?;
+
+        let mut scope = parent.clone();
+        let mut raw = Vec::<(Option<String>, String, String)>::new();
+        let empty = loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                break true;
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break false;
+            }
+
+            let (attr_prefix, attr_local) = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(invalid("attribute value must be quoted.")),
+            };
+            self.pos += 1;
+            let value = self.until(&quote.to_string())?;
+            if value.contains('<') {
+                return Err(invalid("`<` is not allowed in attribute values."));
+            }
+            // https://www.w3.org/TR/xml/#AVNormalize
+            let value = unescape(&value.replace(['\t', '\n'], " "))?;
+
+            match (attr_prefix.as_deref(), attr_local.as_str()) {
+                (None, "xmlns") => {
+                    if value.is_empty() {
+                        scope.remove("");
+                    } else {
+                        scope.insert(String::new(), value);
+                    }
+                }
+                (Some("xmlns"), declared) => {
+                    if value.is_empty() || declared == "xml" || declared == "xmlns" {
+                        return Err(invalid(format!("`xmlns:{}` is not allowed.", declared)));
+                    }
+                    scope.insert(declared.to_string(), value);
+                }
+                _ => {
+                    if raw
+                        .iter()
+                        .any(|(p, l, _)| p == &attr_prefix && l == &attr_local)
+                    {
+                        return Err(invalid(format!("`{}` is duplicated.", attr_local)));
+                    }
+                    raw.push((attr_prefix, attr_local, value))
+                }
+            }
+        };
+
+        let namespace = match &prefix {
+            Some(prefix) => Some(
+                scope
+                    .get(prefix)
+                    .cloned()
+                    .ok_or_else(|| invalid(format!("`{}` is not declared.", prefix)))?,
+            ),
+            None => scope.get("").cloned(),
+        };
+
+        let mut attributes = Vec::<Attribute>::with_capacity(raw.len());
+        for (attr_prefix, attr_local, value) in raw {
+            let attr_namespace = attr_prefix
+                .as_ref()
+                .map(|p| {
+                    scope
+                        .get(p)
+                        .cloned()
+                        .ok_or_else(|| invalid(format!("`{}` is not declared.", p)))
+                })
+                .transpose()?;
+            if attributes
+                .iter()
+                .any(|attr| attr.namespace == attr_namespace && attr.local == attr_local)
+            {
+                return Err(invalid(format!("`{}` is duplicated.", attr_local)));
+            }
+            attributes.push(Attribute {
+                prefix: attr_prefix,
+                local: attr_local,
+                namespace: attr_namespace,
+                value,
+            });
+        }
+
+        let mut element = Element {
+            prefix,
+            local,
+            namespace,
+            attributes,
+            scope,
+            children: Vec::new(),
+        };
+        if empty {
+            return Ok(element);
+        }
+
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(invalid(format!("`{}` is not closed.", element.local)));
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != (element.prefix.clone(), element.local.clone()) {
+                    return Err(invalid(format!("`{}` is not closed.", element.local)));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if rest.starts_with("<!--") {
+                self.until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let text = self.until("]]>")?.to_string();
+                element.push_text(text);
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                return Err(invalid("only elements, text and comments are allowed."));
+            } else if rest.starts_with('<') {
+                let child = self.element(&element.scope, depth + 1)?;
+                element.children.push(Node::Element(child));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                self.pos += len;
+                element.push_text(unescape(&rest[..len])?);
+            }
+        }
+    }
+}
+
+impl Element {
+    fn push_text(&mut self, text: String) {
+        match self.children.last_mut() {
+            Some(Node::Text(last)) => last.push_str(&text),
+            _ => self.children.push(Node::Text(text)),
+        }
+    }
+}
+
+/// Replaces the predefined entities and character references.
+fn unescape(value: &str) -> Result<String, KernelError> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find(';') else {
+            return Err(invalid("entity reference is not terminated."));
+        };
+        let entity = &rest[start + 1..start + len];
+        let c = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                .and_then(Result::ok)
+                .and_then(char::from_u32)
+                .ok_or_else(|| invalid(format!("`&{};` is not supported.", entity)))?,
+        };
+        out.push(c);
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Element;
+
+    #[test]
+    fn canonicalize() -> anyhow::Result<()> {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- before -->
+<a:Root xmlns:a="urn:a" xmlns:b="urn:b" xmlns:unused="urn:unused" z='1' a="&quot;2&quot;&#x9;">
+  <b:Child xmlns="urn:default" b:attr="x" attr="&lt;y&gt;"><!-- comment -->text &amp; <![CDATA[<cdata>]]></b:Child>
+  <Plain xml:lang="en"/>
+  <a:Excluded><b:Inner/></a:Excluded>
+</a:Root>
+"#;
+        let root = Element::parse(document)?;
+        let excluded = root.child("urn:a", "Excluded").unwrap();
+
+        // xmllint --exc-c14n
+        assert_eq!(
+            root.canonicalize(&[], None),
+            r#"<a:Root xmlns:a="urn:a" a="&quot;2&quot;&#x9;" z="1">
+  <b:Child xmlns:b="urn:b" attr="&lt;y>" b:attr="x">text &amp; &lt;cdata&gt;</b:Child>
+  <Plain xml:lang="en"></Plain>
+  <a:Excluded><b:Inner xmlns:b="urn:b"></b:Inner></a:Excluded>
+</a:Root>"#
+        );
+        assert_eq!(
+            root.canonicalize(&["b"], Some(excluded)),
+            concat!(
+                r#"<a:Root xmlns:a="urn:a" xmlns:b="urn:b" a="&quot;2&quot;&#x9;" z="1">"#,
+                "\n  ",
+                r#"<b:Child attr="&lt;y>" b:attr="x">text &amp; &lt;cdata&gt;</b:Child>"#,
+                "\n  ",
+                r#"<Plain xml:lang="en"></Plain>"#,
+                "\n  \n</a:Root>"
+            )
+        );
+
+        let child = root.child("urn:b", "Child").unwrap();
+        assert_eq!(child.attribute("attr"), Some("<y>"));
+        assert_eq!(child.text(), "text & <cdata>");
+        Ok(())
+    }
+
+    #[test]
+    fn reject() {
+        assert!(Element::parse(r#"<!DOCTYPE a [<!ENTITY e "e">]><a>&e;</a>"#).is_err());
+        assert!(Element::parse(r#"<a><?pi?></a>"#).is_err());
+        assert!(Element::parse(r#"<a x="1" x="2"/>"#).is_err());
+        assert!(Element::parse(r#"<a xmlns:p="urn:p" xmlns:q="urn:p" p:x="1" q:x="2"/>"#).is_err());
+        assert!(Element::parse(r#"<p:a/>"#).is_err());
+        assert!(Element::parse(r#"<a></b>"#).is_err());
+        assert!(Element::parse(r#"<a/><b/>"#).is_err());
+        assert!(Element::parse(&"<a>".repeat(100)).is_err());
+    }
+}
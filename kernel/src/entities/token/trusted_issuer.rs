@@ -0,0 +1,90 @@
+use crate::entities::{ClientCertificate, ClientJwkSet};
+use crate::KernelError;
+use uuid::Uuid;
+
+/// External issuer whose assertions are accepted as authorization grants.
+///
+/// JWT assertions are verified with `jwks`, or the document published at `jwks_uri`,
+/// and SAML assertions with the public key of `certificate`.
+/// Only `clients` may present its assertions, and only `subjects` may be asserted,
+/// or any subject if `subjects` contains [`TrustedIssuer::ANY_SUBJECT`].
+/// Nothing is allowed if either is empty.
+/// The audience of the assertion must contain one of `audiences`,
+/// which the grant defaults to the token endpoint if none is configured.
+///
+/// See [RFC7521 Section 5.2](https://datatracker.ietf.org/doc/html/rfc7521#section-5.2)
+#[derive(Debug, Clone)]
+pub struct TrustedIssuer {
+    issuer: String,
+    jwks: Option<ClientJwkSet>,
+    jwks_uri: Option<String>,
+    certificate: Option<ClientCertificate>,
+    clients: Vec<Uuid>,
+    subjects: Vec<String>,
+    audiences: Vec<String>,
+}
+
+impl TrustedIssuer {
+    /// Wildcard of `subjects` allowing the issuer to assert any subject.
+    pub const ANY_SUBJECT: &'static str = "*";
+
+    pub fn new(
+        issuer: impl Into<String>,
+        jwks: Option<ClientJwkSet>,
+        jwks_uri: Option<String>,
+        certificate: Option<ClientCertificate>,
+        clients: impl Into<Vec<Uuid>>,
+        subjects: impl Into<Vec<String>>,
+        audiences: impl Into<Vec<String>>,
+    ) -> Result<Self, KernelError> {
+        let issuer = issuer.into();
+        if jwks.is_none() && jwks_uri.is_none() && certificate.is_none() {
+            return Err(KernelError::InvalidValue {
+                method: "trusted issuer",
+                value: format!(
+                    "`{}` has none of `jwks`, `jwks_uri` or `certificate`.",
+                    issuer
+                ),
+            });
+        }
+        Ok(Self {
+            issuer,
+            jwks,
+            jwks_uri,
+            certificate,
+            clients: clients.into(),
+            subjects: subjects.into(),
+            audiences: audiences.into(),
+        })
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn jwks(&self) -> &Option<ClientJwkSet> {
+        &self.jwks
+    }
+
+    pub fn jwks_uri(&self) -> &Option<String> {
+        &self.jwks_uri
+    }
+
+    pub fn certificate(&self) -> &Option<ClientCertificate> {
+        &self.certificate
+    }
+
+    pub fn audiences(&self) -> &[String] {
+        &self.audiences
+    }
+
+    pub fn allows_client(&self, client_id: &Uuid) -> bool {
+        self.clients.contains(client_id)
+    }
+
+    pub fn allows_subject(&self, sub: &str) -> bool {
+        self.subjects
+            .iter()
+            .any(|allowed| allowed == Self::ANY_SUBJECT || allowed == sub)
+    }
+}
@@ -2,10 +2,13 @@
 //! that define volatiles and temporary data,
 //! intended to be handled in an in-memory database such as Redis.
 
+mod device;
+mod dpop;
 mod mfa_code;
+mod par;
 mod pkce;
 mod session;
 mod state;
 mod ticket;
 
-pub use self::{mfa_code::*, pkce::*, session::*, state::*, ticket::*};
+pub use self::{device::*, dpop::*, mfa_code::*, par::*, pkce::*, session::*, state::*, ticket::*};
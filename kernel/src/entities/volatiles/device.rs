@@ -0,0 +1,232 @@
+use crate::entities::{AuthorizeTokenId, ClientId, ExpiredIn, RedirectUri, ScopeMethod, TicketId};
+use crate::services::RandomizeService;
+use destructure::Destructure;
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+/// Code the device polls the token endpoint with.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DeviceCode(String);
+
+impl DeviceCode {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self(code.into())
+    }
+}
+
+impl From<DeviceCode> for String {
+    fn from(origin: DeviceCode) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for DeviceCode {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for DeviceCode {
+    fn default() -> Self {
+        RandomizeService::gen_str(64, Self::new)
+    }
+}
+
+/// Code the user enters at the verification uri on another device.
+///
+/// It is made of 8 consonants, which is easy to type and does not spell words.
+/// Input is case-insensitive and characters outside the charset, like the `-` in `WDJB-MJHT`, are ignored.
+///
+/// See [RFC8628 Section 6.1](https://datatracker.ietf.org/doc/html/rfc8628#section-6.1)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UserCode(String);
+
+impl UserCode {
+    const CHARSET: &'static [u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+    const LENGTH: usize = 8;
+
+    pub fn new(code: impl Into<String>) -> Self {
+        let code = code
+            .into()
+            .to_ascii_uppercase()
+            .chars()
+            .filter(|c| c.is_ascii() && Self::CHARSET.contains(&(*c as u8)))
+            .collect();
+        Self(code)
+    }
+
+    /// The code split in half with `-` for display, such as `WDJB-MJHT`.
+    pub fn formatted(&self) -> String {
+        let (head, tail) = self.0.split_at(self.0.len() / 2);
+        format!("{}-{}", head, tail)
+    }
+}
+
+impl From<UserCode> for String {
+    fn from(origin: UserCode) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for UserCode {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for UserCode {
+    fn default() -> Self {
+        RandomizeService::gen_str_from(Self::CHARSET, Self::LENGTH, Self::new)
+    }
+}
+
+/// Device Authorization Request awaiting the user.
+///
+/// Once the user enters the [UserCode], the request goes through the same decision
+/// as the authorization endpoint, tracked by `ticket` and `authorize_token`.
+///
+/// See [RFC8628 Section 3](https://datatracker.ietf.org/doc/html/rfc8628#section-3)
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, Destructure)]
+pub struct DeviceAuthorization {
+    device_code: DeviceCode,
+    user_code: UserCode,
+    client_id: ClientId,
+    scopes: Vec<ScopeMethod>,
+    verification_uri: RedirectUri,
+    ticket: Option<TicketId>,
+    authorize_token: Option<AuthorizeTokenId>,
+    interval: i64,
+    polled_at: Option<OffsetDateTime>,
+    expired_in: ExpiredIn,
+}
+
+impl DeviceAuthorization {
+    /// Polling interval the device starts with, in seconds.
+    pub const INTERVAL: i64 = 5;
+
+    pub fn new(
+        client_id: ClientId,
+        scopes: impl Into<Vec<ScopeMethod>>,
+        verification_uri: impl Into<String>,
+        expired_in: impl Into<Duration>,
+    ) -> Self {
+        Self {
+            device_code: DeviceCode::default(),
+            user_code: UserCode::default(),
+            client_id,
+            scopes: scopes.into(),
+            verification_uri: RedirectUri::new(verification_uri),
+            ticket: None,
+            authorize_token: None,
+            interval: Self::INTERVAL,
+            polled_at: None,
+            expired_in: ExpiredIn::new(expired_in),
+        }
+    }
+
+    pub fn device_code(&self) -> &DeviceCode {
+        &self.device_code
+    }
+
+    pub fn user_code(&self) -> &UserCode {
+        &self.user_code
+    }
+
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    pub fn scopes(&self) -> &Vec<ScopeMethod> {
+        &self.scopes
+    }
+
+    /// Where the user enters the [UserCode].
+    ///
+    /// It is also where the user is sent back to after the decision.
+    pub fn verification_uri(&self) -> &RedirectUri {
+        &self.verification_uri
+    }
+
+    /// Ticket of the pending decision, set once the user has entered the [UserCode].
+    pub fn ticket(&self) -> &Option<TicketId> {
+        &self.ticket
+    }
+
+    /// Token that is stored once the user accepts the decision.
+    pub fn authorize_token(&self) -> &Option<AuthorizeTokenId> {
+        &self.authorize_token
+    }
+
+    /// Minimum seconds the device must wait between polls.
+    pub fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    pub fn expired_in(&self) -> &ExpiredIn {
+        &self.expired_in
+    }
+
+    /// Returns `true` if the user has already entered the [UserCode].
+    pub fn is_verified(&self) -> bool {
+        self.ticket.is_some()
+    }
+
+    pub fn verify(mut self, ticket: TicketId, authorize_token: AuthorizeTokenId) -> Self {
+        self.ticket = Some(ticket);
+        self.authorize_token = Some(authorize_token);
+        self
+    }
+
+    /// Records a poll from the device at `now`.
+    ///
+    /// Returns `true` as the second value if the device polled sooner than the interval.
+    /// In that case the interval is increased by 5 seconds for all subsequent polls.
+    ///
+    /// See [RFC8628 Section 3.5](https://datatracker.ietf.org/doc/html/rfc8628#section-3.5)
+    pub fn poll(mut self, now: OffsetDateTime) -> (Self, bool) {
+        let too_fast =
+            matches!(self.polled_at, Some(at) if now < at + Duration::seconds(self.interval));
+        if too_fast {
+            self.interval += Self::INTERVAL;
+        }
+        self.polled_at = Some(now);
+        (self, too_fast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceAuthorization, UserCode};
+    use crate::entities::ClientId;
+    use time::{Duration, OffsetDateTime};
+    use uuid::Uuid;
+
+    #[test]
+    fn user_code() {
+        let code = UserCode::default();
+        assert_eq!(code.as_ref().len(), 8);
+        assert_eq!(UserCode::new(code.formatted().to_lowercase()), code);
+        assert_eq!(UserCode::new("wdjb-mjht").formatted(), "WDJB-MJHT");
+    }
+
+    #[test]
+    fn poll() {
+        let now = OffsetDateTime::now_utc();
+        let device = DeviceAuthorization::new(
+            ClientId::new_at_now(Uuid::new_v4()),
+            vec![],
+            "https://stellar.example.com/device",
+            Duration::minutes(10),
+        );
+
+        let (device, too_fast) = device.poll(now);
+        assert!(!too_fast);
+
+        let (device, too_fast) = device.poll(now + Duration::seconds(1));
+        assert!(too_fast);
+        assert_eq!(device.interval(), DeviceAuthorization::INTERVAL * 2);
+
+        let (_, too_fast) = device.poll(now + Duration::seconds(12));
+        assert!(!too_fast);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::services::RandomizeService;
+use crate::DPOP_NONCE_REQUIRED;
+use serde::{Deserialize, Serialize};
+
+/// Value issued by this server that DPoP proofs have to carry,
+/// to limit how long a pre-generated proof can be used.
+///
+/// See [RFC9449 Section 8](https://datatracker.ietf.org/doc/html/rfc9449#section-8)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DPoPNonce(String);
+
+impl DPoPNonce {
+    /// Seconds for which an issued nonce is accepted.
+    pub const EXPIRES_IN: i64 = 300;
+
+    pub fn new(nonce: impl Into<String>) -> Self {
+        Self(nonce.into())
+    }
+
+    /// Whether proofs must carry a nonce, which is set by `DPOP_NONCE_REQUIRED`.
+    pub fn required() -> bool {
+        *DPOP_NONCE_REQUIRED
+    }
+}
+
+impl Default for DPoPNonce {
+    fn default() -> Self {
+        RandomizeService::gen_str(32, Self::new)
+    }
+}
+
+impl From<DPoPNonce> for String {
+    fn from(origin: DPoPNonce) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for DPoPNonce {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
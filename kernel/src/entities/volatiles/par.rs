@@ -0,0 +1,90 @@
+use crate::entities::{AuthorizeToken, CodeChallenge, ExpiredIn, State};
+use crate::services::RandomizeService;
+use destructure::Destructure;
+use serde::{Deserialize, Serialize};
+use time::Duration;
+
+/// Reference to a pushed authorization request,
+/// passed to the authorization endpoint in place of the request parameters.
+///
+/// See [RFC9126 Section 2.2](https://datatracker.ietf.org/doc/html/rfc9126#section-2.2)
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RequestUri(String);
+
+impl RequestUri {
+    pub const PREFIX: &'static str = "urn:ietf:params:oauth:request_uri:";
+
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self(uri.into())
+    }
+}
+
+impl From<RequestUri> for String {
+    fn from(origin: RequestUri) -> Self {
+        origin.0
+    }
+}
+
+impl AsRef<str> for RequestUri {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for RequestUri {
+    fn default() -> Self {
+        RandomizeService::gen_str(32, |reference| {
+            Self::new(format!("{}{}", Self::PREFIX, reference))
+        })
+    }
+}
+
+/// Authorization request that the client pushed and was already validated,
+/// waiting for the user agent to reach the authorization endpoint.
+///
+/// It is used only once, by the client that pushed it.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, Destructure)]
+pub struct PushedAuthorizationRequest {
+    request_uri: RequestUri,
+    token: AuthorizeToken,
+    code_challenge: CodeChallenge,
+    state: State,
+    expired_in: ExpiredIn,
+}
+
+impl PushedAuthorizationRequest {
+    /// Lifetime of a `request_uri` in seconds.
+    ///
+    /// It only has to cover the redirect of the user agent, so it is kept short.
+    pub const EXPIRES_IN: i64 = 60;
+
+    pub fn new(token: AuthorizeToken, code_challenge: CodeChallenge, state: State) -> Self {
+        Self {
+            request_uri: RequestUri::default(),
+            token,
+            code_challenge,
+            state,
+            expired_in: ExpiredIn::new(Duration::seconds(Self::EXPIRES_IN)),
+        }
+    }
+
+    pub fn request_uri(&self) -> &RequestUri {
+        &self.request_uri
+    }
+
+    pub fn token(&self) -> &AuthorizeToken {
+        &self.token
+    }
+
+    pub fn code_challenge(&self) -> &CodeChallenge {
+        &self.code_challenge
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn expired_in(&self) -> &ExpiredIn {
+        &self.expired_in
+    }
+}
@@ -7,6 +7,11 @@ use sha2::{Digest, Sha256};
 pub struct CodeChallenge(Vec<u8>);
 
 impl CodeChallenge {
+    /// `code_challenge_method`s accepted by the authorization endpoint.
+    ///
+    /// `plain` is not accepted, as it offers no protection once the challenge leaks.
+    pub const SUPPORTED_METHODS: [&'static str; 1] = ["S256"];
+
     /// Decodes and initializes the given string in **Base64Url** format.
     ///
     /// - Note that the value inside is `Vec<u8>`.
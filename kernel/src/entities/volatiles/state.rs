@@ -1,3 +1,4 @@
+use crate::services::RandomizeService;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -21,6 +22,13 @@ impl From<State> for String {
     }
 }
 
+impl Default for State {
+    /// State for requests this server starts by itself, such as the device decision.
+    fn default() -> Self {
+        RandomizeService::gen_str(32, Self::new)
+    }
+}
+
 impl AsRef<str> for State {
     fn as_ref(&self) -> &str {
         &self.0
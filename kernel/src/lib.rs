@@ -38,6 +38,16 @@ pub(crate) static BASE_URL: Lazy<Url> = Lazy::new(|| {
         .expect("`BASE_URL` does not set! This value require.")
 });
 
+/// Whether DPoP proofs must carry a nonce issued by this server. Defaults to `false`.
+pub(crate) static DPOP_NONCE_REQUIRED: Lazy<bool> = Lazy::new(|| {
+    dotenvy::var("DPOP_NONCE_REQUIRED")
+        .ok()
+        .map(|required| required.parse::<bool>())
+        .transpose()
+        .expect("`DPOP_NONCE_REQUIRED` must be `true` or `false`.")
+        .unwrap_or(false)
+});
+
 #[cfg(feature = "url")]
 #[cfg(feature = "time")]
 #[cfg(feature = "uuid")]
@@ -2,7 +2,10 @@ mod account;
 mod client;
 mod mfa_code;
 mod session;
+mod signing_key;
 mod ticket;
 mod token;
 
-pub use self::{account::*, client::*, mfa_code::*, session::*, ticket::*, token::*};
+pub use self::{
+    account::*, client::*, mfa_code::*, session::*, signing_key::*, ticket::*, token::*,
+};
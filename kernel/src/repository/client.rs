@@ -1,4 +1,4 @@
-use crate::entities::ClientName;
+use crate::entities::{ClientDecodeJwt, ClientName};
 use crate::{
     entities::{Client, ClientId},
     KernelError,
@@ -19,3 +19,18 @@ pub trait DependOnClientRegistry: 'static + Sync + Send {
     type ClientRegistry: ClientRegistry;
     fn client_registry(&self) -> &Self::ClientRegistry;
 }
+
+/// Replay cache of client assertions (`private_key_jwt`).
+///
+/// Each `jti` must be kept until the assertion's `exp`.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait JtiVolatileRepository: 'static + Sync + Send {
+    /// Returns `false` if `jti` has already been used by the client.
+    async fn save(&self, assertion: &ClientDecodeJwt) -> Result<bool, KernelError>;
+}
+
+pub trait DependOnJtiVolatileRepository: 'static + Sync + Send {
+    type JtiVolatileRepository: JtiVolatileRepository;
+    fn jti_volatile_repository(&self) -> &Self::JtiVolatileRepository;
+}
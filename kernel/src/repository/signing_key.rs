@@ -0,0 +1,31 @@
+use crate::entities::{KeyId, SigningKey};
+use crate::KernelError;
+
+/// Implementations must encrypt [PrivateKey](crate::entities::PrivateKey) at rest.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait SigningKeyRepository: 'static + Sync + Send {
+    async fn create(&self, create: &SigningKey) -> Result<(), KernelError>;
+    async fn update(&self, update: &SigningKey) -> Result<(), KernelError>;
+    async fn delete(&self, delete: &KeyId) -> Result<(), KernelError>;
+
+    /// Saves `keys` in order in a single transaction,
+    /// updating the keys that exist and creating the others.
+    ///
+    /// Nothing is saved and `false` is returned if `active` and `next` are no longer
+    /// the `Active` and `Next` keys, i.e. another caller rotated them meanwhile.
+    async fn rotate(
+        &self,
+        active: Option<KeyId>,
+        next: Option<KeyId>,
+        keys: &[SigningKey],
+    ) -> Result<bool, KernelError>;
+
+    async fn find_all(&self) -> Result<Vec<SigningKey>, KernelError>;
+    async fn find_active(&self) -> Result<Option<SigningKey>, KernelError>;
+}
+
+pub trait DependOnSigningKeyRepository: 'static + Sync + Send {
+    type SigningKeyRepository: SigningKeyRepository;
+    fn signing_key_repository(&self) -> &Self::SigningKeyRepository;
+}
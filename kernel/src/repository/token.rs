@@ -1,7 +1,11 @@
 use crate::entities::TicketId;
 use crate::{
     entities::{
-        AccessToken, AccessTokenId, AuthorizeToken, AuthorizeTokenId, CodeChallenge, State,
+        AccessToken, AccessTokenId, AuthorizationDetailsType, AuthorizeToken, AuthorizeTokenId,
+        CodeChallenge, DPoPDecodeJwt, DPoPNonce, DeviceAuthorization, DeviceCode,
+        JwtBearerDecodeJwt, ProtectedResource, PushedAuthorizationRequest, RefreshToken,
+        RefreshTokenFamily, RefreshTokenId, RequestUri, SamlDecodeAssertion, State, TrustedIssuer,
+        UserCode,
     },
     KernelError,
 };
@@ -38,12 +42,160 @@ pub trait DependOnPendingAuthorizeTokenRepository: 'static + Sync + Send {
     fn pending_authorize_token_repository(&self) -> &Self::PendingAuthorizeTokenRepository;
 }
 
+/// Device Authorization Requests are as volatile as pending tokens,
+/// so I expect them to be implemented in an in-memory database such as Redis.
+///
+/// - Requests are found by the [DeviceCode] the device polls with
+///   and by the [UserCode] the user enters.
+/// - Stored requests should expire at [DeviceAuthorization::expired_in].
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait DeviceAuthorizationVolatileRepository: 'static + Sync + Send {
+    async fn save(&self, device: &DeviceAuthorization) -> Result<(), KernelError>;
+    async fn dele(&self, device: &DeviceAuthorization) -> Result<(), KernelError>;
+    async fn find_by_device_code(
+        &self,
+        code: &DeviceCode,
+    ) -> Result<Option<DeviceAuthorization>, KernelError>;
+    async fn find_by_user_code(
+        &self,
+        code: &UserCode,
+    ) -> Result<Option<DeviceAuthorization>, KernelError>;
+}
+
+pub trait DependOnDeviceAuthorizationVolatileRepository: 'static + Sync + Send {
+    type DeviceAuthorizationVolatileRepository: DeviceAuthorizationVolatileRepository;
+    fn device_authorization_volatile_repository(
+        &self,
+    ) -> &Self::DeviceAuthorizationVolatileRepository;
+}
+
+/// Pushed Authorization Requests live only until the user agent reaches
+/// the authorization endpoint, so I expect them to be implemented in an in-memory database such as Redis.
+///
+/// - Stored requests should expire at [PushedAuthorizationRequest::expired_in].
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait PushedAuthorizationRequestVolatileRepository: 'static + Sync + Send {
+    async fn save(&self, request: &PushedAuthorizationRequest) -> Result<(), KernelError>;
+    async fn dele(&self, request_uri: &RequestUri) -> Result<(), KernelError>;
+    async fn find(
+        &self,
+        request_uri: &RequestUri,
+    ) -> Result<Option<PushedAuthorizationRequest>, KernelError>;
+}
+
+pub trait DependOnPushedAuthorizationRequestVolatileRepository: 'static + Sync + Send {
+    type PushedAuthorizationRequestVolatileRepository: PushedAuthorizationRequestVolatileRepository;
+    fn pushed_authorization_request_volatile_repository(
+        &self,
+    ) -> &Self::PushedAuthorizationRequestVolatileRepository;
+}
+
+/// Replay cache of DPoP proofs.
+///
+/// Each `jti` must be kept until the proof's `iat` falls out of the acceptable window,
+/// see [DPoPProof::MAX_AGE](crate::entities::DPoPProof::MAX_AGE).
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait DPoPJtiVolatileRepository: 'static + Sync + Send {
+    /// Returns `false` if `jti` has already been used with the key.
+    async fn save(&self, proof: &DPoPDecodeJwt) -> Result<bool, KernelError>;
+}
+
+pub trait DependOnDPoPJtiVolatileRepository: 'static + Sync + Send {
+    type DPoPJtiVolatileRepository: DPoPJtiVolatileRepository;
+    fn dpop_jti_volatile_repository(&self) -> &Self::DPoPJtiVolatileRepository;
+}
+
+/// Nonces issued for DPoP proofs.
+///
+/// - Stored nonces should expire after [DPoPNonce::EXPIRES_IN] seconds.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait DPoPNonceVolatileRepository: 'static + Sync + Send {
+    async fn save(&self, nonce: &DPoPNonce) -> Result<(), KernelError>;
+    async fn find(&self, nonce: &DPoPNonce) -> Result<Option<DPoPNonce>, KernelError>;
+}
+
+pub trait DependOnDPoPNonceVolatileRepository: 'static + Sync + Send {
+    type DPoPNonceVolatileRepository: DPoPNonceVolatileRepository;
+    fn dpop_nonce_volatile_repository(&self) -> &Self::DPoPNonceVolatileRepository;
+}
+
+/// Issuers whose assertions are accepted as authorization grants.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait TrustedIssuerRepository: 'static + Sync + Send {
+    async fn find_by_issuer(&self, issuer: &str) -> Result<Option<TrustedIssuer>, KernelError>;
+}
+
+pub trait DependOnTrustedIssuerRepository: 'static + Sync + Send {
+    type TrustedIssuerRepository: TrustedIssuerRepository;
+    fn trusted_issuer_repository(&self) -> &Self::TrustedIssuerRepository;
+}
+
+/// Registry of the protected resources that can be named by the `resource` parameter,
+/// and of the authorization details types they accept.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait ProtectedResourceRepository: 'static + Sync + Send {
+    async fn find_by_resource(
+        &self,
+        resource: &str,
+    ) -> Result<Option<ProtectedResource>, KernelError>;
+
+    async fn find_authorization_details_type(
+        &self,
+        detail_type: &str,
+    ) -> Result<Option<AuthorizationDetailsType>, KernelError>;
+}
+
+pub trait DependOnProtectedResourceRepository: 'static + Sync + Send {
+    type ProtectedResourceRepository: ProtectedResourceRepository;
+    fn protected_resource_repository(&self) -> &Self::ProtectedResourceRepository;
+}
+
+/// Replay cache of JWT bearer assertions.
+///
+/// Each `jti` must be kept until the assertion's `exp`.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait JwtBearerJtiVolatileRepository: 'static + Sync + Send {
+    /// Returns `false` if `jti` has already been used by the issuer.
+    async fn save(&self, assertion: &JwtBearerDecodeJwt) -> Result<bool, KernelError>;
+}
+
+pub trait DependOnJwtBearerJtiVolatileRepository: 'static + Sync + Send {
+    type JwtBearerJtiVolatileRepository: JwtBearerJtiVolatileRepository;
+    fn jwt_bearer_jti_volatile_repository(&self) -> &Self::JwtBearerJtiVolatileRepository;
+}
+
+/// Replay cache of SAML bearer assertions.
+///
+/// Each `ID` must be kept until the assertion's `NotOnOrAfter`.
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait SamlAssertionIdVolatileRepository: 'static + Sync + Send {
+    /// Returns `false` if `ID` has already been used by the issuer.
+    async fn save(&self, assertion: &SamlDecodeAssertion) -> Result<bool, KernelError>;
+}
+
+pub trait DependOnSamlAssertionIdVolatileRepository: 'static + Sync + Send {
+    type SamlAssertionIdVolatileRepository: SamlAssertionIdVolatileRepository;
+    fn saml_assertion_id_volatile_repository(&self) -> &Self::SamlAssertionIdVolatileRepository;
+}
+
 #[cfg_attr(feature = "mock", mockall::automock)]
 #[async_trait::async_trait]
 pub trait AuthorizeTokenRepository: 'static + Sync + Send {
     async fn save(&self, id: &AuthorizeTokenId, token: &AuthorizeToken) -> Result<(), KernelError>;
     async fn dele(&self, id: &AuthorizeTokenId) -> Result<(), KernelError>;
     async fn find(&self, id: &AuthorizeTokenId) -> Result<Option<AuthorizeToken>, KernelError>;
+
+    /// Find and delete the token in a single step,
+    /// so that only one of concurrent callers gets it.
+    async fn take(&self, id: &AuthorizeTokenId) -> Result<Option<AuthorizeToken>, KernelError>;
 }
 
 pub trait DependOnAuthorizeTokenRepository: 'static + Sync + Send {
@@ -62,6 +214,10 @@ pub trait PKCEVolatileRepository: 'static + Sync + Send {
     async fn dele(&self, token_id: &AuthorizeTokenId) -> Result<(), KernelError>;
     async fn find(&self, token_id: &AuthorizeTokenId)
         -> Result<Option<CodeChallenge>, KernelError>;
+
+    /// Find and delete the challenge in a single step.
+    async fn take(&self, token_id: &AuthorizeTokenId)
+        -> Result<Option<CodeChallenge>, KernelError>;
 }
 
 pub trait DependOnPKCEVolatileRepository: 'static + Sync + Send {
@@ -82,6 +238,8 @@ pub trait DependOnStateVolatileRepository: 'static + Sync + Send {
     fn state_volatile_repository(&self) -> &Self::StateVolatileRepository;
 }
 
+/// Access tokens are bearer credentials,
+/// so implementations should store them by [TokenDigest](crate::entities::TokenDigest) rather than by raw id.
 #[cfg_attr(feature = "mock", mockall::automock)]
 #[async_trait::async_trait]
 pub trait AccessTokenRepository: 'static + Sync + Send {
@@ -97,13 +255,27 @@ pub trait DependOnAccessTokenRepository: 'static + Sync + Send {
     fn access_token_repository(&self) -> &Self::AccessTokenRepository;
 }
 
+/// Refresh tokens are long-lived, so I expect them to be stored in a persistent database.
+///
+/// - Rotated tokens are kept (see [RefreshToken::is_rotated]) so that reuse can be detected.
+/// - Like [AccessTokenRepository], tokens should be stored by their [TokenDigest](crate::entities::TokenDigest).
 #[cfg_attr(feature = "mock", mockall::automock)]
 #[async_trait::async_trait]
 pub trait RefreshTokenRepository: 'static + Sync + Send {
-    async fn create(&self) -> Result<(), KernelError>;
-    async fn delete(&self) -> Result<(), KernelError>;
+    async fn create(&self, create: &RefreshToken) -> Result<(), KernelError>;
+
+    /// Persist a token marked as rotated and delete the access token issued together with it.
+    ///
+    /// The token is marked only if it has not been rotated yet,
+    /// and `false` is returned when another request has already rotated it.
+    async fn rotate(&self, rotated: &RefreshToken) -> Result<bool, KernelError>;
+
+    async fn delete(&self, delete: &RefreshTokenId) -> Result<(), KernelError>;
+
+    /// Delete every refresh token in the family together with the access tokens derived from them.
+    async fn revoke_family(&self, family: &RefreshTokenFamily) -> Result<(), KernelError>;
 
-    async fn find(&self) -> Result<(), KernelError>;
+    async fn find_by_id(&self, id: &RefreshTokenId) -> Result<Option<RefreshToken>, KernelError>;
 }
 
 pub trait DependOnRefreshTokenRepository: 'static + Sync + Send {
@@ -1,4 +1,5 @@
 use rand::distributions::{Alphanumeric, Distribution};
+use rand::Rng;
 
 pub struct RandomizeService;
 
@@ -14,6 +15,18 @@ impl RandomizeService {
             .collect::<String>();
         func(gen)
     }
+
+    /// Like [Self::gen_str], but only uses characters in `charset`.
+    pub fn gen_str_from<F, T>(charset: &[u8], len: usize, func: F) -> T
+    where
+        F: Fn(String) -> T,
+    {
+        let mut rng = rand::thread_rng();
+        let gen = (0..len)
+            .map(|_| char::from(charset[rng.gen_range(0..charset.len())]))
+            .collect::<String>();
+        func(gen)
+    }
 }
 
 #[cfg(test)]
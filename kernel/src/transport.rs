@@ -1,4 +1,6 @@
 mod blacklist;
+mod jwks;
 mod mail;
+mod request_object;
 
-pub use self::{blacklist::*, mail::*};
+pub use self::{blacklist::*, jwks::*, mail::*, request_object::*};
@@ -0,0 +1,14 @@
+use crate::entities::ClientJwkSet;
+use crate::KernelError;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait JwksTransporter: 'static + Sync + Send {
+    /// Fetches the JWK Set published at the client's `jwks_uri`.
+    async fn fetch(&self, uri: &str) -> Result<ClientJwkSet, KernelError>;
+}
+
+pub trait DependOnJwksTransporter: 'static + Sync + Send {
+    type JwksTransporter: JwksTransporter;
+    fn jwks_transporter(&self) -> &Self::JwksTransporter;
+}
@@ -0,0 +1,16 @@
+use crate::entities::RequestObject;
+use crate::KernelError;
+
+#[cfg_attr(feature = "mock", mockall::automock)]
+#[async_trait::async_trait]
+pub trait RequestObjectTransporter: 'static + Sync + Send {
+    /// Fetches the request object published by the client at `request_uri`.
+    ///
+    /// See [RFC9101 Section 5.2.3](https://datatracker.ietf.org/doc/html/rfc9101#section-5.2.3)
+    async fn fetch(&self, uri: &str) -> Result<RequestObject, KernelError>;
+}
+
+pub trait DependOnRequestObjectTransporter: 'static + Sync + Send {
+    type RequestObjectTransporter: RequestObjectTransporter;
+    fn request_object_transporter(&self) -> &Self::RequestObjectTransporter;
+}
@@ -0,0 +1,20 @@
+-- Tokens are looked up by the Base64Url (no padding) encoded SHA-256 digest of their value,
+-- so that a leaked database does not expose tokens usable as bearer credentials.
+CREATE TABLE access_tokens(
+  id          VARCHAR(128) NOT NULL PRIMARY KEY,
+  client_id   UUID         NOT NULL,
+  account     UUID         NOT NULL,
+  scope       TEXT[]       NOT NULL,
+  aud         TEXT         NOT NULL,
+  iss         TEXT         NOT NULL,
+  sub         TEXT         NOT NULL,
+  exp         TIMESTAMPTZ  NOT NULL,
+  iat         TIMESTAMPTZ  NOT NULL,
+  nbf         TIMESTAMPTZ  NOT NULL,
+
+  created_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+  updated_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+  FOREIGN KEY (client_id) REFERENCES clients(client_id) ON DELETE CASCADE,
+  FOREIGN KEY (account)   REFERENCES users(user_id)     ON DELETE CASCADE
+);
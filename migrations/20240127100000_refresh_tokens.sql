@@ -0,0 +1,20 @@
+-- Tokens, and the access token they were issued with, are looked up by the Base64Url (no padding) encoded SHA-256 digest of their value,
+-- so that a leaked database does not expose tokens usable as bearer credentials.
+CREATE TABLE refresh_tokens(
+  id           VARCHAR(128) NOT NULL PRIMARY KEY,
+  family       UUID         NOT NULL,
+  access_token VARCHAR(128) NOT NULL,
+  client_id    UUID         NOT NULL,
+  account      UUID         NOT NULL,
+  scope        TEXT[]       NOT NULL,
+  exp          TIMESTAMPTZ  NOT NULL,
+  rotated      BOOLEAN      NOT NULL DEFAULT FALSE,
+
+  created_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+  updated_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+  FOREIGN KEY (client_id) REFERENCES clients(client_id) ON DELETE CASCADE,
+  FOREIGN KEY (account)   REFERENCES users(user_id)     ON DELETE CASCADE
+);
+
+CREATE INDEX refresh_tokens_family_idx ON refresh_tokens(family);
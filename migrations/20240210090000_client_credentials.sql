@@ -0,0 +1,2 @@
+-- Tokens issued by the Client Credentials Grant are not bound to any user.
+ALTER TABLE access_tokens ALTER COLUMN account DROP NOT NULL;
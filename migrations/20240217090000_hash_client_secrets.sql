@@ -0,0 +1,8 @@
+-- Client secrets are hashed with Argon2 by the application.
+-- Argon2 is not available here, so existing plaintext secrets are digested with SHA-256 instead,
+-- which the application still accepts.
+ALTER TABLE client_cert ALTER COLUMN client_secret TYPE TEXT;
+
+UPDATE client_cert
+  SET client_secret = '$sha256$' || encode(sha256(convert_to(client_secret, 'UTF8')), 'hex')
+  WHERE client_secret IS NOT NULL;
@@ -0,0 +1,17 @@
+CREATE TABLE signing_keys(
+  kid          VARCHAR(64)  NOT NULL PRIMARY KEY,
+  alg          VARCHAR(16)  NOT NULL,
+  state        VARCHAR(16)  NOT NULL,
+  private_key  BYTEA        NOT NULL,
+  public_key   TEXT         NOT NULL,
+  activated_at TIMESTAMPTZ,
+  retired_at   TIMESTAMPTZ,
+
+  created_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+  updated_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+  CHECK (state IN ('next', 'active', 'retired'))
+);
+
+-- At most one key is `next` and one is `active` at a time.
+CREATE UNIQUE INDEX signing_keys_state_idx ON signing_keys(state) WHERE state <> 'retired';
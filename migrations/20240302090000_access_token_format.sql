@@ -0,0 +1,4 @@
+-- RFC9068: Clients may opt in to JWT access tokens.
+ALTER TABLE client_cert
+  ADD COLUMN access_token_format VARCHAR(16) NOT NULL DEFAULT 'opaque'
+  CHECK (access_token_format IN ('opaque', 'jwt'));
@@ -0,0 +1,2 @@
+-- RFC8628: Device Authorization Grant.
+ALTER TYPE GRANT_TYPE ADD VALUE 'urn:ietf:params:oauth:grant-type:device_code';
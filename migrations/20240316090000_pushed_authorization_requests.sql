@@ -0,0 +1,3 @@
+-- RFC9126: Clients may require that authorization requests are pushed.
+ALTER TABLE client_cert
+  ADD COLUMN require_pushed_authorization_requests BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,7 @@
+-- RFC9101: Clients may require that authorization requests are signed request objects.
+ALTER TABLE client_cert
+  ADD COLUMN require_signed_request_object BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- RFC9101: The only `request_uri` values the authorization server fetches for a client.
+ALTER TABLE client_cert
+  ADD COLUMN request_uris TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,7 @@
+-- RFC9449: Access tokens may be bound to the JWK SHA-256 Thumbprint of the client's DPoP key.
+ALTER TABLE access_tokens
+  ADD COLUMN dpop_jkt TEXT;
+
+-- RFC9449: Refresh tokens of public clients are bound to the same key.
+ALTER TABLE refresh_tokens
+  ADD COLUMN dpop_jkt TEXT;
@@ -0,0 +1,16 @@
+-- RFC8705: Clients may authenticate with a mutual-TLS client certificate.
+ALTER TYPE TEP_AM ADD VALUE 'tls_client_auth';
+ALTER TYPE TEP_AM ADD VALUE 'self_signed_tls_client_auth';
+
+-- RFC8705: The subject DN or SAN that the certificate of a `tls_client_auth` client must have.
+ALTER TABLE client_cert
+  ADD COLUMN tls_client_auth_subject_type TEXT,
+  ADD COLUMN tls_client_auth_subject TEXT;
+
+-- RFC8705: Access tokens may be bound to the thumbprint of the client certificate.
+ALTER TABLE access_tokens
+  ADD COLUMN x5t_s256 TEXT;
+
+-- RFC8705: Refresh tokens of public clients are bound to the same certificate.
+ALTER TABLE refresh_tokens
+  ADD COLUMN x5t_s256 TEXT;
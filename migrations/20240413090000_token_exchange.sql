@@ -0,0 +1,10 @@
+-- RFC8693: OAuth 2.0 Token Exchange.
+ALTER TYPE GRANT_TYPE ADD VALUE 'urn:ietf:params:oauth:grant-type:token-exchange';
+
+-- RFC8693: The audiences a client may exchange tokens into.
+ALTER TABLE client_cert
+  ADD COLUMN token_exchange_audiences TEXT[] NOT NULL DEFAULT '{}';
+
+-- RFC8693: The delegation chain of an exchanged token.
+ALTER TABLE access_tokens
+  ADD COLUMN act JSONB;
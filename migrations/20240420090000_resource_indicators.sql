@@ -0,0 +1,3 @@
+-- RFC8707: The protected resource a refresh token was granted for.
+ALTER TABLE refresh_tokens
+  ADD COLUMN resource TEXT;
@@ -0,0 +1,7 @@
+-- RFC9396: The authorization details an access token is granted for.
+ALTER TABLE access_tokens
+  ADD COLUMN authorization_details JSONB;
+
+-- RFC9396: The authorization details of the grant, carried across rotation.
+ALTER TABLE refresh_tokens
+  ADD COLUMN authorization_details JSONB;
@@ -1,12 +1,13 @@
 use application::{ApplicationError, ExpectUserAction};
 use axum::{
-    http::header::CONTENT_LOCATION,
+    http::header::{CONTENT_LOCATION, WWW_AUTHENTICATE},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
 use axum_extra::headers::{HeaderMap, HeaderValue};
 use driver::DriverError;
+use kernel::prelude::entities::DPOP_SIGNING_ALGORITHMS;
 use serde_json::json;
 use std::convert::Infallible;
 use std::fmt::Display;
@@ -25,12 +26,47 @@ pub enum ServerError {
     Serde(anyhow::Error),
     #[error(transparent)]
     RequestParse(anyhow::Error),
+    #[error("client authentication failed: {value}")]
+    InvalidClient { value: String },
+    #[error("{error}: {value}")]
+    OAuth { error: &'static str, value: String },
+    #[error("DPoP proof must carry the nonce `{nonce}`.")]
+    UseDPoPNonce { nonce: String },
+    #[error("bearer token is required.")]
+    MissingToken,
+    #[error("access token is invalid: {value}")]
+    InvalidToken { scheme: TokenScheme, value: String },
+    #[error("access token has insufficient scope: {value}")]
+    InsufficientScope { scheme: TokenScheme, value: String },
     #[error("require user action.")]
     RequireUserAction(ExpectUserAction),
     #[error(transparent)]
     Infallible(#[from] Infallible),
 }
 
+/// Authentication scheme an access token is presented with,
+/// which the protected resource challenges with in turn.
+///
+/// See [RFC6750 Section 3](https://datatracker.ietf.org/doc/html/rfc6750#section-3)
+/// and [RFC9449 Section 7.1](https://datatracker.ietf.org/doc/html/rfc9449#section-7.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TokenScheme {
+    #[default]
+    Bearer,
+    DPoP,
+}
+
+impl ServerError {
+    /// Challenges with `scheme` if the error is about the presented access token.
+    pub fn with_scheme(self, scheme: TokenScheme) -> Self {
+        match self {
+            Self::InvalidToken { value, .. } => Self::InvalidToken { scheme, value },
+            Self::InsufficientScope { value, .. } => Self::InsufficientScope { scheme, value },
+            other => other,
+        }
+    }
+}
+
 impl serde::de::Error for ServerError {
     fn custom<T>(msg: T) -> Self
     where
@@ -44,11 +80,64 @@ impl From<ApplicationError> for ServerError {
     fn from(value: ApplicationError) -> Self {
         match value {
             ApplicationError::RequireUserAction(expect) => Self::RequireUserAction(expect),
+            ApplicationError::InvalidValue {
+                method: "invalid_client",
+                value,
+            } => Self::InvalidClient { value },
+            ApplicationError::InvalidValue {
+                method: "invalid_token",
+                value,
+            } => Self::InvalidToken {
+                scheme: TokenScheme::default(),
+                value,
+            },
+            ApplicationError::InvalidValue {
+                method: "insufficient_scope",
+                value,
+            } => Self::InsufficientScope {
+                scheme: TokenScheme::default(),
+                value,
+            },
+            ApplicationError::InvalidValue {
+                method: "use_dpop_nonce",
+                value,
+            } => Self::UseDPoPNonce { nonce: value },
+            ApplicationError::InvalidValue { method, value } if OAUTH_ERRORS.contains(&method) => {
+                Self::OAuth {
+                    error: method,
+                    value,
+                }
+            }
             _ => Self::Application(value),
         }
     }
 }
 
+/// Error codes that are returned as is in the `error` member.
+///
+/// See [RFC6749 Section 5.2](https://datatracker.ietf.org/doc/html/rfc6749#section-5.2)
+/// and [RFC8628 Section 3.5](https://datatracker.ietf.org/doc/html/rfc8628#section-3.5)
+/// and [RFC9101 Section 6.3](https://datatracker.ietf.org/doc/html/rfc9101#section-6.3)
+/// and [RFC9449 Section 12.2](https://datatracker.ietf.org/doc/html/rfc9449#section-12.2)
+/// and [RFC8693 Section 2.2.2](https://datatracker.ietf.org/doc/html/rfc8693#section-2.2.2)
+/// and [RFC9396 Section 5](https://datatracker.ietf.org/doc/html/rfc9396#section-5)
+const OAUTH_ERRORS: [&str; 14] = [
+    "invalid_request",
+    "invalid_grant",
+    "unauthorized_client",
+    "unsupported_grant_type",
+    "invalid_scope",
+    "authorization_pending",
+    "slow_down",
+    "access_denied",
+    "expired_token",
+    "invalid_request_object",
+    "invalid_request_uri",
+    "invalid_dpop_proof",
+    "invalid_target",
+    "invalid_authorization_details",
+];
+
 impl From<kernel::external::UuidError> for ServerError {
     fn from(e: kernel::external::UuidError) -> Self {
         Self::RequestParse(anyhow::Error::new(e))
@@ -82,6 +171,32 @@ impl IntoResponse for ServerError {
             ServerError::RequireUserAction(expect) => {
                 return require_user_actions(expect).into_response()
             }
+            ServerError::InvalidClient { value } => return invalid_client(value).into_response(),
+            ServerError::OAuth { error, value } => {
+                let json = json!({ "error": error, "error_description": value });
+                return (StatusCode::BAD_REQUEST, Json(json)).into_response();
+            }
+            ServerError::UseDPoPNonce { nonce } => return use_dpop_nonce(nonce).into_response(),
+            ServerError::MissingToken => {
+                return token_challenge(StatusCode::UNAUTHORIZED, TokenScheme::Bearer, None)
+                    .into_response()
+            }
+            ServerError::InvalidToken { scheme, value } => {
+                return token_challenge(
+                    StatusCode::UNAUTHORIZED,
+                    scheme,
+                    Some(("invalid_token", value)),
+                )
+                .into_response()
+            }
+            ServerError::InsufficientScope { scheme, value } => {
+                return token_challenge(
+                    StatusCode::FORBIDDEN,
+                    scheme,
+                    Some(("insufficient_scope", value)),
+                )
+                .into_response()
+            }
         };
 
         let json = json!({ "error": msg });
@@ -90,6 +205,72 @@ impl IntoResponse for ServerError {
     }
 }
 
+/// See [RFC6749 Section 5.2](https://datatracker.ietf.org/doc/html/rfc6749#section-5.2)
+fn invalid_client(description: String) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        WWW_AUTHENTICATE,
+        HeaderValue::from_static(r#"Basic realm="stellar""#),
+    );
+    let json = json!({ "error": "invalid_client", "error_description": description });
+    (StatusCode::UNAUTHORIZED, headers, Json(json))
+}
+
+/// The nonce to retry with is given in the `DPoP-Nonce` header.
+///
+/// See [RFC9449 Section 8](https://datatracker.ietf.org/doc/html/rfc9449#section-8)
+fn use_dpop_nonce(nonce: String) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    if let Ok(nonce) = HeaderValue::from_str(&nonce) {
+        headers.insert("DPoP-Nonce", nonce);
+    }
+    let json = json!({
+        "error": "use_dpop_nonce",
+        "error_description": "Authorization server requires nonce in DPoP proof",
+    });
+    (StatusCode::BAD_REQUEST, headers, Json(json))
+}
+
+/// A request without credentials is only challenged, the others also get an error code.
+/// A `DPoP` challenge also lists the algorithms accepted for the proof.
+///
+/// See [RFC6750 Section 3](https://datatracker.ietf.org/doc/html/rfc6750#section-3)
+/// and [RFC9449 Section 7.1](https://datatracker.ietf.org/doc/html/rfc9449#section-7.1)
+fn token_challenge(
+    status: StatusCode,
+    scheme: TokenScheme,
+    error: Option<(&str, String)>,
+) -> impl IntoResponse {
+    let mut challenge = match scheme {
+        TokenScheme::Bearer => r#"Bearer realm="stellar""#.to_string(),
+        TokenScheme::DPoP => r#"DPoP realm="stellar""#.to_string(),
+    };
+    if let Some((error, description)) = &error {
+        challenge.push_str(&format!(
+            r#", error="{}", error_description="{}""#,
+            error,
+            description.replace(['\\', '"'], "")
+        ));
+    }
+    if scheme == TokenScheme::DPoP {
+        let algs = DPOP_SIGNING_ALGORITHMS
+            .iter()
+            .map(|alg| format!("{:?}", alg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        challenge.push_str(&format!(r#", algs="{}""#, algs));
+    }
+    let mut headers = HeaderMap::new();
+    if let Ok(challenge) = HeaderValue::from_str(&challenge) {
+        headers.insert(WWW_AUTHENTICATE, challenge);
+    }
+    let json = match error {
+        Some((error, description)) => json!({ "error": error, "error_description": description }),
+        None => json!({}),
+    };
+    (status, headers, Json(json))
+}
+
 fn require_user_actions(expect: ExpectUserAction) -> impl IntoResponse {
     match expect {
         ExpectUserAction::Login => (
@@ -107,3 +288,42 @@ fn require_user_actions(expect: ExpectUserAction) -> impl IntoResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ServerError, TokenScheme};
+    use application::ApplicationError;
+    use axum::http::header::WWW_AUTHENTICATE;
+    use axum::response::IntoResponse;
+
+    fn challenge(err: ServerError) -> String {
+        let res = err.into_response();
+        res.headers()[WWW_AUTHENTICATE]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn bearer_challenge() {
+        let err = ServerError::from(ApplicationError::InvalidValue {
+            method: "invalid_token",
+            value: "access token is expired.".to_string(),
+        });
+        let challenge = challenge(err);
+        assert!(challenge.starts_with(r#"Bearer realm="stellar", error="invalid_token""#));
+        assert!(!challenge.contains("algs"));
+    }
+
+    #[test]
+    fn dpop_challenge() {
+        let err = ServerError::from(ApplicationError::InvalidValue {
+            method: "insufficient_scope",
+            value: "`openid` scope is required.".to_string(),
+        })
+        .with_scheme(TokenScheme::DPoP);
+        let challenge = challenge(err);
+        assert!(challenge.starts_with(r#"DPoP realm="stellar", error="insufficient_scope""#));
+        assert!(challenge.ends_with(r#"algs="RS256 PS256 ES256 EdDSA""#));
+    }
+}
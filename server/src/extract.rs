@@ -1 +1,4 @@
+pub mod certificate;
+pub mod client;
+pub mod dpop;
 pub mod session;
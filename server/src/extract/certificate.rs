@@ -0,0 +1,90 @@
+use crate::ServerError;
+use axum::extract::FromRequestParts;
+use axum::http::header::InvalidHeaderName;
+use axum::http::request::Parts;
+use axum::http::HeaderName;
+use kernel::prelude::entities::ClientCertificate;
+use kernel::KernelError;
+use once_cell::sync::Lazy;
+use percent_encoding::percent_decode_str;
+
+/// Header in which a reverse proxy terminating mutual TLS forwards the client certificate,
+/// e.g. `X-Client-Cert` set to nginx's `$ssl_client_escaped_cert`.
+///
+/// Certificates are only read when this is set to a non-empty value,
+/// as the header must not be accepted from anything but the trusted proxy.
+static CLIENT_CERTIFICATE_HEADER: Lazy<Result<Option<HeaderName>, InvalidHeaderName>> =
+    Lazy::new(|| {
+        dotenvy::var("MTLS_CLIENT_CERT_HEADER")
+            .ok()
+            .filter(|name| !name.is_empty())
+            .map(|name| HeaderName::try_from(name.as_str()))
+            .transpose()
+    });
+
+/// The configured `MTLS_CLIENT_CERT_HEADER`.
+///
+/// Called at startup, so that an invalid name is reported before the first request.
+pub fn client_certificate_header() -> Result<Option<&'static HeaderName>, ServerError> {
+    CLIENT_CERTIFICATE_HEADER
+        .as_ref()
+        .map(Option::as_ref)
+        .map_err(|e| ServerError::InvalidValue {
+            method: "MTLS_CLIENT_CERT_HEADER",
+            value: e.to_string(),
+        })
+}
+
+/// PEM encoded certificate of the client on the mutual-TLS connection.
+///
+/// See [RFC8705 Section 2](https://datatracker.ietf.org/doc/html/rfc8705#section-2)
+pub struct ClientCertificateHeader(pub Option<String>);
+
+impl ClientCertificateHeader {
+    /// `x5t#S256` of the certificate, if one was presented.
+    pub fn thumbprint(&self) -> Result<Option<String>, KernelError> {
+        self.0
+            .as_deref()
+            .map(ClientCertificate::from_pem)
+            .transpose()
+            .map(|certificate| certificate.as_ref().map(ClientCertificate::thumbprint))
+    }
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for ClientCertificateHeader
+where
+    S: Sync + Send,
+{
+    type Rejection = ServerError;
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(name) = client_certificate_header()? else {
+            return Ok(Self(None));
+        };
+
+        let Some(value) = parts.headers.get(name) else {
+            return Ok(Self(None));
+        };
+
+        let value = value.to_str().map_err(|e| ServerError::InvalidClient {
+            value: e.to_string(),
+        })?;
+        let value = percent_decode_str(value.trim())
+            .decode_utf8()
+            .map_err(|e| ServerError::InvalidClient {
+                value: e.to_string(),
+            })?;
+
+        // Some proxies forward the Base64 encoded DER without the PEM armor.
+        let pem = if value.starts_with("-----BEGIN") {
+            value.into_owned()
+        } else {
+            format!(
+                "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+                value
+            )
+        };
+
+        Ok(Self(Some(pem)))
+    }
+}
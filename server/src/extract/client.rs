@@ -0,0 +1,269 @@
+use crate::extract::certificate::ClientCertificateHeader;
+use crate::{Handler, ServerError};
+use application::services::{AuthenticateClientService, DependOnAuthenticateClientService};
+use application::transfer::client::{ClientAuthenticationDto, TokenEndPointAuthMethodDto};
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Request};
+use axum::RequestPartsExt;
+use axum_extra::headers::authorization::Basic;
+use axum_extra::headers::Authorization;
+use axum_extra::typed_header::TypedHeader;
+use kernel::external::Uuid;
+use kernel::prelude::entities::{ClientSignJwt, CLIENT_ASSERTION_TYPE};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Form of a request made by a client authenticated at the token endpoint.
+///
+/// Credentials are read from either the `Authorization: Basic` header, the form body,
+/// a `client_assertion` or the mutual-TLS client certificate,
+/// and are verified against the method registered for the client.
+///
+/// See [RFC6749 Section 2.3.1](https://datatracker.ietf.org/doc/html/rfc6749#section-2.3.1)
+/// and [RFC7523 Section 2.2](https://datatracker.ietf.org/doc/html/rfc7523#section-2.2)
+/// and [RFC8705 Section 2](https://datatracker.ietf.org/doc/html/rfc8705#section-2)
+pub struct AuthenticatedClient<F> {
+    pub client_id: Uuid,
+    pub form: F,
+    /// Thumbprint of the client certificate, to bind issued tokens to.
+    pub x5t_s256: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClientCredentialForm {
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    client_assertion_type: Option<String>,
+    client_assertion: Option<String>,
+}
+
+#[axum::async_trait]
+impl<F> FromRequest<Handler> for AuthenticatedClient<F>
+where
+    F: DeserializeOwned + Send,
+{
+    type Rejection = ServerError;
+    async fn from_request(req: Request, handler: &Handler) -> Result<Self, Self::Rejection> {
+        let (mut parts, body) = req.into_parts();
+        let basic: Option<TypedHeader<Authorization<Basic>>> = parts.extract().await?;
+        let certificate: ClientCertificateHeader = parts.extract().await?;
+        let x5t_s256 = certificate
+            .thumbprint()
+            .map_err(|e| ServerError::InvalidClient {
+                value: e.to_string(),
+            })?;
+        let ClientCertificateHeader(certificate) = certificate;
+
+        let body = Bytes::from_request(Request::from_parts(parts, body), handler)
+            .await
+            .map_err(|e| ServerError::RequestParse(anyhow::Error::new(e)))?;
+
+        reject_repeated(&body)?;
+        let form: F = serde_urlencoded::from_bytes(&body)
+            .map_err(|e| ServerError::RequestParse(anyhow::Error::new(e)))?;
+        let credential: ClientCredentialForm = serde_urlencoded::from_bytes(&body)
+            .map_err(|e| ServerError::RequestParse(anyhow::Error::new(e)))?;
+
+        let assertion = match (
+            credential.client_assertion_type,
+            credential.client_assertion,
+        ) {
+            (Some(ty), Some(assertion)) if ty.eq(CLIENT_ASSERTION_TYPE) => Some(assertion),
+            (None, None) => None,
+            (Some(ty), Some(_)) => {
+                return Err(ServerError::InvalidValue {
+                    method: "invalid_request",
+                    value: format!("`client_assertion_type`: `{}` is not supported.", ty),
+                })
+            }
+            _ => {
+                return Err(ServerError::InvalidValue {
+                    method: "invalid_request",
+                    value: "`client_assertion_type` and `client_assertion` are required together."
+                        .to_string(),
+                })
+            }
+        };
+
+        if let Some(assertion) = assertion {
+            if basic.is_some() || credential.client_secret.is_some() {
+                return Err(ServerError::InvalidValue {
+                    method: "invalid_request",
+                    value: "client must not use more than one authentication method.".to_string(),
+                });
+            }
+
+            // `client_id` is optional with an assertion, whose `sub` is the client.
+            let client_id = match credential.client_id {
+                Some(client_id) => client_id,
+                None => ClientSignJwt::new(assertion.as_str())
+                    .unverified_subject()
+                    .map_err(|_| ServerError::InvalidClient {
+                        value: "client authentication failed.".to_string(),
+                    })?
+                    .to_string(),
+            };
+
+            let client_id = parse_client_id(&client_id)?;
+            let auth = ClientAuthenticationDto {
+                client_id,
+                method: TokenEndPointAuthMethodDto::PrivateKeyJWT,
+                secret: None,
+                assertion: Some(assertion),
+                certificate,
+            };
+            authenticate(handler, auth).await?;
+
+            return Ok(Self {
+                client_id,
+                form,
+                x5t_s256,
+            });
+        }
+
+        let (client_id, method, secret) = match (basic, credential.client_secret) {
+            (Some(_), Some(_)) => {
+                return Err(ServerError::InvalidValue {
+                    method: "invalid_request",
+                    value: "client must not use more than one authentication method.".to_string(),
+                })
+            }
+            (Some(TypedHeader(Authorization(basic))), None) => {
+                let client_id = form_decode(basic.username())?;
+                if credential
+                    .client_id
+                    .is_some_and(|form| form.ne(client_id.as_str()))
+                {
+                    return Err(ServerError::InvalidValue {
+                        method: "invalid_request",
+                        value: "`client_id` does not match the `Authorization` header.".to_string(),
+                    });
+                }
+                (
+                    client_id,
+                    TokenEndPointAuthMethodDto::ClientSecretBasic,
+                    Some(form_decode(basic.password())?),
+                )
+            }
+            (None, Some(secret)) => (
+                required_client_id(credential.client_id)?,
+                TokenEndPointAuthMethodDto::ClientSecretPost,
+                Some(secret),
+            ),
+            // Which of the mTLS methods applies is decided by the registration.
+            (None, None) if certificate.is_some() => (
+                required_client_id(credential.client_id)?,
+                TokenEndPointAuthMethodDto::TlsClientAuth,
+                None,
+            ),
+            (None, None) => (
+                required_client_id(credential.client_id)?,
+                TokenEndPointAuthMethodDto::None,
+                None,
+            ),
+        };
+
+        let client_id = parse_client_id(&client_id)?;
+        let auth = ClientAuthenticationDto {
+            client_id,
+            method,
+            secret,
+            assertion: None,
+            certificate,
+        };
+        authenticate(handler, auth).await?;
+
+        Ok(Self {
+            client_id,
+            form,
+            x5t_s256,
+        })
+    }
+}
+
+/// Parameters must not be included more than once.
+///
+/// `resource` may be repeated to request several resources,
+/// but only a single one is supported, so that is reported as `invalid_target`.
+///
+/// See [RFC6749 Section 3.2](https://datatracker.ietf.org/doc/html/rfc6749#section-3.2)
+/// and [RFC8707 Section 2](https://datatracker.ietf.org/doc/html/rfc8707#section-2)
+fn reject_repeated(body: &[u8]) -> Result<(), ServerError> {
+    let params = serde_urlencoded::from_bytes::<Vec<(String, String)>>(body)
+        .map_err(|e| ServerError::RequestParse(anyhow::Error::new(e)))?;
+    let mut seen = HashSet::new();
+    match params.iter().find(|(name, _)| !seen.insert(name.as_str())) {
+        Some((name, _)) if name.eq("resource") => Err(ServerError::OAuth {
+            error: "invalid_target",
+            value: "only a single `resource` is supported.".to_string(),
+        }),
+        Some((name, _)) => Err(ServerError::OAuth {
+            error: "invalid_request",
+            value: format!("`{}` must not be included more than once.", name),
+        }),
+        None => Ok(()),
+    }
+}
+
+async fn authenticate(handler: &Handler, auth: ClientAuthenticationDto) -> Result<(), ServerError> {
+    handler
+        .authenticate_client_service()
+        .authenticate(auth)
+        .await?;
+    Ok(())
+}
+
+fn parse_client_id(client_id: &str) -> Result<Uuid, ServerError> {
+    Uuid::parse_str(client_id).map_err(|_| ServerError::InvalidClient {
+        value: "client authentication failed.".to_string(),
+    })
+}
+
+fn required_client_id(client_id: Option<String>) -> Result<String, ServerError> {
+    client_id.ok_or_else(|| ServerError::InvalidClient {
+        value: "`client_id` is required.".to_string(),
+    })
+}
+
+/// Credentials in the `Authorization: Basic` header are encoded with
+/// `application/x-www-form-urlencoded` before being encoded in Base64.
+fn form_decode(value: &str) -> Result<String, ServerError> {
+    let decoded = serde_urlencoded::from_str::<Vec<(String, String)>>(&format!("v={}", value))
+        .map_err(|e| ServerError::RequestParse(anyhow::Error::new(e)))?;
+    Ok(decoded
+        .into_iter()
+        .next()
+        .map(|(_, value)| value)
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reject_repeated;
+    use crate::ServerError;
+
+    #[test]
+    fn repeated_params() {
+        assert!(reject_repeated(
+            b"grant_type=client_credentials&resource=https%3A%2F%2Fapi.example.com"
+        )
+        .is_ok());
+        assert!(matches!(
+            reject_repeated(
+                b"resource=https%3A%2F%2Fa.example.com&resource=https%3A%2F%2Fb.example.com"
+            ),
+            Err(ServerError::OAuth {
+                error: "invalid_target",
+                ..
+            })
+        ));
+        assert!(matches!(
+            reject_repeated(b"scope=read&scope=write"),
+            Err(ServerError::OAuth {
+                error: "invalid_request",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{ServerError, TokenScheme};
+use application::transfer::token::DPoPProofDto;
+use axum::extract::FromRequestParts;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use kernel::prelude::entities::Issuer;
+
+/// DPoP proof presented in the `DPoP` header, along with the method and URI of the request.
+///
+/// See [RFC9449 Section 4.1](https://datatracker.ietf.org/doc/html/rfc9449#section-4.1)
+pub struct DPoPHeader {
+    proof: Option<String>,
+    htm: String,
+    htu: String,
+}
+
+impl DPoPHeader {
+    /// `access_token` is given when the proof is presented with it to a resource.
+    pub fn into_dto(self, access_token: Option<String>) -> Option<DPoPProofDto> {
+        let Self { proof, htm, htu } = self;
+        proof.map(|proof| DPoPProofDto {
+            proof,
+            htm,
+            htu,
+            access_token,
+        })
+    }
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for DPoPHeader
+where
+    S: Sync + Send,
+{
+    type Rejection = ServerError;
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let mut proofs = parts.headers.get_all("DPoP").iter();
+        let proof = proofs
+            .next()
+            .map(|proof| proof.to_str().map(ToOwned::to_owned))
+            .transpose()
+            .map_err(|e| ServerError::OAuth {
+                error: "invalid_dpop_proof",
+                value: e.to_string(),
+            })?;
+
+        if proofs.next().is_some() {
+            return Err(ServerError::OAuth {
+                error: "invalid_dpop_proof",
+                value: "only one `DPoP` header is allowed.".to_string(),
+            });
+        }
+
+        Ok(Self {
+            proof,
+            htm: parts.method.as_str().to_string(),
+            htu: format!("{}{}", Issuer::default().as_ref(), parts.uri.path()),
+        })
+    }
+}
+
+/// Access token presented in the `Authorization` header,
+/// either as `Bearer` or as `DPoP` along with the proof of its key.
+///
+/// See [RFC9449 Section 7.1](https://datatracker.ietf.org/doc/html/rfc9449#section-7.1)
+pub struct DPoPToken {
+    pub scheme: TokenScheme,
+    pub token: String,
+    pub proof: Option<DPoPProofDto>,
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for DPoPToken
+where
+    S: Sync + Send,
+{
+    type Rejection = ServerError;
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let dpop = DPoPHeader::from_request_parts(parts, state).await?;
+
+        let Some((scheme, token)) = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok())
+            .and_then(|authorization| authorization.split_once(' '))
+        else {
+            return Err(ServerError::MissingToken);
+        };
+        let token = token.trim().to_string();
+
+        if scheme.eq_ignore_ascii_case("Bearer") {
+            return Ok(Self {
+                scheme: TokenScheme::Bearer,
+                token,
+                proof: None,
+            });
+        }
+
+        if !scheme.eq_ignore_ascii_case("DPoP") {
+            return Err(ServerError::MissingToken);
+        }
+
+        let Some(proof) = dpop.into_dto(Some(token.clone())) else {
+            return Err(ServerError::OAuth {
+                error: "invalid_dpop_proof",
+                value: "`DPoP` header is required.".to_string(),
+            });
+        };
+
+        Ok(Self {
+            scheme: TokenScheme::DPoP,
+            token,
+            proof: Some(proof),
+        })
+    }
+}
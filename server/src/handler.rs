@@ -1,36 +1,56 @@
 use application::{
     interactor::{RegisterClientInteractor, UpdateClientInteractor},
     services::{
-        DependOnAcceptAuthorizeTokenService, DependOnCreateAccountService,
-        DependOnCreateNonVerifiedAccountService, DependOnDeleteAccountService,
-        DependOnPendingAuthorizeTokenService, DependOnRegisterClientService,
-        DependOnRejectAuthorizeTokenService, DependOnUpdateAccountService,
-        DependOnUpdateClientService, DependOnVerifyAccountService, DependOnVerifyMFACodeService,
+        DependOnAcceptAuthorizeTokenService, DependOnAuthenticateClientService,
+        DependOnClientCredentialsService, DependOnCreateAccessTokenService,
+        DependOnCreateAccountService, DependOnCreateNonVerifiedAccountService, DependOnDPoPService,
+        DependOnDeleteAccountService, DependOnDeviceAccessTokenService,
+        DependOnDeviceAuthorizationService, DependOnGetJwkSetService,
+        DependOnIntrospectTokenService, DependOnJwtBearerService,
+        DependOnPendingAuthorizeTokenService, DependOnPushAuthorizationRequestService,
+        DependOnRefreshAccessTokenService, DependOnRegisterClientService,
+        DependOnRejectAuthorizeTokenService, DependOnRevokeTokenService,
+        DependOnRotateSigningKeyService, DependOnSaml2BearerService, DependOnTokenExchangeService,
+        DependOnUpdateAccountService, DependOnUpdateClientService, DependOnUserInfoService,
+        DependOnVerifyAccountService, DependOnVerifyMFACodeService, DependOnVerifyUserCodeService,
     },
 };
 use kernel::interfaces::{
     repository::{
-        DependOnAcceptedActionVolatileRepository, DependOnAccountRepository,
-        DependOnAuthorizeTokenRepository, DependOnClientRegistry,
-        DependOnMFACodeVolatileRepository, DependOnPKCEVolatileRepository,
-        DependOnPendingActionVolatileRepository, DependOnPendingAuthorizeTokenRepository,
-        DependOnSessionVolatileRepository, DependOnStateVolatileRepository,
-        DependOnTemporaryAccountRepository,
+        DependOnAcceptedActionVolatileRepository, DependOnAccessTokenRepository,
+        DependOnAccountRepository, DependOnAuthorizeTokenRepository, DependOnClientRegistry,
+        DependOnDPoPJtiVolatileRepository, DependOnDPoPNonceVolatileRepository,
+        DependOnDeviceAuthorizationVolatileRepository, DependOnJtiVolatileRepository,
+        DependOnJwtBearerJtiVolatileRepository, DependOnMFACodeVolatileRepository,
+        DependOnPKCEVolatileRepository, DependOnPendingActionVolatileRepository,
+        DependOnPendingAuthorizeTokenRepository, DependOnProtectedResourceRepository,
+        DependOnPushedAuthorizationRequestVolatileRepository, DependOnRefreshTokenRepository,
+        DependOnSamlAssertionIdVolatileRepository, DependOnSessionVolatileRepository,
+        DependOnSigningKeyRepository, DependOnStateVolatileRepository,
+        DependOnTemporaryAccountRepository, DependOnTrustedIssuerRepository,
+    },
+    transport::{
+        DependOnJwksTransporter, DependOnRequestObjectTransporter,
+        DependOnVerificationMailTransporter,
     },
-    transport::DependOnVerificationMailTransporter,
 };
 
+use crate::extract::certificate::client_certificate_header;
 use crate::ServerError;
 #[allow(unused_imports)]
 use driver::{
     database::{
-        AcceptedActionVolatileDataBase, AccountDataBase, AuthorizeTokenVolatileDataBase,
-        ClientDataBase, MFACodeVolatileDataBase, NonVerifiedAccountDataBase, PKCEVolatileDataBase,
-        PendingActionVolatileDataBase, PendingAuthorizeTokenVolatileDataBase,
-        SessionVolatileDataBase, StateVolatileDataBase,
+        AcceptedActionVolatileDataBase, AccessTokenDataBase, AccountDataBase,
+        AuthorizeTokenVolatileDataBase, ClientDataBase, DPoPJtiVolatileDataBase,
+        DPoPNonceVolatileDataBase, DeviceAuthorizationVolatileDataBase, JtiVolatileDataBase,
+        JwtBearerJtiVolatileDataBase, MFACodeVolatileDataBase, NonVerifiedAccountDataBase,
+        PKCEVolatileDataBase, PendingActionVolatileDataBase, PendingAuthorizeTokenVolatileDataBase,
+        PushedAuthorizationRequestVolatileDataBase, RefreshTokenDataBase,
+        SamlAssertionIdVolatileDataBase, SessionVolatileDataBase, SigningKeyDataBase,
+        StateVolatileDataBase,
     },
-    transport::VerificationMailer,
-    DataBaseDriver, SmtpDriver,
+    transport::{JwkSetFetcher, RequestObjectFetcher, VerificationMailer},
+    DataBaseDriver, ProtectedResourceConfig, SmtpDriver, TrustedIssuerConfig,
 };
 
 #[cfg(debug_assertions)]
@@ -42,6 +62,11 @@ type ClientRegisterer = RegisterClientInteractor<ClientDataBase, AccountDataBase
 pub struct Handler {
     ac_repo: AccountDataBase,
     clients: ClientDataBase,
+    access_tokens: AccessTokenDataBase,
+    refresh_tokens: RefreshTokenDataBase,
+    signing_keys: SigningKeyDataBase,
+    trusted_issuers: TrustedIssuerConfig,
+    protected_resources: ProtectedResourceConfig,
 
     nvac_repo: NonVerifiedAccountDataBase,
     p_authz_v_repo: PendingAuthorizeTokenVolatileDataBase,
@@ -52,6 +77,16 @@ pub struct Handler {
     session_v_repo: SessionVolatileDataBase,
     pending_action_v_repo: PendingActionVolatileDataBase,
     accepted_action_v_repo: AcceptedActionVolatileDataBase,
+    jti_v_repo: JtiVolatileDataBase,
+    device_v_repo: DeviceAuthorizationVolatileDataBase,
+    par_v_repo: PushedAuthorizationRequestVolatileDataBase,
+    dpop_jti_v_repo: DPoPJtiVolatileDataBase,
+    dpop_nonce_v_repo: DPoPNonceVolatileDataBase,
+    jwt_bearer_jti_v_repo: JwtBearerJtiVolatileDataBase,
+    saml_id_v_repo: SamlAssertionIdVolatileDataBase,
+
+    jwks_fetcher: JwkSetFetcher,
+    request_object_fetcher: RequestObjectFetcher,
 
     #[cfg(not(debug_assertions))]
     mailer: VerificationMailer,
@@ -69,11 +104,18 @@ impl Handler {
         let pg_pool = DataBaseDriver::setup_postgres().await?;
         let redis_pool = DataBaseDriver::setup_redis().await?;
 
+        client_certificate_header()?;
+
         #[cfg(not(debug_assertions))]
         let smtp_pool = SmtpDriver::setup_lettre()?;
 
         let ac_repo = AccountDataBase::new(pg_pool.clone());
-        let clients = ClientDataBase::new(pg_pool);
+        let clients = ClientDataBase::new(pg_pool.clone());
+        let access_tokens = AccessTokenDataBase::new(pg_pool.clone());
+        let refresh_tokens = RefreshTokenDataBase::new(pg_pool.clone());
+        let signing_keys = SigningKeyDataBase::new(pg_pool)?;
+        let trusted_issuers = TrustedIssuerConfig::load()?;
+        let protected_resources = ProtectedResourceConfig::load()?;
 
         let nvac_repo = NonVerifiedAccountDataBase::new(redis_pool.clone());
         let p_authz_v_repo = PendingAuthorizeTokenVolatileDataBase::new(redis_pool.clone());
@@ -83,7 +125,17 @@ impl Handler {
         let mfa_code_v_repo = MFACodeVolatileDataBase::new(redis_pool.clone());
         let session_v_repo = SessionVolatileDataBase::new(redis_pool.clone());
         let pending_action_v_repo = PendingActionVolatileDataBase::new(redis_pool.clone());
-        let accepted_action_v_repo = AcceptedActionVolatileDataBase::new(redis_pool);
+        let accepted_action_v_repo = AcceptedActionVolatileDataBase::new(redis_pool.clone());
+        let jti_v_repo = JtiVolatileDataBase::new(redis_pool.clone());
+        let device_v_repo = DeviceAuthorizationVolatileDataBase::new(redis_pool.clone());
+        let par_v_repo = PushedAuthorizationRequestVolatileDataBase::new(redis_pool.clone());
+        let dpop_jti_v_repo = DPoPJtiVolatileDataBase::new(redis_pool.clone());
+        let dpop_nonce_v_repo = DPoPNonceVolatileDataBase::new(redis_pool.clone());
+        let jwt_bearer_jti_v_repo = JwtBearerJtiVolatileDataBase::new(redis_pool.clone());
+        let saml_id_v_repo = SamlAssertionIdVolatileDataBase::new(redis_pool);
+
+        let jwks_fetcher = JwkSetFetcher::default();
+        let request_object_fetcher = RequestObjectFetcher::default();
 
         #[cfg(not(debug_assertions))]
         let mailer = VerificationMailer::new(smtp_pool);
@@ -97,6 +149,11 @@ impl Handler {
         Ok(Self {
             ac_repo,
             clients,
+            access_tokens,
+            refresh_tokens,
+            signing_keys,
+            trusted_issuers,
+            protected_resources,
 
             nvac_repo,
             p_authz_v_repo,
@@ -107,6 +164,16 @@ impl Handler {
             session_v_repo,
             pending_action_v_repo,
             accepted_action_v_repo,
+            jti_v_repo,
+            device_v_repo,
+            par_v_repo,
+            dpop_jti_v_repo,
+            dpop_nonce_v_repo,
+            jwt_bearer_jti_v_repo,
+            saml_id_v_repo,
+
+            jwks_fetcher,
+            request_object_fetcher,
 
             mailer,
 
@@ -132,6 +199,98 @@ impl DependOnClientRegistry for Handler {
     }
 }
 
+impl DependOnJtiVolatileRepository for Handler {
+    type JtiVolatileRepository = JtiVolatileDataBase;
+
+    fn jti_volatile_repository(&self) -> &Self::JtiVolatileRepository {
+        &self.jti_v_repo
+    }
+}
+
+impl DependOnDPoPJtiVolatileRepository for Handler {
+    type DPoPJtiVolatileRepository = DPoPJtiVolatileDataBase;
+
+    fn dpop_jti_volatile_repository(&self) -> &Self::DPoPJtiVolatileRepository {
+        &self.dpop_jti_v_repo
+    }
+}
+
+impl DependOnJwtBearerJtiVolatileRepository for Handler {
+    type JwtBearerJtiVolatileRepository = JwtBearerJtiVolatileDataBase;
+
+    fn jwt_bearer_jti_volatile_repository(&self) -> &Self::JwtBearerJtiVolatileRepository {
+        &self.jwt_bearer_jti_v_repo
+    }
+}
+
+impl DependOnSamlAssertionIdVolatileRepository for Handler {
+    type SamlAssertionIdVolatileRepository = SamlAssertionIdVolatileDataBase;
+
+    fn saml_assertion_id_volatile_repository(&self) -> &Self::SamlAssertionIdVolatileRepository {
+        &self.saml_id_v_repo
+    }
+}
+
+impl DependOnTrustedIssuerRepository for Handler {
+    type TrustedIssuerRepository = TrustedIssuerConfig;
+
+    fn trusted_issuer_repository(&self) -> &Self::TrustedIssuerRepository {
+        &self.trusted_issuers
+    }
+}
+
+impl DependOnProtectedResourceRepository for Handler {
+    type ProtectedResourceRepository = ProtectedResourceConfig;
+
+    fn protected_resource_repository(&self) -> &Self::ProtectedResourceRepository {
+        &self.protected_resources
+    }
+}
+
+impl DependOnDPoPNonceVolatileRepository for Handler {
+    type DPoPNonceVolatileRepository = DPoPNonceVolatileDataBase;
+
+    fn dpop_nonce_volatile_repository(&self) -> &Self::DPoPNonceVolatileRepository {
+        &self.dpop_nonce_v_repo
+    }
+}
+
+impl DependOnDeviceAuthorizationVolatileRepository for Handler {
+    type DeviceAuthorizationVolatileRepository = DeviceAuthorizationVolatileDataBase;
+
+    fn device_authorization_volatile_repository(
+        &self,
+    ) -> &Self::DeviceAuthorizationVolatileRepository {
+        &self.device_v_repo
+    }
+}
+
+impl DependOnPushedAuthorizationRequestVolatileRepository for Handler {
+    type PushedAuthorizationRequestVolatileRepository = PushedAuthorizationRequestVolatileDataBase;
+
+    fn pushed_authorization_request_volatile_repository(
+        &self,
+    ) -> &Self::PushedAuthorizationRequestVolatileRepository {
+        &self.par_v_repo
+    }
+}
+
+impl DependOnJwksTransporter for Handler {
+    type JwksTransporter = JwkSetFetcher;
+
+    fn jwks_transporter(&self) -> &Self::JwksTransporter {
+        &self.jwks_fetcher
+    }
+}
+
+impl DependOnRequestObjectTransporter for Handler {
+    type RequestObjectTransporter = RequestObjectFetcher;
+
+    fn request_object_transporter(&self) -> &Self::RequestObjectTransporter {
+        &self.request_object_fetcher
+    }
+}
+
 impl DependOnTemporaryAccountRepository for Handler {
     type TemporaryAccountRepository = NonVerifiedAccountDataBase;
 
@@ -196,6 +355,20 @@ impl DependOnAuthorizeTokenRepository for Handler {
     }
 }
 
+impl DependOnAccessTokenRepository for Handler {
+    type AccessTokenRepository = AccessTokenDataBase;
+    fn access_token_repository(&self) -> &Self::AccessTokenRepository {
+        &self.access_tokens
+    }
+}
+
+impl DependOnRefreshTokenRepository for Handler {
+    type RefreshTokenRepository = RefreshTokenDataBase;
+    fn refresh_token_repository(&self) -> &Self::RefreshTokenRepository {
+        &self.refresh_tokens
+    }
+}
+
 impl DependOnCreateNonVerifiedAccountService for Handler {
     type CreateNonVerifiedAccountService = Self;
 
@@ -280,6 +453,13 @@ impl DependOnPendingAuthorizeTokenService for Handler {
     }
 }
 
+impl DependOnPushAuthorizationRequestService for Handler {
+    type PushAuthorizationRequestService = Self;
+    fn push_authorization_request_service(&self) -> &Self::PushAuthorizationRequestService {
+        self
+    }
+}
+
 impl DependOnAcceptAuthorizeTokenService for Handler {
     type AcceptAuthorizeTokenService = Self;
     fn accept_authorize_token_service(&self) -> &Self::AcceptAuthorizeTokenService {
@@ -294,6 +474,126 @@ impl DependOnRejectAuthorizeTokenService for Handler {
     }
 }
 
+impl DependOnCreateAccessTokenService for Handler {
+    type CreateAccessTokenService = Self;
+    fn create_access_token_service(&self) -> &Self::CreateAccessTokenService {
+        self
+    }
+}
+
+impl DependOnRefreshAccessTokenService for Handler {
+    type RefreshAccessTokenService = Self;
+    fn refresh_access_token_service(&self) -> &Self::RefreshAccessTokenService {
+        self
+    }
+}
+
+impl DependOnAuthenticateClientService for Handler {
+    type AuthenticateClientService = Self;
+    fn authenticate_client_service(&self) -> &Self::AuthenticateClientService {
+        self
+    }
+}
+
+impl DependOnClientCredentialsService for Handler {
+    type ClientCredentialsService = Self;
+    fn client_credentials_service(&self) -> &Self::ClientCredentialsService {
+        self
+    }
+}
+
+impl DependOnJwtBearerService for Handler {
+    type JwtBearerService = Self;
+    fn jwt_bearer_service(&self) -> &Self::JwtBearerService {
+        self
+    }
+}
+
+impl DependOnSaml2BearerService for Handler {
+    type Saml2BearerService = Self;
+    fn saml2_bearer_service(&self) -> &Self::Saml2BearerService {
+        self
+    }
+}
+
+impl DependOnTokenExchangeService for Handler {
+    type TokenExchangeService = Self;
+    fn token_exchange_service(&self) -> &Self::TokenExchangeService {
+        self
+    }
+}
+
+impl DependOnIntrospectTokenService for Handler {
+    type IntrospectTokenService = Self;
+    fn introspect_token_service(&self) -> &Self::IntrospectTokenService {
+        self
+    }
+}
+
+impl DependOnDPoPService for Handler {
+    type DPoPService = Self;
+    fn dpop_service(&self) -> &Self::DPoPService {
+        self
+    }
+}
+
+impl DependOnUserInfoService for Handler {
+    type UserInfoService = Self;
+    fn userinfo_service(&self) -> &Self::UserInfoService {
+        self
+    }
+}
+
+impl DependOnRevokeTokenService for Handler {
+    type RevokeTokenService = Self;
+    fn revoke_token_service(&self) -> &Self::RevokeTokenService {
+        self
+    }
+}
+
+impl DependOnDeviceAuthorizationService for Handler {
+    type DeviceAuthorizationService = Self;
+    fn device_authorization_service(&self) -> &Self::DeviceAuthorizationService {
+        self
+    }
+}
+
+impl DependOnVerifyUserCodeService for Handler {
+    type VerifyUserCodeService = Self;
+    fn verify_user_code_service(&self) -> &Self::VerifyUserCodeService {
+        self
+    }
+}
+
+impl DependOnDeviceAccessTokenService for Handler {
+    type DeviceAccessTokenService = Self;
+    fn device_access_token_service(&self) -> &Self::DeviceAccessTokenService {
+        self
+    }
+}
+
+impl DependOnSigningKeyRepository for Handler {
+    type SigningKeyRepository = SigningKeyDataBase;
+
+    fn signing_key_repository(&self) -> &Self::SigningKeyRepository {
+        &self.signing_keys
+    }
+}
+
+impl DependOnRotateSigningKeyService for Handler {
+    type RotateSigningKeyService = Self;
+    fn rotate_signing_key_service(&self) -> &Self::RotateSigningKeyService {
+        self
+    }
+}
+
+impl DependOnGetJwkSetService for Handler {
+    type GetJwkSetService = Self;
+    fn get_jwk_set_service(&self) -> &Self::GetJwkSetService {
+        self
+    }
+}
+
 #[cfg(debug_assertions)]
 mod mock {
     use axum::async_trait;
@@ -1,3 +1,4 @@
+use application::services::{DependOnRotateSigningKeyService, RotateSigningKeyService};
 use axum::{
     http::StatusCode,
     response::IntoResponse,
@@ -5,7 +6,11 @@ use axum::{
     Router,
 };
 use server::{
-    routes::{authorization, decision, login, signup, stellar_info, verify},
+    routes::{
+        authorization, authorization_server_metadata, decision, device_authorization, endpoints,
+        introspect, jwks, login, openid_configuration, push_authorization_request, revoke, signup,
+        stellar_info, token, userinfo, verify, verify_user_code,
+    },
     Handler,
 };
 use std::net::SocketAddr;
@@ -30,16 +35,35 @@ async fn main() -> anyhow::Result<()> {
 
     let handler = Handler::init().await?;
 
+    handler.rotate_signing_key_service().rotate().await?;
+    tokio::spawn(rotate_signing_keys(handler.clone()));
+
     let statics = Router::new()
-        .route("/.well-known", get(|| async { todo!() }))
-        .route("/hc", get(healthcheck));
+        .route(endpoints::JWKS, get(jwks))
+        .route(
+            endpoints::AUTHORIZATION_SERVER_METADATA,
+            get(authorization_server_metadata),
+        )
+        .route(endpoints::OPENID_CONFIGURATION, get(openid_configuration))
+        .route("/hc", get(healthcheck))
+        .route(endpoints::TOKEN, post(token))
+        .route(endpoints::INTROSPECTION, post(introspect))
+        .route(endpoints::REVOCATION, post(revoke))
+        .route(endpoints::USERINFO, get(userinfo).post(userinfo))
+        .route(endpoints::DEVICE_AUTHORIZATION, post(device_authorization))
+        .route(endpoints::DEVICE_VERIFICATION, get(verify_user_code))
+        .route(
+            endpoints::PUSHED_AUTHORIZATION_REQUEST,
+            post(push_authorization_request),
+        )
+        .route(
+            endpoints::AUTHORIZATION,
+            get(authorization)
+                .patch(decision::accept)
+                .delete(decision::reject),
+        );
 
-    let clients = Router::new().route("/stellar", get(stellar_info)).route(
-        "/authorize",
-        get(authorization)
-            .patch(decision::accept)
-            .delete(decision::reject),
-    );
+    let clients = Router::new().route("/stellar", get(stellar_info));
 
     let accounts = Router::new()
         .route("/login", post(login))
@@ -72,6 +96,17 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Checks hourly whether the signing key is due for rotation.
+async fn rotate_signing_keys(handler: Handler) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+    loop {
+        interval.tick().await;
+        if let Err(e) = handler.rotate_signing_key_service().rotate().await {
+            tracing::error!("failed to rotate signing keys: {:?}", e);
+        }
+    }
+}
+
 async fn healthcheck() -> impl IntoResponse {
     StatusCode::OK
 }
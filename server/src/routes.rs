@@ -1,7 +1,10 @@
+pub mod endpoints;
+
 mod infos;
+mod well_known;
 
 mod account;
 mod auth;
 mod client;
 
-pub use self::{account::*, auth::*, client::*, infos::*};
+pub use self::{account::*, auth::*, client::*, infos::*, well_known::*};
@@ -1,5 +1,6 @@
 mod login;
 mod signup;
+mod userinfo;
 mod verify;
 
-pub use self::{login::*, signup::*, verify::*};
+pub use self::{login::*, signup::*, userinfo::*, verify::*};
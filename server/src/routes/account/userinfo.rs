@@ -0,0 +1,58 @@
+use crate::extract::certificate::ClientCertificateHeader;
+use crate::extract::dpop::DPoPToken;
+use crate::{Handler, ServerError};
+use application::services::{DependOnUserInfoService, UserInfoService};
+use application::transfer::account::UserInfoDto;
+use axum::{extract::State, response::IntoResponse, Json};
+use serde::Serialize;
+
+/// UserInfo Endpoint, accepting both `GET` and `POST`.
+///
+/// See [OpenID Connect Core 1.0 Section 5.3](https://openid.net/specs/openid-connect-core-1_0.html#UserInfo)
+pub async fn userinfo(
+    State(handler): State<Handler>,
+    DPoPToken {
+        scheme,
+        token,
+        proof,
+    }: DPoPToken,
+    certificate: ClientCertificateHeader,
+) -> Result<impl IntoResponse, ServerError> {
+    let x5t_s256 = certificate
+        .thumbprint()
+        .map_err(|e| ServerError::InvalidToken {
+            scheme,
+            value: e.to_string(),
+        })?;
+    let info = handler
+        .userinfo_service()
+        .userinfo(&token, proof, x5t_s256)
+        .await
+        .map_err(|e| ServerError::from(e).with_scheme(scheme))?;
+    Ok(Json(UserInfoResponse::from(info)))
+}
+
+#[derive(Serialize, Debug)]
+pub struct UserInfoResponse {
+    pub sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+impl From<UserInfoDto> for UserInfoResponse {
+    fn from(value: UserInfoDto) -> Self {
+        Self {
+            sub: value.sub,
+            name: value.name,
+            preferred_username: value.preferred_username,
+            email: value.email,
+            email_verified: value.email_verified,
+        }
+    }
+}
@@ -1,6 +1,10 @@
 mod access;
 mod authorize;
+mod device;
+mod introspection;
+mod par;
+mod revocation;
 
 pub mod decision;
 
-pub use self::authorize::*;
+pub use self::{access::*, authorize::*, device::*, introspection::*, par::*, revocation::*};
@@ -1 +1,243 @@
-// Todo: Impl Access Token Exchange.
+use crate::extract::client::AuthenticatedClient;
+use crate::extract::dpop::DPoPHeader;
+use crate::{Handler, ServerError};
+use application::services::{
+    ClientCredentialsService, CreateAccessTokenService, DPoPService,
+    DependOnClientCredentialsService, DependOnCreateAccessTokenService, DependOnDPoPService,
+    DependOnDeviceAccessTokenService, DependOnJwtBearerService, DependOnRefreshAccessTokenService,
+    DependOnSaml2BearerService, DependOnTokenExchangeService, DeviceAccessTokenService,
+    JwtBearerService, RefreshAccessTokenService, Saml2BearerService, TokenExchangeService,
+};
+use application::transfer::token::{
+    AccessTokenDto, ClientCredentialsDto, CreateAccessTokenDto, DeviceAccessTokenDto, JwtBearerDto,
+    RefreshAccessTokenDto, Saml2BearerDto, TokenExchangeDto,
+};
+use axum::{
+    extract::State,
+    http::{
+        header::{CACHE_CONTROL, PRAGMA},
+        HeaderMap, HeaderValue,
+    },
+    response::IntoResponse,
+    Json,
+};
+use kernel::prelude::entities::GrantType;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Token Endpoint.
+///
+/// A `DPoP` proof, if presented, binds the issued access token to its key.
+///
+/// Defined in [RFC6749 Section 3.2](https://datatracker.ietf.org/doc/html/rfc6749#section-3.2)
+/// and [RFC9449 Section 5](https://datatracker.ietf.org/doc/html/rfc9449#section-5)
+pub async fn token(
+    State(handler): State<Handler>,
+    dpop: DPoPHeader,
+    AuthenticatedClient {
+        client_id,
+        form,
+        x5t_s256,
+    }: AuthenticatedClient<AccessTokenRequest>,
+) -> Result<impl IntoResponse, ServerError> {
+    let grant_type = GrantType::from_str(&form.grant_type)
+        .ok()
+        .filter(|ty| GrantType::SUPPORTED.contains(ty))
+        .ok_or_else(|| ServerError::InvalidValue {
+            method: "unsupported_grant_type",
+            value: form.grant_type.clone(),
+        })?;
+
+    let dpop_jkt = match dpop.into_dto(None) {
+        Some(proof) => Some(handler.dpop_service().verify_dpop_proof(proof).await?),
+        None => None,
+    };
+
+    let token = match grant_type {
+        GrantType::AuthorizationCode => {
+            let create = CreateAccessTokenDto {
+                code: required(form.code, "code")?,
+                redirect_uri: form.redirect_uri,
+                client_id,
+                code_verifier: required(form.code_verifier, "code_verifier")?,
+                resource: form.resource,
+                authorization_details: form.authorization_details,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler.create_access_token_service().create(create).await?
+        }
+        GrantType::RefreshToken => {
+            let refresh = RefreshAccessTokenDto {
+                refresh_token: required(form.refresh_token, "refresh_token")?,
+                client_id,
+                scope: form
+                    .scope
+                    .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect()),
+                resource: form.resource,
+                authorization_details: form.authorization_details,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler
+                .refresh_access_token_service()
+                .refresh(refresh)
+                .await?
+        }
+        GrantType::ClientCredentials => {
+            let issue = ClientCredentialsDto {
+                client_id,
+                scope: form
+                    .scope
+                    .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect()),
+                resource: form.resource,
+                authorization_details: form.authorization_details,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler.client_credentials_service().issue(issue).await?
+        }
+        GrantType::DeviceCode => {
+            let exchange = DeviceAccessTokenDto {
+                device_code: required(form.device_code, "device_code")?,
+                client_id,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler
+                .device_access_token_service()
+                .exchange_device_code(exchange)
+                .await?
+        }
+        GrantType::JWTBearer => {
+            let issue = JwtBearerDto {
+                client_id,
+                assertion: required(form.assertion, "assertion")?,
+                scope: form
+                    .scope
+                    .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect()),
+                resource: form.resource,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler
+                .jwt_bearer_service()
+                .issue_by_assertion(issue)
+                .await?
+        }
+        GrantType::Saml2Bearer => {
+            let issue = Saml2BearerDto {
+                client_id,
+                assertion: required(form.assertion, "assertion")?,
+                scope: form
+                    .scope
+                    .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect()),
+                resource: form.resource,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler
+                .saml2_bearer_service()
+                .issue_by_saml_assertion(issue)
+                .await?
+        }
+        GrantType::TokenExchange => {
+            let exchange = TokenExchangeDto {
+                client_id,
+                subject_token: required(form.subject_token, "subject_token")?,
+                subject_token_type: required(form.subject_token_type, "subject_token_type")?,
+                actor_token: form.actor_token,
+                actor_token_type: form.actor_token_type,
+                resource: form.resource,
+                audience: form.audience,
+                scope: form
+                    .scope
+                    .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect()),
+                requested_token_type: form.requested_token_type,
+                dpop_jkt,
+                x5t_s256,
+            };
+            handler.token_exchange_service().exchange(exchange).await?
+        }
+        other => {
+            return Err(ServerError::InvalidValue {
+                method: "unsupported_grant_type",
+                value: other.as_ref().to_string(),
+            })
+        }
+    };
+
+    // https://datatracker.ietf.org/doc/html/rfc6749#section-5.1
+    let mut headers = HeaderMap::new();
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(PRAGMA, HeaderValue::from_static("no-cache"));
+
+    Ok((headers, Json(AccessTokenResponse::from(token))))
+}
+
+pub(super) fn required<T>(value: Option<T>, name: &'static str) -> Result<T, ServerError> {
+    value.ok_or_else(|| ServerError::InvalidValue {
+        method: "invalid_request",
+        value: format!("`{}` is required.", name),
+    })
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AccessTokenRequest {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+    pub device_code: Option<String>,
+    // RFC7523 §2.1 Using JWTs as Authorization Grants
+    // RFC7522 §2.1 Using SAML Assertions as Authorization Grants
+    pub assertion: Option<String>,
+    // RFC8693 §2.1 Token Exchange Request
+    pub subject_token: Option<String>,
+    pub subject_token_type: Option<String>,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    // RFC8707 §2.2 Access Token Request, also used by the token exchange
+    pub resource: Option<String>,
+    pub audience: Option<String>,
+    pub requested_token_type: Option<String>,
+    // RFC9396 §6 Token Request
+    pub authorization_details: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AccessTokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_token_type: Option<String>,
+    /// See [RFC9396 Section 7](https://datatracker.ietf.org/doc/html/rfc9396#section-7)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_details: Option<serde_json::Value>,
+}
+
+impl From<AccessTokenDto> for AccessTokenResponse {
+    fn from(value: AccessTokenDto) -> Self {
+        Self {
+            access_token: value.access_token,
+            token_type: value.token_type,
+            expires_in: value.expires_in,
+            refresh_token: value.refresh_token,
+            scope: (!value.scope.is_empty()).then(|| value.scope.join(" ")),
+            id_token: value.id_token,
+            issued_token_type: value.issued_token_type,
+            authorization_details: value
+                .authorization_details
+                .and_then(|details| serde_json::from_str(&details).ok()),
+        }
+    }
+}
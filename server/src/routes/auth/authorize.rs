@@ -1,19 +1,24 @@
+use super::access::required;
 use crate::{Handler, ServerError};
 use application::services::{DependOnPendingAuthorizeTokenService, PendingAuthorizeTokenService};
-use application::transfer::token::CreateAuthorizeTokenDto;
+use application::transfer::token::{
+    AuthorizationParamsDto, CreateAuthorizeTokenDto, PushedAuthorizeTokenDto,
+    SignedAuthorizeTokenDto,
+};
 use axum::{
     extract::{Query, State},
     response::IntoResponse,
     Json,
 };
 use kernel::external::Uuid;
+use kernel::prelude::entities::RequestUri;
 use serde::{Deserialize, Deserializer};
 
 pub async fn authorization(
     State(handler): State<Handler>,
-    Query(query): Query<AuthorizationGrantQuery>,
+    Query(query): Query<AuthorizationQuery>,
 ) -> Result<impl IntoResponse, ServerError> {
-    let AuthorizationGrantQuery {
+    let AuthorizationQuery {
         response_type,
         client_id,
         redirect_uri,
@@ -21,45 +26,124 @@ pub async fn authorization(
         state,
         code_challenge,
         code_challenge_method,
+        nonce,
+        response_mode,
+        resource,
+        authorization_details,
+        request,
+        request_uri,
     } = query;
 
     let client_id = Uuid::parse_str(&client_id)?;
 
-    let ticket = handler
-        .pending_authorize_token_service()
-        .pending(CreateAuthorizeTokenDto {
-            response_type,
-            client_id,
-            redirect_uri,
-            scope,
-            state,
-            code_challenge,
-            code_challenge_method,
-        })
-        .await?;
+    let pending = match (request, request_uri) {
+        // https://datatracker.ietf.org/doc/html/rfc9126#section-4
+        (None, Some(request_uri)) if request_uri.starts_with(RequestUri::PREFIX) => {
+            handler
+                .pending_authorize_token_service()
+                .pending_pushed(PushedAuthorizeTokenDto {
+                    client_id,
+                    request_uri,
+                })
+                .await?
+        }
+        (None, None) => {
+            handler
+                .pending_authorize_token_service()
+                .pending(CreateAuthorizeTokenDto {
+                    response_type: required(response_type, "response_type")?,
+                    client_id,
+                    redirect_uri,
+                    scope: required(scope, "scope")?,
+                    state: required(state, "state")?,
+                    code_challenge: required(code_challenge, "code_challenge")?,
+                    code_challenge_method: required(
+                        code_challenge_method,
+                        "code_challenge_method",
+                    )?,
+                    nonce,
+                    response_mode,
+                    resource,
+                    authorization_details,
+                })
+                .await?
+        }
+        // https://datatracker.ietf.org/doc/html/rfc9101#section-5
+        (request, request_uri) => {
+            handler
+                .pending_authorize_token_service()
+                .pending_signed(SignedAuthorizeTokenDto {
+                    client_id,
+                    request,
+                    request_uri,
+                    params: AuthorizationParamsDto {
+                        response_type,
+                        redirect_uri,
+                        scope,
+                        state,
+                        code_challenge,
+                        code_challenge_method,
+                        nonce,
+                        response_mode,
+                        resource,
+                        authorization_details,
+                    },
+                })
+                .await?
+        }
+    };
+
+    // What the user is asked to consent to.
     let value = serde_json::json!({
-        "ticket": ticket.0
+        "ticket": pending.ticket,
+        "client_id": pending.client_id,
+        "scope": pending.scope.join(" "),
+        "authorization_details": pending
+            .authorization_details
+            .and_then(|details| serde_json::from_str::<serde_json::Value>(&details).ok()),
     });
     Ok(Json(value))
 }
 
+/// Parameters are given as is, pushed beforehand and referred to by `request_uri`,
+/// or carried by a request object given by value (`request`) or by reference (`request_uri`).
 #[allow(unused)]
 #[derive(Deserialize, Debug)]
-pub struct AuthorizationGrantQuery {
-    pub response_type: String,
+pub struct AuthorizationQuery {
+    pub response_type: Option<String>,
     pub client_id: String,
     pub redirect_uri: Option<String>,
-    #[serde(deserialize_with = "scope_deserializer")]
-    pub scope: Vec<String>,
-    pub state: String,
-    pub code_challenge: String,
-    pub code_challenge_method: String,
+    #[serde(default, deserialize_with = "optional_scope_deserializer")]
+    pub scope: Option<Vec<String>>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    /// Echoed back in the ID Token of an OpenID Connect request.
+    ///
+    /// See [OpenID Connect Core 1.0 Section 3.1.2.1](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest)
+    pub nonce: Option<String>,
+    /// How the authorization response is delivered to `redirect_uri`.
+    ///
+    /// See [OAuth 2.0 Multiple Response Type Encoding Practices Section 2.1](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html#ResponseModes)
+    /// and [JARM Section 2.3](https://openid.net/specs/oauth-v2-jarm.html#name-response-mode-jwt)
+    pub response_mode: Option<String>,
+    /// Protected resource the access token is requested for.
+    ///
+    /// See [RFC8707 Section 2](https://datatracker.ietf.org/doc/html/rfc8707#section-2)
+    pub resource: Option<String>,
+    /// JSON encoded array of authorization details objects.
+    ///
+    /// See [RFC9396 Section 2](https://datatracker.ietf.org/doc/html/rfc9396#section-2)
+    pub authorization_details: Option<String>,
+    /// See [RFC9101 Section 5.1](https://datatracker.ietf.org/doc/html/rfc9101#section-5.1)
+    pub request: Option<String>,
+    pub request_uri: Option<String>,
 }
 
 /// This function converts a space-delimited string into an array.
 ///
 /// Defined in [RFC6749 Section 3.3 Access Token Scope](https://datatracker.ietf.org/doc/html/rfc6749#section-3.3)
-fn scope_deserializer<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+pub(super) fn scope_deserializer<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -72,6 +156,15 @@ where
     Ok(scopes)
 }
 
+pub(super) fn optional_scope_deserializer<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    scope_deserializer(deserializer).map(Some)
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(dead_code)]
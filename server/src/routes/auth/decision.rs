@@ -3,8 +3,11 @@ use application::services::{
     AcceptAuthorizeTokenService, DependOnAcceptAuthorizeTokenService,
     DependOnRejectAuthorizeTokenService, RejectAuthorizeTokenService,
 };
-use application::transfer::token::AcceptUserFormDto;
+use application::transfer::token::{AcceptUserFormDto, AuthorizationResponseDto, DecisionDto};
 use axum::extract::Query;
+use axum::http::header::{CACHE_CONTROL, LOCATION};
+use axum::response::{Html, Response};
+use axum::Json;
 use axum::{extract::State, http::StatusCode, response::IntoResponse};
 
 use self::forms::*;
@@ -17,26 +20,126 @@ pub async fn accept(
         address: "".to_string(),
         pass: "".to_string(),
     };
-    handler
+    let response = handler
         .accept_authorize_token_service()
         .accept(&query.ticket, &query.state, input)
         .await?;
-    Ok(StatusCode::OK)
+    Ok(Decision::from(response))
 }
 
 pub async fn reject(
     State(handler): State<Handler>,
     Query(query): Query<UserQueryReject>,
 ) -> Result<impl IntoResponse, ServerError> {
-    handler
+    let response = handler
         .reject_authorize_token_service()
         .reject(&query.ticket)
         .await?;
-    Ok(StatusCode::OK)
+    Ok(Decision::from(response))
+}
+
+/// Completes the user's decision,
+/// either with the authorization response to the client
+/// or, for a device authorization request, with the outcome for the user.
+pub struct Decision(DecisionDto);
+
+impl From<DecisionDto> for Decision {
+    fn from(value: DecisionDto) -> Self {
+        Self(value)
+    }
+}
+
+impl IntoResponse for Decision {
+    fn into_response(self) -> Response {
+        match self.0 {
+            DecisionDto::Redirect(response) => {
+                AuthorizationResponse::from(response).into_response()
+            }
+            DecisionDto::Device { approved } => (
+                [(CACHE_CONTROL, "no-store")],
+                Json(DeviceDecisionCompleted { approved }),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// Delivers the authorization response to the redirect uri of the client
+/// in the `response_mode` of the request.
+///
+/// `query` and `fragment` redirect the user agent,
+/// while `form_post` returns a page that posts the parameters on load.
+///
+/// See [OAuth 2.0 Multiple Response Type Encoding Practices Section 2.1](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html#ResponseModes)
+/// and [OAuth 2.0 Form Post Response Mode Section 2](https://openid.net/specs/oauth-v2-form-post-response-mode-1_0.html#FormPostResponseMode)
+pub struct AuthorizationResponse(AuthorizationResponseDto);
+
+impl From<AuthorizationResponseDto> for AuthorizationResponse {
+    fn from(value: AuthorizationResponseDto) -> Self {
+        Self(value)
+    }
+}
+
+impl IntoResponse for AuthorizationResponse {
+    fn into_response(self) -> Response {
+        let AuthorizationResponseDto {
+            redirect_uri,
+            response_mode,
+            params,
+        } = self.0;
+
+        let location = match response_mode.as_str() {
+            "form_post" => {
+                let inputs = params
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            r#"<input type="hidden" name="{}" value="{}"/>"#,
+                            escape_html(name),
+                            escape_html(value)
+                        )
+                    })
+                    .collect::<String>();
+                let page = format!(
+                    r#"<!DOCTYPE html><html><head><title>Submit This Form</title></head><body onload="javascript:document.forms[0].submit()"><form method="post" action="{}">{}</form></body></html>"#,
+                    escape_html(&redirect_uri),
+                    inputs
+                );
+                return ([(CACHE_CONTROL, "no-store")], Html(page)).into_response();
+            }
+            "fragment" => format!("{}#{}", redirect_uri, encode(&params)),
+            // A query component of the registered uri must be retained.
+            // https://datatracker.ietf.org/doc/html/rfc6749#section-3.1.2
+            _ if redirect_uri.contains('?') => format!("{}&{}", redirect_uri, encode(&params)),
+            _ => format!("{}?{}", redirect_uri, encode(&params)),
+        };
+
+        (
+            StatusCode::FOUND,
+            [
+                (LOCATION, location),
+                (CACHE_CONTROL, "no-store".to_string()),
+            ],
+        )
+            .into_response()
+    }
+}
+
+fn encode(params: &[(String, String)]) -> String {
+    serde_urlencoded::to_string(params).expect("pairs of strings are always encodable")
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
 }
 
 mod forms {
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
 
     #[derive(Deserialize)]
     pub struct UserQueryAccept {
@@ -48,4 +151,75 @@ mod forms {
     pub struct UserQueryReject {
         pub ticket: String,
     }
+
+    /// The user may go back to the device, which receives the result by polling.
+    #[derive(Serialize)]
+    pub struct DeviceDecisionCompleted {
+        pub approved: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthorizationResponse, Decision};
+    use application::transfer::token::{AuthorizationResponseDto, DecisionDto};
+    use axum::http::header::LOCATION;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    fn new_response(redirect_uri: &str, response_mode: &str) -> AuthorizationResponse {
+        AuthorizationResponse::from(AuthorizationResponseDto {
+            redirect_uri: redirect_uri.to_string(),
+            response_mode: response_mode.to_string(),
+            params: vec![
+                ("code".to_string(), "SplxlOBeZQQYbYS6WxSbIA".to_string()),
+                ("state".to_string(), "af0ifjsldkj&\"".to_string()),
+            ],
+        })
+    }
+
+    #[test]
+    fn redirect() {
+        let res = new_response("https://client.example.com/cb", "query").into_response();
+        assert_eq!(res.status(), StatusCode::FOUND);
+        assert_eq!(
+            res.headers()[LOCATION],
+            "https://client.example.com/cb?code=SplxlOBeZQQYbYS6WxSbIA&state=af0ifjsldkj%26%22"
+        );
+
+        let res = new_response("https://client.example.com/cb?lang=en", "query").into_response();
+        assert_eq!(
+            res.headers()[LOCATION],
+            "https://client.example.com/cb?lang=en&code=SplxlOBeZQQYbYS6WxSbIA&state=af0ifjsldkj%26%22"
+        );
+
+        let res = new_response("https://client.example.com/cb", "fragment").into_response();
+        assert_eq!(
+            res.headers()[LOCATION],
+            "https://client.example.com/cb#code=SplxlOBeZQQYbYS6WxSbIA&state=af0ifjsldkj%26%22"
+        );
+    }
+
+    #[tokio::test]
+    async fn form_post() -> anyhow::Result<()> {
+        let res = new_response("https://client.example.com/cb", "form_post").into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await?;
+        let page = String::from_utf8(body.to_vec())?;
+        assert!(page.contains(r#"action="https://client.example.com/cb""#));
+        assert!(page.contains(r#"name="state" value="af0ifjsldkj&amp;&quot;""#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn device() -> anyhow::Result<()> {
+        let res = Decision::from(DecisionDto::Device { approved: true }).into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.headers().get(LOCATION).is_none());
+
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await?;
+        assert_eq!(body.as_ref(), br#"{"approved":true}"#);
+        Ok(())
+    }
 }
@@ -0,0 +1,125 @@
+use crate::extract::client::AuthenticatedClient;
+use crate::extract::session::Session;
+use crate::routes::endpoints;
+use crate::{Handler, ServerError};
+use application::services::{
+    DependOnDeviceAuthorizationService, DependOnVerifyUserCodeService, DeviceAuthorizationService,
+    VerifyUserCodeService,
+};
+use application::transfer::token::{
+    DeviceAuthorizationDto, DeviceAuthorizationRequestDto, DeviceDecisionDto, VerifyUserCodeDto,
+};
+use axum::{
+    extract::{Query, State},
+    http::{header::CACHE_CONTROL, HeaderMap, HeaderValue},
+    response::IntoResponse,
+    Json,
+};
+use kernel::external::Uuid;
+use kernel::prelude::entities::Issuer;
+use serde::{Deserialize, Serialize};
+
+/// Device Authorization Endpoint.
+///
+/// Defined in [RFC8628 Section 3.1](https://datatracker.ietf.org/doc/html/rfc8628#section-3.1)
+pub async fn device_authorization(
+    State(handler): State<Handler>,
+    AuthenticatedClient {
+        client_id, form, ..
+    }: AuthenticatedClient<DeviceAuthorizationRequest>,
+) -> Result<impl IntoResponse, ServerError> {
+    let request = DeviceAuthorizationRequestDto {
+        client_id,
+        scope: form
+            .scope
+            .map(|scope| scope.split(' ').map(ToOwned::to_owned).collect()),
+        verification_uri: format!(
+            "{}{}",
+            Issuer::default().as_ref(),
+            endpoints::DEVICE_VERIFICATION
+        ),
+    };
+    let device = handler
+        .device_authorization_service()
+        .authorize_device(request)
+        .await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+
+    Ok((headers, Json(DeviceAuthorizationResponse::from(device))))
+}
+
+/// Where the user enters the user code shown on the device.
+///
+/// The user must be signed in. The response carries the ticket and state
+/// to accept or reject at the authorization decision endpoint.
+///
+/// See [RFC8628 Section 3.3](https://datatracker.ietf.org/doc/html/rfc8628#section-3.3)
+pub async fn verify_user_code(
+    State(handler): State<Handler>,
+    session: Session,
+    Query(query): Query<UserCodeQuery>,
+) -> Result<impl IntoResponse, ServerError> {
+    let verify = VerifyUserCodeDto {
+        user_code: query.user_code,
+        session: session.into(),
+    };
+    let decision = handler
+        .verify_user_code_service()
+        .verify_user_code(verify)
+        .await?;
+    Ok(Json(DeviceDecisionResponse::from(decision)))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeviceAuthorizationRequest {
+    pub scope: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DeviceAuthorizationResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: i64,
+    pub interval: i64,
+}
+
+impl From<DeviceAuthorizationDto> for DeviceAuthorizationResponse {
+    fn from(value: DeviceAuthorizationDto) -> Self {
+        Self {
+            device_code: value.device_code,
+            user_code: value.user_code,
+            verification_uri: value.verification_uri,
+            verification_uri_complete: value.verification_uri_complete,
+            expires_in: value.expires_in,
+            interval: value.interval,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UserCodeQuery {
+    pub user_code: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DeviceDecisionResponse {
+    pub ticket: String,
+    pub state: String,
+    pub client_id: Uuid,
+    pub scope: String,
+}
+
+impl From<DeviceDecisionDto> for DeviceDecisionResponse {
+    fn from(value: DeviceDecisionDto) -> Self {
+        Self {
+            ticket: value.ticket,
+            state: value.state,
+            client_id: value.client_id,
+            scope: value.scope.join(" "),
+        }
+    }
+}
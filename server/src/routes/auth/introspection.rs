@@ -0,0 +1,99 @@
+use crate::extract::client::AuthenticatedClient;
+use crate::{Handler, ServerError};
+use application::services::{DependOnIntrospectTokenService, IntrospectTokenService};
+use application::transfer::token::{IntrospectTokenDto, TokenIntrospectionDto};
+use axum::{extract::State, response::IntoResponse, Json};
+use kernel::external::Uuid;
+use serde::{Deserialize, Serialize};
+
+/// Introspection Endpoint.
+///
+/// Defined in [RFC7662 Section 2](https://datatracker.ietf.org/doc/html/rfc7662#section-2)
+pub async fn introspect(
+    State(handler): State<Handler>,
+    AuthenticatedClient {
+        client_id, form, ..
+    }: AuthenticatedClient<IntrospectionRequest>,
+) -> Result<impl IntoResponse, ServerError> {
+    let introspect = IntrospectTokenDto {
+        client_id,
+        token: form.token,
+        token_type_hint: form.token_type_hint,
+    };
+    let introspected = handler
+        .introspect_token_service()
+        .introspect(introspect)
+        .await?;
+    Ok(Json(IntrospectionResponse::from(introspected)))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IntrospectionRequest {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct IntrospectionResponse {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    /// See [RFC9449 Section 6.2](https://datatracker.ietf.org/doc/html/rfc9449#section-6.2)
+    /// and [RFC8705 Section 3.2](https://datatracker.ietf.org/doc/html/rfc8705#section-3.2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cnf: Option<serde_json::Value>,
+    /// See [RFC8693 Section 4.1](https://datatracker.ietf.org/doc/html/rfc8693#section-4.1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub act: Option<serde_json::Value>,
+    /// See [RFC9396 Section 9.2](https://datatracker.ietf.org/doc/html/rfc9396#section-9.2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_details: Option<serde_json::Value>,
+}
+
+impl From<TokenIntrospectionDto> for IntrospectionResponse {
+    fn from(value: TokenIntrospectionDto) -> Self {
+        Self {
+            active: value.active,
+            scope: (!value.scope.is_empty()).then(|| value.scope.join(" ")),
+            client_id: value.client_id,
+            token_type: value.token_type,
+            exp: value.exp,
+            iat: value.iat,
+            nbf: value.nbf,
+            sub: value.sub,
+            aud: value.aud,
+            iss: value.iss,
+            cnf: match (value.jkt, value.x5t_s256) {
+                (Some(jkt), _) => Some(serde_json::json!({ "jkt": jkt })),
+                (None, Some(x5t)) => Some(serde_json::json!({ "x5t#S256": x5t })),
+                (None, None) => None,
+            },
+            // Nest the prior actors from the innermost outwards.
+            act: value.act.into_iter().rev().fold(None, |prior, sub| {
+                Some(match prior {
+                    Some(prior) => serde_json::json!({ "sub": sub, "act": prior }),
+                    None => serde_json::json!({ "sub": sub }),
+                })
+            }),
+            authorization_details: value
+                .authorization_details
+                .and_then(|details| serde_json::from_str(&details).ok()),
+        }
+    }
+}
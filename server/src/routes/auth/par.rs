@@ -0,0 +1,142 @@
+use super::access::required;
+use super::authorize::optional_scope_deserializer;
+use crate::extract::client::AuthenticatedClient;
+use crate::{Handler, ServerError};
+use application::services::{
+    DependOnPushAuthorizationRequestService, PushAuthorizationRequestService,
+};
+use application::transfer::token::{
+    AuthorizationParamsDto, CreateAuthorizeTokenDto, PushedAuthorizationRequestDto,
+    SignedAuthorizeTokenDto,
+};
+use axum::{
+    extract::State,
+    http::{header::CACHE_CONTROL, HeaderMap, HeaderValue, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Pushed Authorization Request Endpoint.
+///
+/// The client is authenticated like at the token endpoint,
+/// and the returned `request_uri` is passed to the authorization endpoint with the `client_id`.
+///
+/// Defined in [RFC9126 Section 2](https://datatracker.ietf.org/doc/html/rfc9126#section-2)
+pub async fn push_authorization_request(
+    State(handler): State<Handler>,
+    AuthenticatedClient {
+        client_id, form, ..
+    }: AuthenticatedClient<PushedAuthorizationRequest>,
+) -> Result<impl IntoResponse, ServerError> {
+    let PushedAuthorizationRequest {
+        response_type,
+        redirect_uri,
+        scope,
+        state,
+        code_challenge,
+        code_challenge_method,
+        nonce,
+        response_mode,
+        resource,
+        authorization_details,
+        request,
+        request_uri,
+    } = form;
+
+    // https://datatracker.ietf.org/doc/html/rfc9126#section-2.1
+    if request_uri.is_some() {
+        return Err(ServerError::InvalidValue {
+            method: "invalid_request",
+            value: "`request_uri` must not be pushed.".to_string(),
+        });
+    }
+
+    let pushed = match request {
+        // https://datatracker.ietf.org/doc/html/rfc9126#section-3
+        Some(request) => {
+            handler
+                .push_authorization_request_service()
+                .push_signed(SignedAuthorizeTokenDto {
+                    client_id,
+                    request: Some(request),
+                    request_uri: None,
+                    params: AuthorizationParamsDto {
+                        response_type,
+                        redirect_uri,
+                        scope,
+                        state,
+                        code_challenge,
+                        code_challenge_method,
+                        nonce,
+                        response_mode,
+                        resource,
+                        authorization_details,
+                    },
+                })
+                .await?
+        }
+        None => {
+            handler
+                .push_authorization_request_service()
+                .push(CreateAuthorizeTokenDto {
+                    response_type: required(response_type, "response_type")?,
+                    client_id,
+                    redirect_uri,
+                    scope: required(scope, "scope")?,
+                    state: required(state, "state")?,
+                    code_challenge: required(code_challenge, "code_challenge")?,
+                    code_challenge_method: required(
+                        code_challenge_method,
+                        "code_challenge_method",
+                    )?,
+                    nonce,
+                    response_mode,
+                    resource,
+                    authorization_details,
+                })
+                .await?
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+
+    Ok((
+        StatusCode::CREATED,
+        headers,
+        Json(PushedAuthorizationResponse::from(pushed)),
+    ))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PushedAuthorizationRequest {
+    pub response_type: Option<String>,
+    pub redirect_uri: Option<String>,
+    #[serde(default, deserialize_with = "optional_scope_deserializer")]
+    pub scope: Option<Vec<String>>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
+    pub response_mode: Option<String>,
+    pub resource: Option<String>,
+    pub authorization_details: Option<String>,
+    pub request: Option<String>,
+    pub request_uri: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PushedAuthorizationResponse {
+    pub request_uri: String,
+    pub expires_in: i64,
+}
+
+impl From<PushedAuthorizationRequestDto> for PushedAuthorizationResponse {
+    fn from(value: PushedAuthorizationRequestDto) -> Self {
+        Self {
+            request_uri: value.request_uri,
+            expires_in: value.expires_in,
+        }
+    }
+}
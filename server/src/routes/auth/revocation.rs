@@ -0,0 +1,32 @@
+use crate::extract::client::AuthenticatedClient;
+use crate::{Handler, ServerError};
+use application::services::{DependOnRevokeTokenService, RevokeTokenService};
+use application::transfer::token::RevokeTokenDto;
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use serde::Deserialize;
+
+/// Revocation Endpoint.
+///
+/// Responds with `200 OK` even if the token was unknown.
+///
+/// Defined in [RFC7009 Section 2](https://datatracker.ietf.org/doc/html/rfc7009#section-2)
+pub async fn revoke(
+    State(handler): State<Handler>,
+    AuthenticatedClient {
+        client_id, form, ..
+    }: AuthenticatedClient<RevocationRequest>,
+) -> Result<impl IntoResponse, ServerError> {
+    let revoke = RevokeTokenDto {
+        client_id,
+        token: form.token,
+        token_type_hint: form.token_type_hint,
+    };
+    handler.revoke_token_service().revoke(revoke).await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RevocationRequest {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+}
@@ -2,7 +2,8 @@ use std::str::FromStr;
 
 use crate::ServerError;
 use application::transfer::client::{
-    GrantTypeDto, RegisterClientDto, ResponseTypeDto, ScopeDto, TokenEndPointAuthMethodDto,
+    AccessTokenFormatDto, GrantTypeDto, RegisterClientDto, ResponseTypeDto, ScopeDto,
+    TlsClientAuthSubjectDto, TokenEndPointAuthMethodDto,
 };
 use kernel::external::Uuid;
 use serde::de::Error;
@@ -27,6 +28,27 @@ pub struct RegistrationForm {
     policy_uri: String,
     jwks_uri: Option<String>, // ─┬─ MUST NOT both be present in the same request or response.
     jwks: Option<String>,     // ─┘
+    // RFC9068 JWT Profile for OAuth 2.0 Access Tokens
+    #[serde(default)]
+    access_token_format: AccessTokenFormat,
+    // RFC9126 OAuth 2.0 Pushed Authorization Requests
+    #[serde(default)]
+    require_pushed_authorization_requests: bool,
+    // RFC9101 JWT-Secured Authorization Request
+    #[serde(default)]
+    require_signed_request_object: bool,
+    #[serde(default)]
+    request_uris: Vec<String>,
+    // RFC8705 OAuth 2.0 Mutual-TLS Client Authentication
+    // Only one of them may be present.
+    tls_client_auth_subject_dn: Option<String>,
+    tls_client_auth_san_dns: Option<String>,
+    tls_client_auth_san_uri: Option<String>,
+    tls_client_auth_san_ip: Option<String>,
+    tls_client_auth_san_email: Option<String>,
+    // RFC8693 OAuth 2.0 Token Exchange
+    #[serde(default)]
+    token_exchange_audiences: Vec<String>,
 }
 
 impl RegistrationForm {
@@ -46,7 +68,51 @@ impl RegistrationForm {
             policy_uri,
             jwks_uri,
             jwks,
+            access_token_format,
+            require_pushed_authorization_requests,
+            require_signed_request_object,
+            request_uris,
+            tls_client_auth_subject_dn,
+            tls_client_auth_san_dns,
+            tls_client_auth_san_uri,
+            tls_client_auth_san_ip,
+            tls_client_auth_san_email,
+            token_exchange_audiences,
         } = self;
+
+        let mut subjects = [
+            tls_client_auth_subject_dn.map(TlsClientAuthSubjectDto::SubjectDn),
+            tls_client_auth_san_dns.map(TlsClientAuthSubjectDto::SanDns),
+            tls_client_auth_san_uri.map(TlsClientAuthSubjectDto::SanUri),
+            tls_client_auth_san_ip.map(TlsClientAuthSubjectDto::SanIp),
+            tls_client_auth_san_email.map(TlsClientAuthSubjectDto::SanEmail),
+        ]
+        .into_iter()
+        .flatten();
+        let tls_client_auth_subject = subjects.next();
+        if subjects.next().is_some() {
+            return Err(ServerError::InvalidValue {
+                method: "convert_dto",
+                value: "only one `tls_client_auth_*` subject may be registered.".to_string(),
+            });
+        }
+        if matches!(tepam, TokenEndPointAuthMethod::TlsClientAuth)
+            && tls_client_auth_subject.is_none()
+        {
+            return Err(ServerError::InvalidValue {
+                method: "convert_dto",
+                value: "`tls_client_auth` requires a `tls_client_auth_*` subject.".to_string(),
+            });
+        }
+
+        // RFC9101 Section 10.4.1: The server only fetches request objects over TLS.
+        if let Some(uri) = request_uris.iter().find(|uri| !uri.starts_with("https://")) {
+            return Err(ServerError::InvalidValue {
+                method: "convert_dto",
+                value: format!("`request_uris` must use https, but `{}` does not.", uri),
+            });
+        }
+
         Ok(RegisterClientDto {
             name,
             client_uri,
@@ -63,6 +129,12 @@ impl RegistrationForm {
             contacts,
             jwks,
             jwks_uri,
+            access_token_format: access_token_format.into(),
+            require_pushed_authorization_requests,
+            require_signed_request_object,
+            request_uris,
+            tls_client_auth_subject,
+            token_exchange_audiences,
         })
     }
 }
@@ -73,6 +145,9 @@ pub enum TokenEndPointAuthMethod {
     ClientSecretBasic,
     // OpenID Connect Core 1.0 §9 Client Authentication
     PrivateKeyJWT,
+    // RFC8705 §2 Mutual-TLS Client Authentication
+    TlsClientAuth,
+    SelfSignedTlsClientAuth,
     None,
 }
 
@@ -92,6 +167,8 @@ impl FromStr for TokenEndPointAuthMethod {
             "client_secret_post" => Self::ClientSecretPost,
             "client_secret_basic" => Self::ClientSecretBasic,
             "private_key_jwt" => Self::PrivateKeyJWT,
+            "tls_client_auth" => Self::TlsClientAuth,
+            "self_signed_tls_client_auth" => Self::SelfSignedTlsClientAuth,
             "none" => Self::None,
             _ => Self::default(),
         })
@@ -104,6 +181,8 @@ impl From<TokenEndPointAuthMethod> for TokenEndPointAuthMethodDto {
             TokenEndPointAuthMethod::ClientSecretPost => Self::ClientSecretPost,
             TokenEndPointAuthMethod::ClientSecretBasic => Self::ClientSecretBasic,
             TokenEndPointAuthMethod::PrivateKeyJWT => Self::PrivateKeyJWT,
+            TokenEndPointAuthMethod::TlsClientAuth => Self::TlsClientAuth,
+            TokenEndPointAuthMethod::SelfSignedTlsClientAuth => Self::SelfSignedTlsClientAuth,
             TokenEndPointAuthMethod::None => Self::None,
         }
     }
@@ -120,6 +199,49 @@ impl<'de> Deserialize<'de> for TokenEndPointAuthMethod {
     }
 }
 
+#[derive(Debug, Default)]
+pub enum AccessTokenFormat {
+    #[default]
+    Opaque,
+    Jwt,
+}
+
+impl FromStr for AccessTokenFormat {
+    type Err = ServerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "opaque" => Self::Opaque,
+            "jwt" => Self::Jwt,
+            _ => {
+                return Err(ServerError::InvalidValue {
+                    method: "from_str in access token format",
+                    value: s.to_string(),
+                })
+            }
+        })
+    }
+}
+
+impl From<AccessTokenFormat> for AccessTokenFormatDto {
+    fn from(value: AccessTokenFormat) -> Self {
+        match value {
+            AccessTokenFormat::Opaque => Self::Opaque,
+            AccessTokenFormat::Jwt => Self::Jwt,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AccessTokenFormat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_str(Deserialize::deserialize(deserializer)?)
+            .map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
 #[derive(Debug)]
 pub enum GrantType {
     AuthorizationCode,
@@ -129,6 +251,8 @@ pub enum GrantType {
     RefreshToken,
     JWTBearer,
     Saml2Bearer,
+    DeviceCode,
+    TokenExchange,
 }
 
 impl Default for GrantType {
@@ -151,6 +275,8 @@ impl FromStr for GrantType {
             "refresh_token" => Self::RefreshToken,
             "urn:ietf:params:oauth:grant-type:jwt-bearer" => Self::JWTBearer,
             "urn:ietf:params:oauth:grant-type:saml2-bearer" => Self::Saml2Bearer,
+            "urn:ietf:params:oauth:grant-type:device_code" => Self::DeviceCode,
+            "urn:ietf:params:oauth:grant-type:token-exchange" => Self::TokenExchange,
             _ => Self::default(), // Here it is.
         })
     }
@@ -166,6 +292,8 @@ impl From<GrantType> for GrantTypeDto {
             GrantType::RefreshToken => Self::RefreshToken,
             GrantType::JWTBearer => Self::JWTBearer,
             GrantType::Saml2Bearer => Self::Saml2Bearer,
+            GrantType::DeviceCode => Self::DeviceCode,
+            GrantType::TokenExchange => Self::TokenExchange,
         }
     }
 }
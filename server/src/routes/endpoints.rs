@@ -0,0 +1,15 @@
+//! Paths the router mounts the endpoints at.
+//!
+//! They are shared with the server metadata, so that it always points to the real endpoints.
+
+pub const AUTHORIZATION: &str = "/clients/authorize";
+pub const TOKEN: &str = "/token";
+pub const INTROSPECTION: &str = "/introspect";
+pub const REVOCATION: &str = "/revoke";
+pub const USERINFO: &str = "/userinfo";
+pub const DEVICE_AUTHORIZATION: &str = "/device_authorization";
+pub const DEVICE_VERIFICATION: &str = "/device";
+pub const PUSHED_AUTHORIZATION_REQUEST: &str = "/par";
+pub const JWKS: &str = "/.well-known/jwks.json";
+pub const AUTHORIZATION_SERVER_METADATA: &str = "/.well-known/oauth-authorization-server";
+pub const OPENID_CONFIGURATION: &str = "/.well-known/openid-configuration";
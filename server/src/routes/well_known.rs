@@ -0,0 +1,208 @@
+use super::endpoints;
+use crate::{Handler, ServerError};
+use application::services::{DependOnGetJwkSetService, GetJwkSetService};
+use application::ApplicationError;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use kernel::interfaces::repository::{ClientRegistry, DependOnClientRegistry};
+use kernel::prelude::entities::{
+    ClientName, CodeChallenge, GrantType, Issuer, ResponseMode, ResponseType, ScopeMethod,
+    SigningAlgorithm, TokenEndPointAuthMethod, CLIENT_ASSERTION_ALGORITHMS,
+    DPOP_SIGNING_ALGORITHMS,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// JWK Set of the keys this server signs tokens with.
+///
+/// See [RFC7517 Section 5](https://datatracker.ietf.org/doc/html/rfc7517#section-5)
+pub async fn jwks(State(handler): State<Handler>) -> Result<impl IntoResponse, ServerError> {
+    let jwks = handler.get_jwk_set_service().jwks().await?;
+    let keys = jwks
+        .keys
+        .iter()
+        .map(|key| serde_json::from_str::<Value>(key))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ServerError::Serde(anyhow::Error::new(e)))?;
+    Ok(Json(JwkSetResponse { keys }))
+}
+
+#[derive(Serialize)]
+struct JwkSetResponse {
+    keys: Vec<Value>,
+}
+
+/// Authorization Server Metadata.
+///
+/// See [RFC8414 Section 3](https://datatracker.ietf.org/doc/html/rfc8414#section-3)
+pub async fn authorization_server_metadata(
+    State(handler): State<Handler>,
+) -> Result<impl IntoResponse, ServerError> {
+    Ok(Json(ServerMetadata::new(&handler, None).await?))
+}
+
+/// OpenID Provider Metadata.
+///
+/// See [OpenID Connect Discovery 1.0 Section 3](https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata)
+pub async fn openid_configuration(
+    State(handler): State<Handler>,
+) -> Result<impl IntoResponse, ServerError> {
+    let provider = OpenIdProviderMetadata {
+        userinfo_endpoint: format!("{}{}", Issuer::default().as_ref(), endpoints::USERINFO),
+        subject_types_supported: vec!["public"],
+        id_token_signing_alg_values_supported: vec![SigningAlgorithm::default()
+            .as_ref()
+            .to_string()],
+        claims_supported: vec![
+            "iss",
+            "sub",
+            "aud",
+            "exp",
+            "iat",
+            "auth_time",
+            "nonce",
+            "name",
+            "preferred_username",
+            "email",
+            "email_verified",
+        ],
+    };
+    Ok(Json(ServerMetadata::new(&handler, Some(provider)).await?))
+}
+
+#[derive(Serialize)]
+struct ServerMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    introspection_endpoint: String,
+    revocation_endpoint: String,
+    device_authorization_endpoint: String,
+    pushed_authorization_request_endpoint: String,
+    require_pushed_authorization_requests: bool,
+    request_parameter_supported: bool,
+    request_uri_parameter_supported: bool,
+    require_signed_request_object: bool,
+    request_object_signing_alg_values_supported: Vec<Value>,
+    jwks_uri: String,
+    scopes_supported: Vec<String>,
+    response_types_supported: Vec<&'static str>,
+    response_modes_supported: Vec<&'static str>,
+    authorization_signing_alg_values_supported: Vec<String>,
+    grant_types_supported: Vec<&'static str>,
+    token_endpoint_auth_methods_supported: Vec<&'static str>,
+    token_endpoint_auth_signing_alg_values_supported: Vec<Value>,
+    code_challenge_methods_supported: Vec<&'static str>,
+    introspection_endpoint_auth_methods_supported: Vec<&'static str>,
+    revocation_endpoint_auth_methods_supported: Vec<&'static str>,
+    dpop_signing_alg_values_supported: Vec<Value>,
+    tls_client_certificate_bound_access_tokens: bool,
+    #[serde(flatten)]
+    provider: Option<OpenIdProviderMetadata>,
+}
+
+#[derive(Serialize)]
+struct OpenIdProviderMetadata {
+    userinfo_endpoint: String,
+    subject_types_supported: Vec<&'static str>,
+    id_token_signing_alg_values_supported: Vec<String>,
+    claims_supported: Vec<&'static str>,
+}
+
+impl ServerMetadata {
+    async fn new(
+        handler: &Handler,
+        provider: Option<OpenIdProviderMetadata>,
+    ) -> Result<Self, ServerError> {
+        let issuer = Issuer::default();
+        let endpoint = |path: &str| format!("{}{}", issuer.as_ref(), path);
+
+        // Scopes are registered per client, so the ones of Stellar itself are published.
+        let mut scopes_supported: Vec<String> = handler
+            .client_registry()
+            .find_by_name(&ClientName::new("Stellar"))
+            .await
+            .map_err(ApplicationError::from)?
+            .map(|stellar| {
+                stellar
+                    .scopes()
+                    .iter()
+                    .map(|(method, _)| method.as_ref().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        for scope in [
+            ScopeMethod::OPENID,
+            ScopeMethod::PROFILE,
+            ScopeMethod::EMAIL,
+        ] {
+            if !scopes_supported.iter().any(|supported| supported == scope) {
+                scopes_supported.push(scope.to_string());
+            }
+        }
+
+        let token_endpoint_auth_signing_alg_values_supported = CLIENT_ASSERTION_ALGORITHMS
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ServerError::Serde(anyhow::Error::new(e)))?;
+
+        let dpop_signing_alg_values_supported = DPOP_SIGNING_ALGORITHMS
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ServerError::Serde(anyhow::Error::new(e)))?;
+
+        Ok(Self {
+            authorization_endpoint: endpoint(endpoints::AUTHORIZATION),
+            token_endpoint: endpoint(endpoints::TOKEN),
+            introspection_endpoint: endpoint(endpoints::INTROSPECTION),
+            revocation_endpoint: endpoint(endpoints::REVOCATION),
+            device_authorization_endpoint: endpoint(endpoints::DEVICE_AUTHORIZATION),
+            pushed_authorization_request_endpoint: endpoint(
+                endpoints::PUSHED_AUTHORIZATION_REQUEST,
+            ),
+            // Only clients registered with the flag are required to push.
+            require_pushed_authorization_requests: false,
+            request_parameter_supported: true,
+            request_uri_parameter_supported: true,
+            // Same as above, per client.
+            require_signed_request_object: false,
+            // Request objects are verified with the client's keys, like client assertions.
+            request_object_signing_alg_values_supported:
+                token_endpoint_auth_signing_alg_values_supported.clone(),
+            jwks_uri: endpoint(endpoints::JWKS),
+            scopes_supported,
+            response_types_supported: ResponseType::SUPPORTED.iter().map(AsRef::as_ref).collect(),
+            response_modes_supported: ResponseMode::SUPPORTED.iter().map(AsRef::as_ref).collect(),
+            // JWT secured responses are signed with the same keys as ID Tokens.
+            // https://openid.net/specs/oauth-v2-jarm.html#name-authorization-server-metada
+            authorization_signing_alg_values_supported: vec![SigningAlgorithm::default()
+                .as_ref()
+                .to_string()],
+            grant_types_supported: GrantType::SUPPORTED.iter().map(AsRef::as_ref).collect(),
+            token_endpoint_auth_methods_supported: TokenEndPointAuthMethod::SUPPORTED
+                .iter()
+                .map(AsRef::as_ref)
+                .collect(),
+            token_endpoint_auth_signing_alg_values_supported,
+            code_challenge_methods_supported: CodeChallenge::SUPPORTED_METHODS.to_vec(),
+            // Public clients cannot introspect tokens.
+            introspection_endpoint_auth_methods_supported: TokenEndPointAuthMethod::SUPPORTED
+                .iter()
+                .filter(|method| !matches!(method, TokenEndPointAuthMethod::None))
+                .map(AsRef::as_ref)
+                .collect(),
+            revocation_endpoint_auth_methods_supported: TokenEndPointAuthMethod::SUPPORTED
+                .iter()
+                .map(AsRef::as_ref)
+                .collect(),
+            dpop_signing_alg_values_supported,
+            // Tokens are bound whenever the client presents a certificate at the token endpoint.
+            tls_client_certificate_bound_access_tokens: true,
+            provider,
+            issuer: issuer.into(),
+        })
+    }
+}